@@ -1,5 +1,8 @@
 use super::*;
-use std::marker::PhantomData;
+use std::{
+    marker::PhantomData,
+    ops::{BitOr, BitOrAssign},
+};
 
 /// Defines the stride of each vbo item.
 /// ```
@@ -22,11 +25,114 @@ pub enum BufferStorageType {
     Dynamic,
 }
 
+/// Describes the roles that a [`BufferId`] may serve.
+/// Usages can be combined with `|`.
+/// ```
+/// use mepeyew::BufferUsage;
+///
+/// let usage = BufferUsage::STORAGE | BufferUsage::VERTEX | BufferUsage::COPY_SRC;
+/// assert!(usage.contains(BufferUsage::VERTEX));
+/// ```
+#[derive(Default, Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct BufferUsage(u32);
+
+impl BufferUsage {
+    /// Bind with [`PassStep::add_vertex_buffer`].
+    pub const VERTEX: Self = Self(1 << 0);
+    /// Bind with [`PassStep::set_index_buffer`].
+    pub const INDEX: Self = Self(1 << 1);
+    /// Bind with [`ShaderUniformType::GenericUniformBuffer`].
+    pub const UNIFORM: Self = Self(1 << 2);
+    /// Bind with [`ShaderUniformType::GenericShaderStorageBuffer`] or
+    /// [`ShaderUniformType::GenericShaderStorageBufferReadOnly`].
+    pub const STORAGE: Self = Self(1 << 3);
+    /// Allow the buffer to be read back using [`Submit::sync_buffer`] or copied into a texture
+    /// using [`Submit::copy_buffer_to_texture`].
+    pub const COPY_SRC: Self = Self(1 << 4);
+    /// Allow the buffer to be written to using [`Submit::transfer_into_buffer`] or
+    /// [`Submit::copy_texture_to_buffer`], regardless of the [`BufferStorageType`].
+    pub const COPY_DST: Self = Self(1 << 5);
+
+    pub fn empty() -> Self {
+        Self(0)
+    }
+
+    pub fn contains(&self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    pub fn bits(&self) -> u32 {
+        self.0
+    }
+}
+
+impl BitOr for BufferUsage {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for BufferUsage {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0;
+    }
+}
+
+/// Anything that can be bound as a vertex buffer.
+/// You shouldn't need to construct this yourself since [`VertexBufferId`] and [`BufferId`] both
+/// convert into it.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum GenericVertexBufferId {
+    Vertex(VertexBufferId),
+    Buffer(BufferId),
+}
+
+impl From<VertexBufferId> for GenericVertexBufferId {
+    fn from(vbo: VertexBufferId) -> Self {
+        Self::Vertex(vbo)
+    }
+}
+
+impl From<BufferId> for GenericVertexBufferId {
+    fn from(buffer: BufferId) -> Self {
+        Self::Buffer(buffer)
+    }
+}
+
+/// Anything that can be bound as an index buffer.
+/// You shouldn't need to construct this yourself since [`IndexBufferId`] and [`BufferId`] both
+/// convert into it.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum GenericIndexBufferId {
+    Index(IndexBufferId),
+    Buffer(BufferId),
+}
+
+impl From<IndexBufferId> for GenericIndexBufferId {
+    fn from(ibo: IndexBufferId) -> Self {
+        Self::Index(ibo)
+    }
+}
+
+impl From<BufferId> for GenericIndexBufferId {
+    fn from(buffer: BufferId) -> Self {
+        Self::Buffer(buffer)
+    }
+}
+
 /// The expected type of all vertex buffers.
 pub type VertexBufferElement = f32;
 /// The expected type of all index buffers.
 pub type IndexBufferElement = u32;
 
+/// Currently has extra extension options.
+#[derive(Default, Debug, Clone)]
+pub struct NewBufferExt {}
+/// Currently has extra extension options.
+#[derive(Default, Debug, Clone)]
+pub struct ReadSyncedBufferExt {}
 /// Currently has extra extension options.
 #[derive(Default, Debug, Clone)]
 pub struct NewVertexBufferExt {}
@@ -50,6 +156,52 @@ pub struct UniformBufferTypeGuard<T: Copy>(pub UniformBufferId, PhantomData<T>);
 pub struct DynamicUniformBufferTypeGuard<T: Copy>(pub DynamicUniformBufferId, PhantomData<T>);
 
 impl Context {
+    /// Create a buffer that can serve any of the roles given by `usage`.
+    /// Such a buffer is accepted everywhere the specialized buffer ids are.
+    pub fn new_buffer<T: Copy>(
+        &mut self,
+        data: &[T],
+        usage: BufferUsage,
+        storage_type: BufferStorageType,
+        ext: Option<NewBufferExt>,
+    ) -> GResult<BufferId> {
+        match self {
+            Self::Vulkan(vk) => vk.new_buffer(data, usage, storage_type, ext),
+            Self::WebGpu(wgpu) => wgpu.new_buffer(data, usage, storage_type, ext),
+        }
+    }
+
+    /// Read from a synced buffer after rendering.
+    /// Sync a buffer using [`Submit::sync_buffer`].
+    ///
+    /// This method is **not** compatible with WebGpu.
+    /// Use [`Context::async_read_synced_buffer`] instead.
+    pub fn read_synced_buffer(
+        &self,
+        buffer: BufferId,
+        ext: Option<ReadSyncedBufferExt>,
+    ) -> GResult<Vec<u8>> {
+        match self {
+            Self::Vulkan(vk) => vk.read_synced_buffer(buffer, ext),
+            Self::WebGpu(wgpu) => wgpu.read_synced_buffer(buffer, ext),
+        }
+    }
+
+    /// Read from a synced buffer after rendering.
+    /// Sync a buffer using [`Submit::sync_buffer`].
+    ///
+    /// This method is compatible with WebGpu.
+    pub async fn async_read_synced_buffer(
+        &self,
+        buffer: BufferId,
+        ext: Option<ReadSyncedBufferExt>,
+    ) -> GResult<Vec<u8>> {
+        match self {
+            Self::Vulkan(vk) => vk.async_read_synced_buffer(buffer, ext).await,
+            Self::WebGpu(wgpu) => wgpu.async_read_synced_buffer(buffer, ext).await,
+        }
+    }
+
     pub fn new_vertex_buffer(
         &mut self,
         data: &[VertexBufferElement],
@@ -0,0 +1,596 @@
+use super::*;
+use naga_translation::{NagaTranslationInput, NagaTranslationStage};
+
+const IBL_DEFAULT_CUBEMAP_SIZE: usize = 512;
+const IBL_DEFAULT_IRRADIANCE_SIZE: usize = 32;
+const IBL_DEFAULT_PREFILTERED_SIZE: usize = 128;
+const IBL_DEFAULT_PREFILTERED_MIP_LEVELS: u32 = 5;
+const IBL_DEFAULT_BRDF_LUT_SIZE: usize = 512;
+const IBL_DEFAULT_SAMPLE_COUNT: u32 = 1024;
+
+const IBL_FORMAT: AttachmentImageColorFormat = AttachmentImageColorFormat::R16G16B16A16SFloat;
+
+/// Allows for the configuration of:
+/// - Output sizes
+/// - Prefiltered mip levels
+/// - Sample counts
+#[derive(Default, Debug, Clone)]
+pub struct IblExtensionNewIblEnvironmentExt {
+    /// The size of each face of the environment cubemap.
+    /// Defaults to 512.
+    pub cubemap_size: Option<usize>,
+    /// The size of each face of the irradiance cubemap.
+    /// Defaults to 32.
+    pub irradiance_size: Option<usize>,
+    /// The size of each face of the first mip level of the prefiltered cubemap.
+    /// Defaults to 128.
+    pub prefiltered_size: Option<usize>,
+    /// The number of roughness levels in the prefiltered cubemap, one per mip level.
+    /// Defaults to 5.
+    pub prefiltered_mip_levels: Option<u32>,
+    /// The width and height of the BRDF lookup table.
+    /// Defaults to 512.
+    pub brdf_lut_size: Option<usize>,
+    /// The number of samples taken per texel when convolving.
+    /// Defaults to 1024.
+    pub sample_count: Option<u32>,
+}
+
+/// The output of [`Context::new_ibl_environment`].
+/// Every image is an `R16G16B16A16SFloat` attachment image with a fixed size,
+/// ready to be bound with [`ShaderUniformType::AttachmentTexture`].
+#[derive(Debug, Clone, Copy)]
+pub struct IblEnvironment {
+    /// The environment as a cubemap with a full mip chain.
+    pub cubemap: AttachmentImageId,
+    /// The cosine weighted irradiance cubemap for diffuse lighting.
+    /// Multiply by the albedo to get the diffuse term.
+    pub irradiance: AttachmentImageId,
+    /// The GGX prefiltered cubemap for specular lighting.
+    /// Sample at mip level `roughness * (prefiltered_mip_levels - 1)`.
+    pub prefiltered: AttachmentImageId,
+    pub prefiltered_mip_levels: u32,
+    /// The split sum BRDF lookup table, sampled with `(n_dot_v, roughness)`.
+    /// The red channel holds the scale and the green channel holds the bias applied to F0.
+    pub brdf_lut: AttachmentImageId,
+}
+
+//  Indexed by `mip_level * 6 + face` as a dynamic uniform buffer.
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct IblFace {
+    face: u32,
+    roughness: f32,
+    sample_count: u32,
+    padding: u32,
+}
+
+const IBL_VERTEX_SHADER: &str = r#"
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+};
+
+@vertex
+fn main(@location(0) position: vec2<f32>) -> VertexOutput {
+    var output: VertexOutput;
+    output.position = vec4<f32>(position, 0.0, 1.0);
+    //  Rows go from top to bottom.
+    output.uv = vec2<f32>(position.x * 0.5 + 0.5, 0.5 - position.y * 0.5);
+    return output;
+}
+"#;
+
+const IBL_COMMON: &str = r#"
+const PI: f32 = 3.14159265359;
+
+struct IblFace {
+    face: u32,
+    roughness: f32,
+    sample_count: u32,
+    padding: u32,
+};
+
+@group(0) @binding(0) var<uniform> ibl_face: IblFace;
+
+//  Faces are in the order of `CubemapTextureUpload`.
+fn cube_direction(face: u32, uv: vec2<f32>) -> vec3<f32> {
+    let s = uv.x * 2.0 - 1.0;
+    let t = uv.y * 2.0 - 1.0;
+    var direction = vec3<f32>(-s, -t, -1.0);
+    switch face {
+        case 0u: {
+            direction = vec3<f32>(1.0, -t, -s);
+        }
+        case 1u: {
+            direction = vec3<f32>(-1.0, -t, s);
+        }
+        case 2u: {
+            direction = vec3<f32>(s, 1.0, t);
+        }
+        case 3u: {
+            direction = vec3<f32>(s, -1.0, -t);
+        }
+        case 4u: {
+            direction = vec3<f32>(s, -t, 1.0);
+        }
+        default: {}
+    }
+    return normalize(direction);
+}
+
+fn hammersley(i: u32, count: u32) -> vec2<f32> {
+    return vec2<f32>(f32(i) / f32(count), f32(reverseBits(i)) * 2.3283064365386963e-10);
+}
+
+fn tangent_to_world(v: vec3<f32>, n: vec3<f32>) -> vec3<f32> {
+    var up = vec3<f32>(1.0, 0.0, 0.0);
+    if abs(n.z) < 0.999 {
+        up = vec3<f32>(0.0, 0.0, 1.0);
+    }
+    let tangent = normalize(cross(up, n));
+    let bitangent = cross(n, tangent);
+    return normalize(tangent * v.x + bitangent * v.y + n * v.z);
+}
+
+fn importance_sample_ggx(xi: vec2<f32>, roughness: f32) -> vec3<f32> {
+    let a = roughness * roughness;
+    let phi = 2.0 * PI * xi.x;
+    let cos_theta = sqrt((1.0 - xi.y) / (1.0 + (a * a - 1.0) * xi.y));
+    let sin_theta = sqrt(1.0 - cos_theta * cos_theta);
+    return vec3<f32>(cos(phi) * sin_theta, sin(phi) * sin_theta, cos_theta);
+}
+"#;
+
+const IBL_ENVIRONMENT_COMMON: &str = r#"
+@group(1) @binding(0) var environment: texture_cube<f32>;
+@group(1) @binding(1) var environment_sampler: sampler;
+
+//  Sample blurrier mip levels when there are fewer samples than texels to avoid aliasing.
+fn filtered_lod(pdf: f32) -> f32 {
+    let size = f32(textureDimensions(environment).x);
+    let texel_solid_angle = 4.0 * PI / (6.0 * size * size);
+    let sample_solid_angle = 1.0 / (f32(ibl_face.sample_count) * pdf + 0.0001);
+    return max(0.5 * log2(sample_solid_angle / texel_solid_angle) + 1.0, 0.0);
+}
+"#;
+
+const IBL_CUBEMAP_FRAGMENT_SHADER: &str = r#"
+@group(1) @binding(0) var equirectangular: texture_2d<f32>;
+
+//  Filtered by hand since 32 bit float textures cannot be filtered on every device.
+fn load_equirectangular(uv: vec2<f32>) -> vec4<f32> {
+    let size = vec2<i32>(textureDimensions(equirectangular));
+    let texel = uv * vec2<f32>(size) - 0.5;
+    let base = floor(texel);
+    let weight = texel - base;
+    //  Wrap horizontally and clamp vertically.
+    let x0 = (i32(base.x) % size.x + size.x) % size.x;
+    let x1 = (x0 + 1) % size.x;
+    let y0 = clamp(i32(base.y), 0, size.y - 1);
+    let y1 = clamp(i32(base.y) + 1, 0, size.y - 1);
+    let top = mix(
+        textureLoad(equirectangular, vec2<i32>(x0, y0), 0),
+        textureLoad(equirectangular, vec2<i32>(x1, y0), 0),
+        weight.x
+    );
+    let bottom = mix(
+        textureLoad(equirectangular, vec2<i32>(x0, y1), 0),
+        textureLoad(equirectangular, vec2<i32>(x1, y1), 0),
+        weight.x
+    );
+    return mix(top, bottom, weight.y);
+}
+
+@fragment
+fn main(@location(0) uv: vec2<f32>) -> @location(0) vec4<f32> {
+    let direction = cube_direction(ibl_face.face, uv);
+    let equirectangular_uv = vec2<f32>(
+        atan2(direction.z, direction.x) / (2.0 * PI) + 0.5,
+        acos(clamp(direction.y, -1.0, 1.0)) / PI
+    );
+    return vec4<f32>(load_equirectangular(equirectangular_uv).rgb, 1.0);
+}
+"#;
+
+const IBL_IRRADIANCE_FRAGMENT_SHADER: &str = r#"
+@fragment
+fn main(@location(0) uv: vec2<f32>) -> @location(0) vec4<f32> {
+    let normal = cube_direction(ibl_face.face, uv);
+    var irradiance = vec3<f32>(0.0);
+    for (var i = 0u; i < ibl_face.sample_count; i += 1u) {
+        //  Cosine weighted, so the average is already divided by pi.
+        let xi = hammersley(i, ibl_face.sample_count);
+        let phi = 2.0 * PI * xi.x;
+        let cos_theta = sqrt(1.0 - xi.y);
+        let sin_theta = sqrt(xi.y);
+        let direction = tangent_to_world(
+            vec3<f32>(cos(phi) * sin_theta, sin(phi) * sin_theta, cos_theta),
+            normal
+        );
+        let lod = filtered_lod(cos_theta / PI);
+        irradiance += textureSampleLevel(environment, environment_sampler, direction, lod).rgb;
+    }
+    return vec4<f32>(irradiance / f32(ibl_face.sample_count), 1.0);
+}
+"#;
+
+const IBL_PREFILTERED_FRAGMENT_SHADER: &str = r#"
+fn distribution_ggx(n_dot_h: f32, roughness: f32) -> f32 {
+    let a = roughness * roughness;
+    let a2 = a * a;
+    let d = n_dot_h * n_dot_h * (a2 - 1.0) + 1.0;
+    return a2 / (PI * d * d);
+}
+
+@fragment
+fn main(@location(0) uv: vec2<f32>) -> @location(0) vec4<f32> {
+    //  Assume that the view direction is the normal.
+    let n = cube_direction(ibl_face.face, uv);
+    let roughness = ibl_face.roughness;
+    var color = vec3<f32>(0.0);
+    var total_weight = 0.0;
+    for (var i = 0u; i < ibl_face.sample_count; i += 1u) {
+        let h = tangent_to_world(
+            importance_sample_ggx(hammersley(i, ibl_face.sample_count), roughness),
+            n
+        );
+        let l = normalize(2.0 * dot(n, h) * h - n);
+        let n_dot_l = dot(n, l);
+        if n_dot_l > 0.0 {
+            //  With the view direction as the normal, the pdf simplifies to d / 4.
+            let pdf = distribution_ggx(max(dot(n, h), 0.0), roughness) / 4.0;
+            let lod = select(filtered_lod(pdf), 0.0, roughness == 0.0);
+            color += textureSampleLevel(environment, environment_sampler, l, lod).rgb * n_dot_l;
+            total_weight += n_dot_l;
+        }
+    }
+    return vec4<f32>(color / max(total_weight, 0.0001), 1.0);
+}
+"#;
+
+const IBL_BRDF_LUT_FRAGMENT_SHADER: &str = r#"
+//  Uses k = a^2 / 2 rather than the remapping used for direct lighting.
+fn geometry_schlick_ggx(n_dot_v: f32, roughness: f32) -> f32 {
+    let k = roughness * roughness / 2.0;
+    return n_dot_v / (n_dot_v * (1.0 - k) + k);
+}
+
+@fragment
+fn main(@location(0) uv: vec2<f32>) -> @location(0) vec4<f32> {
+    let n_dot_v = max(uv.x, 0.0001);
+    let roughness = uv.y;
+    let v = vec3<f32>(sqrt(1.0 - n_dot_v * n_dot_v), 0.0, n_dot_v);
+    var scale = 0.0;
+    var bias = 0.0;
+    for (var i = 0u; i < ibl_face.sample_count; i += 1u) {
+        let h = importance_sample_ggx(hammersley(i, ibl_face.sample_count), roughness);
+        let l = normalize(2.0 * dot(v, h) * h - v);
+        let n_dot_l = max(l.z, 0.0);
+        let n_dot_h = max(h.z, 0.0);
+        let v_dot_h = max(dot(v, h), 0.0);
+        if n_dot_l > 0.0 {
+            let g = geometry_schlick_ggx(n_dot_v, roughness)
+                * geometry_schlick_ggx(n_dot_l, roughness);
+            let g_vis = g * v_dot_h / (n_dot_h * n_dot_v);
+            let fc = pow(1.0 - v_dot_h, 5.0);
+            scale += (1.0 - fc) * g_vis;
+            bias += fc * g_vis;
+        }
+    }
+    let sample_count = f32(ibl_face.sample_count);
+    return vec4<f32>(scale / sample_count, bias / sample_count, 0.0, 1.0);
+}
+"#;
+
+impl Context {
+    /// Preprocess an equirectangular environment texture, such as an HDR image loaded with
+    /// `new_texture_from_encoded`, for image based lighting.
+    /// Everything is rendered on the gpu with render passes and submitted synchronously.
+    /// Requires that the `ibl` feature is enabled for you project.
+    pub fn new_ibl_environment(
+        &mut self,
+        equirectangular: TextureId,
+        ext: Option<IblExtensionNewIblEnvironmentExt>,
+    ) -> GResult<IblEnvironment> {
+        let ext = ext.unwrap_or_default();
+
+        let cubemap_size = ext.cubemap_size.unwrap_or(IBL_DEFAULT_CUBEMAP_SIZE);
+        let irradiance_size = ext.irradiance_size.unwrap_or(IBL_DEFAULT_IRRADIANCE_SIZE);
+        let prefiltered_size = ext.prefiltered_size.unwrap_or(IBL_DEFAULT_PREFILTERED_SIZE);
+        let prefiltered_mip_levels = ext
+            .prefiltered_mip_levels
+            .unwrap_or(IBL_DEFAULT_PREFILTERED_MIP_LEVELS);
+        let brdf_lut_size = ext.brdf_lut_size.unwrap_or(IBL_DEFAULT_BRDF_LUT_SIZE);
+        let sample_count = ext.sample_count.unwrap_or(IBL_DEFAULT_SAMPLE_COUNT);
+
+        if cubemap_size == 0 || irradiance_size == 0 || prefiltered_size == 0 || brdf_lut_size == 0
+        {
+            Err(gpu_api_err!("ibl sizes cannot be zero"))?;
+        }
+        if prefiltered_mip_levels == 0 || prefiltered_mip_levels > prefiltered_size.ilog2() + 1 {
+            Err(gpu_api_err!(
+                "ibl prefiltered mip levels {} is out of range for size {}",
+                prefiltered_mip_levels,
+                prefiltered_size
+            ))?;
+        }
+        if sample_count == 0 {
+            Err(gpu_api_err!("ibl sample count cannot be zero"))?;
+        }
+
+        let cubemap = self.new_attachment_image(
+            cubemap_size,
+            cubemap_size,
+            AttachmentImageUsage::ColorAttachment,
+            Some(NewAttachmentImageExt {
+                color_format: Some(IBL_FORMAT),
+                mip_levels: Some(cubemap_size.ilog2() + 1),
+                enable_cubemap: Some(()),
+                fixed_size: Some(()),
+                ..Default::default()
+            }),
+        )?;
+        let irradiance = self.new_attachment_image(
+            irradiance_size,
+            irradiance_size,
+            AttachmentImageUsage::ColorAttachment,
+            Some(NewAttachmentImageExt {
+                color_format: Some(IBL_FORMAT),
+                enable_cubemap: Some(()),
+                fixed_size: Some(()),
+                ..Default::default()
+            }),
+        )?;
+        let prefiltered = self.new_attachment_image(
+            prefiltered_size,
+            prefiltered_size,
+            AttachmentImageUsage::ColorAttachment,
+            Some(NewAttachmentImageExt {
+                color_format: Some(IBL_FORMAT),
+                mip_levels: Some(prefiltered_mip_levels),
+                enable_cubemap: Some(()),
+                fixed_size: Some(()),
+                ..Default::default()
+            }),
+        )?;
+        let brdf_lut = self.new_attachment_image(
+            brdf_lut_size,
+            brdf_lut_size,
+            AttachmentImageUsage::ColorAttachment,
+            Some(NewAttachmentImageExt {
+                color_format: Some(IBL_FORMAT),
+                fixed_size: Some(()),
+                ..Default::default()
+            }),
+        )?;
+
+        let faces = (0..prefiltered_mip_levels)
+            .flat_map(|mip_level| {
+                let roughness = if prefiltered_mip_levels > 1 {
+                    mip_level as f32 / (prefiltered_mip_levels - 1) as f32
+                } else {
+                    0.0
+                };
+                (0..6).map(move |face| IblFace {
+                    face,
+                    roughness,
+                    sample_count,
+                    padding: 0,
+                })
+            })
+            .collect::<Vec<_>>();
+        let (faces_buffer, _) = self.new_dynamic_uniform_buffer(&faces, None)?;
+
+        //  A single triangle that covers the whole render area.
+        let vbo = self.new_vertex_buffer(
+            &[-1.0, -1.0, 3.0, -1.0, -1.0, 3.0],
+            BufferStorageType::Static,
+            None,
+        )?;
+
+        //  Sampling the environment cubemap reads across its whole mip chain.
+        let sampler = self.get_sampler(Some(GetSamplerExt {
+            mip_filter: MipSamplerFilter::Linear,
+            u_mode: SamplerMode::ClampToEdge,
+            v_mode: SamplerMode::ClampToEdge,
+            max_lod: Some((cubemap_size.ilog2() + 1) as f32),
+            ..Default::default()
+        }))?;
+
+        let faces_uniform = ShaderUniform {
+            set: 0,
+            binding: 0,
+            ty: ShaderUniformType::DynamicUniformBuffer(faces_buffer),
+        };
+        let environment_uniforms = [
+            faces_uniform.clone(),
+            ShaderUniform {
+                set: 1,
+                binding: 0,
+                ty: ShaderUniformType::AttachmentTexture(cubemap),
+            },
+            ShaderUniform {
+                set: 1,
+                binding: 1,
+                ty: ShaderUniformType::Sampler(sampler),
+            },
+        ];
+
+        let cubemap_program = self.new_ibl_program(
+            &[IBL_COMMON, IBL_CUBEMAP_FRAGMENT_SHADER],
+            &[
+                faces_uniform.clone(),
+                ShaderUniform {
+                    set: 1,
+                    binding: 0,
+                    ty: ShaderUniformType::Texture(equirectangular),
+                },
+            ],
+        )?;
+        let irradiance_program = self.new_ibl_program(
+            &[
+                IBL_COMMON,
+                IBL_ENVIRONMENT_COMMON,
+                IBL_IRRADIANCE_FRAGMENT_SHADER,
+            ],
+            &environment_uniforms,
+        )?;
+        let prefiltered_program = self.new_ibl_program(
+            &[
+                IBL_COMMON,
+                IBL_ENVIRONMENT_COMMON,
+                IBL_PREFILTERED_FRAGMENT_SHADER,
+            ],
+            &environment_uniforms,
+        )?;
+        let brdf_lut_program = self.new_ibl_program(
+            &[IBL_COMMON, IBL_BRDF_LUT_FRAGMENT_SHADER],
+            &[faces_uniform],
+        )?;
+
+        let mut submit = Submit::new();
+
+        submit.pass(self.new_ibl_cube_pass(
+            cubemap,
+            cubemap_size,
+            0,
+            cubemap_program,
+            vbo,
+            faces_buffer,
+        )?);
+        submit.generate_mipmaps(cubemap);
+        submit.pass(self.new_ibl_cube_pass(
+            irradiance,
+            irradiance_size,
+            0,
+            irradiance_program,
+            vbo,
+            faces_buffer,
+        )?);
+        for mip_level in 0..prefiltered_mip_levels {
+            submit.pass(self.new_ibl_cube_pass(
+                prefiltered,
+                prefiltered_size,
+                mip_level,
+                prefiltered_program,
+                vbo,
+                faces_buffer,
+            )?);
+        }
+
+        let mut pass = Pass::new(brdf_lut_size, brdf_lut_size, None);
+        let brdf_lut_attachment =
+            pass.add_attachment_color_image(brdf_lut, PassInputLoadOpColorType::Clear);
+        pass.add_step()
+            .add_vertex_buffer(vbo)
+            .add_program(brdf_lut_program)
+            .add_write_color(brdf_lut_attachment);
+        let mut pass_submit = PassSubmitData::new(self.compile_pass(&pass, None)?);
+        let mut step_submit = StepSubmitData::new();
+        step_submit
+            .draw(brdf_lut_program, 0, 3)
+            .set_viewport(ibl_viewport(brdf_lut_size))
+            .set_dynamic_uniform_buffer_index(faces_buffer, 0);
+        pass_submit
+            .step(step_submit)
+            .set_attachment_clear_color(brdf_lut_attachment, IBL_CLEAR_COLOR);
+        submit.pass(pass_submit);
+
+        self.submit(submit, Some(SubmitExt { sync: Some(()) }))?;
+
+        Ok(IblEnvironment {
+            cubemap,
+            irradiance,
+            prefiltered,
+            prefiltered_mip_levels,
+            brdf_lut,
+        })
+    }
+
+    fn new_ibl_program(
+        &mut self,
+        fragment_sources: &[&str],
+        uniforms: &[ShaderUniform],
+    ) -> GResult<ProgramId> {
+        let vs = self.naga_translate_shader_code(
+            NagaTranslationStage::Vertex,
+            NagaTranslationInput::Wgsl,
+            IBL_VERTEX_SHADER.as_bytes(),
+            Default::default(),
+        )?;
+        let fs = self.naga_translate_shader_code(
+            NagaTranslationStage::Fragment,
+            NagaTranslationInput::Wgsl,
+            fragment_sources.concat().as_bytes(),
+            Default::default(),
+        )?;
+        self.new_program(
+            &ShaderSet::shaders(&[
+                (ShaderType::Vertex(VertexBufferInput { args: vec![2] }), &vs),
+                (ShaderType::Fragment, &fs),
+            ]),
+            uniforms,
+            None,
+        )
+    }
+
+    //  Renders each face of a single mip level in its own step.
+    fn new_ibl_cube_pass(
+        &mut self,
+        image: AttachmentImageId,
+        size: usize,
+        mip_level: u32,
+        program: ProgramId,
+        vbo: VertexBufferId,
+        faces_buffer: DynamicUniformBufferId,
+    ) -> GResult<PassSubmitData> {
+        let (mip_size, _) = mip_level_extent(size, size, mip_level);
+
+        let mut pass = Pass::new(mip_size, mip_size, None);
+        let attachments = (0..6)
+            .map(|layer| {
+                pass.add_attachment_color_image_subresource(
+                    image,
+                    AttachmentImageSubresource { layer, mip_level },
+                    PassInputLoadOpColorType::Clear,
+                )
+            })
+            .collect::<Vec<_>>();
+        for &attachment in attachments.iter() {
+            pass.add_step()
+                .add_vertex_buffer(vbo)
+                .add_program(program)
+                .add_write_color(attachment);
+        }
+
+        let mut pass_submit = PassSubmitData::new(self.compile_pass(&pass, None)?);
+        for (face, &attachment) in attachments.iter().enumerate() {
+            let mut step_submit = StepSubmitData::new();
+            step_submit
+                .draw(program, 0, 3)
+                .set_viewport(ibl_viewport(mip_size))
+                .set_dynamic_uniform_buffer_index(faces_buffer, mip_level as usize * 6 + face);
+            pass_submit
+                .step(step_submit)
+                .set_attachment_clear_color(attachment, IBL_CLEAR_COLOR);
+        }
+        Ok(pass_submit)
+    }
+}
+
+const IBL_CLEAR_COLOR: ClearColor = ClearColor {
+    r: 0.0,
+    g: 0.0,
+    b: 0.0,
+    a: 1.0,
+};
+
+fn ibl_viewport(size: usize) -> DrawViewport {
+    DrawViewport {
+        x: 0.0,
+        y: 0.0,
+        width: size as f32,
+        height: size as f32,
+    }
+}
@@ -0,0 +1,107 @@
+use super::*;
+use image::DynamicImage;
+
+/// Allows for the configuration of:
+/// - Mipmap generation
+/// - sRGB decoding
+#[derive(Default, Debug, Clone)]
+pub struct ImageLoadingExtensionNewTextureFromEncodedExt {
+    /// Generate mipmaps through [`UploadTextureExt::generate_mipmaps`].
+    pub generate_mipmaps: Option<()>,
+    /// Treat 8 bit color images as sRGB, creating a [`TextureFormat::Rgba8Srgb`] texture.
+    pub srgb: Option<()>,
+}
+
+impl Context {
+    /// Decode a PNG, JPEG, or HDR image into a new texture.
+    /// Grayscale images become `R8` or `Rg8`, 8 bit color images become `Rgba`,
+    /// and everything else becomes `Rgba16F`.
+    /// Values beyond the range of a 16 bit float are clamped.
+    pub fn new_texture_from_encoded(
+        &mut self,
+        data: &[u8],
+        ext: Option<ImageLoadingExtensionNewTextureFromEncodedExt>,
+    ) -> GResult<TextureId> {
+        let ext = ext.unwrap_or_default();
+
+        let image = image::load_from_memory(data)
+            .map_err(|e| gpu_api_err!("image loading decode: {}", e))?;
+        let (width, height) = (image.width() as usize, image.height() as usize);
+
+        let (format, pixels) = match image {
+            DynamicImage::ImageLuma8(image) => (TextureFormat::R8, image.into_raw()),
+            DynamicImage::ImageLumaA8(image) => (TextureFormat::Rg8, image.into_raw()),
+            DynamicImage::ImageRgb8(_) | DynamicImage::ImageRgba8(_) => (
+                if ext.srgb.is_some() {
+                    TextureFormat::Rgba8Srgb
+                } else {
+                    TextureFormat::Rgba
+                },
+                image.into_rgba8().into_raw(),
+            ),
+            //  Unlike `Rgba32F`, this can be filtered everywhere.
+            image => (
+                TextureFormat::Rgba16F,
+                image
+                    .into_rgba32f()
+                    .into_raw()
+                    .into_iter()
+                    .flat_map(|value| f32_to_f16_bits(value).to_ne_bytes())
+                    .collect(),
+            ),
+        };
+
+        let texture = self.new_texture(
+            width,
+            height,
+            format,
+            Some(NewTextureExt {
+                enable_mipmaps: ext.generate_mipmaps,
+                ..Default::default()
+            }),
+        )?;
+        self.upload_texture(
+            texture,
+            &pixels,
+            Some(UploadTextureExt {
+                generate_mipmaps: ext.generate_mipmaps,
+                ..Default::default()
+            }),
+        )?;
+
+        Ok(texture)
+    }
+}
+
+//  Rounds to nearest even, clamping to the largest finite value instead of overflowing to infinity.
+fn f32_to_f16_bits(value: f32) -> u16 {
+    let bits = value.to_bits();
+    let sign = ((bits >> 16) & 0x8000) as u16;
+    let exponent = ((bits >> 23) & 0xff) as i32;
+    let mantissa = bits & 0x7f_ffff;
+
+    //  Infinity and NaN.
+    if exponent == 0xff {
+        return sign | 0x7c00 | if mantissa != 0 { 0x200 } else { 0 };
+    }
+
+    let half_exponent = exponent - 127 + 15;
+    if half_exponent >= 0x1f {
+        return sign | 0x7bff;
+    }
+    if half_exponent < -10 {
+        return sign;
+    }
+
+    //  Subnormal halves keep the implicit leading bit in their mantissa.
+    let (full_mantissa, shift) = if half_exponent <= 0 {
+        (mantissa | 0x80_0000, (14 - half_exponent) as u32)
+    } else {
+        (mantissa, 13)
+    };
+    let half = ((std::cmp::max(half_exponent, 0) as u32) << 10) | (full_mantissa >> shift);
+    let round_bit = 1 << (shift - 1);
+    let round_up =
+        full_mantissa & round_bit != 0 && (full_mantissa & (round_bit - 1) != 0 || half & 1 != 0);
+    sign | std::cmp::min(half + round_up as u32, 0x7bff) as u16
+}
@@ -16,6 +16,9 @@
 //! | `native_debug`                    | ✅        | 🆗        |
 //! | `memory_flush`                    | ✅        | 🆗        |
 //! | `naga_translation`                | ✅        | ✅        |
+//! | `texture_container`               | ✅        | ✅        |
+//! | `image_loading`                   | ✅        | ✅        |
+//! | `ibl`                             | ✅        | ✅        |
 //! | `webgpu_init_from_window`         | 🆗        | ✅        |
 //! | `webgpu_init`                     | 🆗        | ✅        |
 //! | `surface_extension`               | ✅        | ✅        |
@@ -37,6 +40,15 @@ pub mod surface;
 #[cfg(feature = "naga_translation")]
 pub mod naga_translation;
 
+#[cfg(feature = "texture_container")]
+pub mod texture_container;
+
+#[cfg(feature = "image_loading")]
+pub mod image_loading;
+
+#[cfg(feature = "ibl")]
+pub mod ibl;
+
 pub use compute::{
     CompileComputePassExt, ComputePass, ComputePassSubmitData, Dispatch, DispatchType,
     NewComputeProgramExt,
@@ -55,6 +67,15 @@ pub use surface::*;
 #[cfg(feature = "naga_translation")]
 pub use naga_translation::*;
 
+#[cfg(feature = "texture_container")]
+pub use texture_container::*;
+
+#[cfg(feature = "image_loading")]
+pub use image_loading::*;
+
+#[cfg(feature = "ibl")]
+pub use ibl::*;
+
 use super::*;
 
 #[derive(Default, Debug, Clone)]
@@ -103,6 +124,33 @@ impl Extensions {
         self
     }
 
+    /// Load KTX2 and DDS files into textures.
+    /// Invoke using [`Context::load_texture_container`].
+    /// Requires that the `texture_container` feature is enabled for you project.
+    #[cfg(feature = "texture_container")]
+    pub fn texture_container(&mut self) -> &mut Self {
+        self.extensions.push(Extension::TextureContainer);
+        self
+    }
+
+    /// Decode PNG, JPEG, and HDR images into textures via [`image`](https://github.com/image-rs/image).
+    /// Invoke using [`Context::new_texture_from_encoded`].
+    /// Requires that the `image_loading` feature is enabled for you project.
+    #[cfg(feature = "image_loading")]
+    pub fn image_loading(&mut self) -> &mut Self {
+        self.extensions.push(Extension::ImageLoading);
+        self
+    }
+
+    /// Preprocess equirectangular environment maps for image based lighting.
+    /// Invoke using [`Context::new_ibl_environment`].
+    /// Requires that the `ibl` feature is enabled for you project.
+    #[cfg(feature = "ibl")]
+    pub fn ibl(&mut self) -> &mut Self {
+        self.extensions.push(Extension::Ibl);
+        self
+    }
+
     /// Initialize the WebGpu Context.
     /// You will need to use [`Context::async_new`] to use this.
     pub fn webgpu_init(&mut self, init: WebGpuInit) -> &mut Self {
@@ -149,6 +197,9 @@ pub enum Extension {
     NativeDebug(NativeDebugConfiguration),
     MemoryFlush,
     NagaTranslation,
+    TextureContainer,
+    ImageLoading,
+    Ibl,
     WebGpuInitFromWindow(WebGpuInitFromWindow),
     WebGpuInit(WebGpuInit),
     Surface(SurfaceConfiguration),
@@ -0,0 +1,600 @@
+use super::*;
+
+/// Additional information for loading texture containers.
+/// This is currently empty.
+#[derive(Default, Debug)]
+pub struct TextureContainerExtensionLoadTextureContainerExt {}
+
+/// The container formats understood by [`Context::load_texture_container`].
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum TextureContainerFormat {
+    Ktx2,
+    Dds,
+}
+
+const KTX2_IDENTIFIER: [u8; 12] = [
+    0xab, 0x4b, 0x54, 0x58, 0x20, 0x32, 0x30, 0xbb, 0x0d, 0x0a, 0x1a, 0x0a,
+];
+const DDS_MAGIC: [u8; 4] = *b"DDS ";
+
+impl TextureContainerFormat {
+    /// Guess the container format from the first few bytes of `data`.
+    pub fn detect(data: &[u8]) -> Option<Self> {
+        if data.starts_with(&KTX2_IDENTIFIER) {
+            Some(TextureContainerFormat::Ktx2)
+        } else if data.starts_with(&DDS_MAGIC) {
+            Some(TextureContainerFormat::Dds)
+        } else {
+            None
+        }
+    }
+}
+
+impl Context {
+    /// Load a KTX2 or DDS file into a new texture.
+    /// Every mip level, array layer, and cubemap face stored in the file is uploaded.
+    /// KTX2 supercompression, volume textures, and cubemap arrays are not supported.
+    pub fn load_texture_container(
+        &mut self,
+        data: &[u8],
+        _ext: Option<TextureContainerExtensionLoadTextureContainerExt>,
+    ) -> GResult<TextureId> {
+        let container = match TextureContainerFormat::detect(data) {
+            Some(TextureContainerFormat::Ktx2) => parse_ktx2(data)?,
+            Some(TextureContainerFormat::Dds) => parse_dds(data)?,
+            None => Err(gpu_api_err!("texture container unknown file format"))?,
+        };
+
+        let texture = self.new_texture(
+            container.width,
+            container.height,
+            container.format,
+            Some(NewTextureExt {
+                enable_mipmaps: (container.mip_levels > 1 || container.generate_mipmaps)
+                    .then_some(()),
+                mip_levels: (!container.generate_mipmaps).then_some(container.mip_levels),
+                enable_cubemap: container.is_cubemap.then_some(()),
+                array_layers: container.array_layers,
+                ..Default::default()
+            }),
+        )?;
+
+        let upload_ext = Some(UploadTextureExt {
+            generate_mipmaps: container.generate_mipmaps.then_some(()),
+            includes_mip_chain: (!container.generate_mipmaps).then_some(()),
+        });
+
+        if container.array_layers.is_some() {
+            for (layer, image) in container.images.iter().enumerate() {
+                self.upload_texture_layer(texture, layer as u32, image, upload_ext.clone())?;
+            }
+            return Ok(texture);
+        }
+
+        match container.images.as_slice() {
+            [image] => self.upload_texture(texture, image, upload_ext)?,
+            [posx, negx, posy, negy, posz, negz] => self.upload_cubemap_texture(
+                texture,
+                CubemapTextureUpload {
+                    posx,
+                    negx,
+                    posy,
+                    negy,
+                    posz,
+                    negz,
+                },
+                upload_ext,
+            )?,
+            images => Err(gpu_api_err!(
+                "texture container expected 1 or 6 images, got {}",
+                images.len()
+            ))?,
+        }
+
+        Ok(texture)
+    }
+}
+
+/// Each image is a cubemap face or array layer holding its full mip chain,
+/// tightly packed from largest to smallest.
+struct TextureContainer {
+    width: usize,
+    height: usize,
+    format: TextureFormat,
+    mip_levels: u32,
+    generate_mipmaps: bool,
+    is_cubemap: bool,
+    array_layers: Option<u32>,
+    images: Vec<Vec<u8>>,
+}
+
+fn read_u32(data: &[u8], offset: usize) -> GResult<u32> {
+    data.get(offset..offset + 4)
+        .map(|bytes| u32::from_le_bytes(bytes.try_into().unwrap()))
+        .ok_or(gpu_api_err!(
+            "texture container unexpected end of file at {}",
+            offset
+        ))
+}
+
+fn read_u64(data: &[u8], offset: usize) -> GResult<u64> {
+    data.get(offset..offset + 8)
+        .map(|bytes| u64::from_le_bytes(bytes.try_into().unwrap()))
+        .ok_or(gpu_api_err!(
+            "texture container unexpected end of file at {}",
+            offset
+        ))
+}
+
+fn read_bytes(data: &[u8], offset: usize, size: usize) -> GResult<&[u8]> {
+    offset
+        .checked_add(size)
+        .and_then(|end| data.get(offset..end))
+        .ok_or(gpu_api_err!(
+            "texture container unexpected end of file, wanted {} bytes at {}",
+            size,
+            offset
+        ))
+}
+
+fn read_u64_as_usize(data: &[u8], offset: usize) -> GResult<usize> {
+    let value = read_u64(data, offset)?;
+    usize::try_from(value).map_err(|_| {
+        gpu_api_err!(
+            "texture container value {} at {} is too large",
+            value,
+            offset
+        )
+    })
+}
+
+//  Everything read from the file is untrusted, so sizes are checked for overflow and
+//  mip levels are limited to what the dimensions allow.
+fn check_dimensions(width: usize, height: usize, mip_levels: u32) -> GResult<()> {
+    if width == 0 || height == 0 {
+        Err(gpu_api_err!(
+            "texture container invalid dimensions {}x{}",
+            width,
+            height
+        ))?
+    }
+    let max_mip_levels = std::cmp::max(width, height).ilog2() + 1;
+    if mip_levels > max_mip_levels {
+        Err(gpu_api_err!(
+            "texture container {} mip levels > max mip levels {} for {}x{}",
+            mip_levels,
+            max_mip_levels,
+            width,
+            height
+        ))?
+    }
+    Ok(())
+}
+
+fn checked_image_byte_size(format: TextureFormat, width: usize, height: usize) -> GResult<usize> {
+    let (block_width, block_height) = format.block_dimensions();
+    width
+        .div_ceil(block_width)
+        .checked_mul(format.block_byte_size())
+        .and_then(|row_size| row_size.checked_mul(height.div_ceil(block_height)))
+        .ok_or(gpu_api_err!(
+            "texture container image size overflows for {}x{}",
+            width,
+            height
+        ))
+}
+
+fn checked_images_byte_size(image_size: usize, image_count: usize) -> GResult<usize> {
+    image_size.checked_mul(image_count).ok_or(gpu_api_err!(
+        "texture container size of {} images overflows",
+        image_count
+    ))
+}
+
+fn parse_ktx2(data: &[u8]) -> GResult<TextureContainer> {
+    let vk_format = read_u32(data, 12)?;
+    let width = read_u32(data, 20)? as usize;
+    let height = read_u32(data, 24)? as usize;
+    let depth = read_u32(data, 28)?;
+    let layer_count = read_u32(data, 32)?;
+    let face_count = read_u32(data, 36)? as usize;
+    let level_count = read_u32(data, 40)?;
+    let supercompression_scheme = read_u32(data, 44)?;
+
+    if supercompression_scheme != 0 {
+        Err(gpu_api_err!(
+            "texture container ktx2 supercompression scheme {} is not supported",
+            supercompression_scheme
+        ))?
+    }
+    if depth > 1 {
+        Err(gpu_api_err!(
+            "texture container ktx2 volume textures are not supported"
+        ))?
+    }
+    if face_count != 1 && face_count != 6 {
+        Err(gpu_api_err!(
+            "texture container ktx2 invalid face count {}",
+            face_count
+        ))?
+    }
+    if layer_count > 0 && face_count == 6 {
+        Err(gpu_api_err!(
+            "texture container ktx2 cubemap arrays are not supported"
+        ))?
+    }
+    //  A layer count of 0 means that this is not an array texture.
+    let array_layers = (layer_count > 0).then_some(layer_count);
+    let image_count = face_count
+        .checked_mul(std::cmp::max(layer_count, 1) as usize)
+        .ok_or(gpu_api_err!(
+            "texture container ktx2 layer count {} overflows",
+            layer_count
+        ))?;
+    check_dimensions(width, height, level_count)?;
+
+    let format = match vk_format {
+        9 => TextureFormat::R8,
+        16 => TextureFormat::Rg8,
+        37 => TextureFormat::Rgba,
+        43 => TextureFormat::Rgba8Srgb,
+        44 => TextureFormat::Bgra8,
+        76 => TextureFormat::R16F,
+        97 => TextureFormat::Rgba16F,
+        100 => TextureFormat::R32F,
+        109 => TextureFormat::Rgba32F,
+        133 => TextureFormat::Bc1Rgba,
+        134 => TextureFormat::Bc1RgbaSrgb,
+        137 => TextureFormat::Bc3Rgba,
+        138 => TextureFormat::Bc3RgbaSrgb,
+        141 => TextureFormat::Bc5Rg,
+        145 => TextureFormat::Bc7Rgba,
+        146 => TextureFormat::Bc7RgbaSrgb,
+        147 => TextureFormat::Etc2Rgb8,
+        148 => TextureFormat::Etc2Rgb8Srgb,
+        151 => TextureFormat::Etc2Rgba8,
+        152 => TextureFormat::Etc2Rgba8Srgb,
+        157 => TextureFormat::Astc4x4,
+        158 => TextureFormat::Astc4x4Srgb,
+        _ => Err(gpu_api_err!(
+            "texture container ktx2 vkFormat {} is not supported",
+            vk_format
+        ))?,
+    };
+
+    //  A level count of 0 asks for the mip chain to be generated at load time.
+    let generate_mipmaps = level_count == 0;
+    let mip_levels = std::cmp::max(level_count, 1);
+
+    //  The first level is the largest, so don't allocate anything if even that is missing.
+    let base_level_size =
+        checked_images_byte_size(checked_image_byte_size(format, width, height)?, image_count)?;
+    if base_level_size > data.len() {
+        Err(gpu_api_err!(
+            "texture container ktx2 expected at least {} bytes, got {}",
+            base_level_size,
+            data.len()
+        ))?
+    }
+
+    //  KTX2 stores every layer and face of a level together,
+    //  mepeyew wants every level of a layer or face together.
+    let mut images = vec![vec![]; image_count];
+    for level in 0..mip_levels {
+        let level_index = 80 + level as usize * 24;
+        let byte_offset = read_u64_as_usize(data, level_index)?;
+        let byte_length = read_u64_as_usize(data, level_index + 8)?;
+
+        let (mip_width, mip_height) = mip_level_extent(width, height, level);
+        let image_size = format.image_byte_size(mip_width, mip_height);
+        let level_size = checked_images_byte_size(image_size, image_count)?;
+        if byte_length < level_size {
+            Err(gpu_api_err!(
+                "texture container ktx2 level {} has {} bytes, expected {}",
+                level,
+                byte_length,
+                level_size
+            ))?
+        }
+
+        let level_data = read_bytes(data, byte_offset, level_size)?;
+        for (image, image_data) in images.iter_mut().zip(level_data.chunks_exact(image_size)) {
+            image.extend_from_slice(image_data);
+        }
+    }
+
+    Ok(TextureContainer {
+        width,
+        height,
+        format,
+        mip_levels,
+        generate_mipmaps,
+        is_cubemap: face_count == 6,
+        array_layers,
+        images,
+    })
+}
+
+const DDSD_MIPMAPCOUNT: u32 = 0x20000;
+const DDPF_ALPHAPIXELS: u32 = 0x1;
+const DDPF_FOURCC: u32 = 0x4;
+const DDPF_RGB: u32 = 0x40;
+const DDSCAPS2_CUBEMAP: u32 = 0x200;
+const DDSCAPS2_VOLUME: u32 = 0x200000;
+const DDS_RESOURCE_MISC_TEXTURECUBE: u32 = 0x4;
+const DDS_RESOURCE_DIMENSION_TEXTURE3D: u32 = 4;
+
+fn parse_dds(data: &[u8]) -> GResult<TextureContainer> {
+    let flags = read_u32(data, 8)?;
+    let height = read_u32(data, 12)? as usize;
+    let width = read_u32(data, 16)? as usize;
+    let mip_map_count = read_u32(data, 28)?;
+    let pixel_format_flags = read_u32(data, 80)?;
+    let four_cc = read_bytes(data, 84, 4)?;
+    let rgb_bit_count = read_u32(data, 88)?;
+    let masks = (
+        read_u32(data, 92)?,
+        read_u32(data, 96)?,
+        read_u32(data, 100)?,
+        read_u32(data, 104)?,
+    );
+    let caps2 = read_u32(data, 112)?;
+
+    let mip_levels = if flags & DDSD_MIPMAPCOUNT != 0 {
+        std::cmp::max(mip_map_count, 1)
+    } else {
+        1
+    };
+
+    let has_dx10_header = pixel_format_flags & DDPF_FOURCC != 0 && four_cc == b"DX10";
+    let (format, face_count, array_layers, data_offset) = if has_dx10_header {
+        let dxgi_format = read_u32(data, 128)?;
+        let resource_dimension = read_u32(data, 132)?;
+        let misc_flag = read_u32(data, 136)?;
+        let array_size = read_u32(data, 140)?;
+
+        if resource_dimension == DDS_RESOURCE_DIMENSION_TEXTURE3D {
+            Err(gpu_api_err!(
+                "texture container dds volume textures are not supported"
+            ))?
+        }
+
+        let format = match dxgi_format {
+            2 => TextureFormat::Rgba32F,
+            10 => TextureFormat::Rgba16F,
+            28 => TextureFormat::Rgba,
+            29 => TextureFormat::Rgba8Srgb,
+            41 => TextureFormat::R32F,
+            49 => TextureFormat::Rg8,
+            54 => TextureFormat::R16F,
+            61 => TextureFormat::R8,
+            71 => TextureFormat::Bc1Rgba,
+            72 => TextureFormat::Bc1RgbaSrgb,
+            77 => TextureFormat::Bc3Rgba,
+            78 => TextureFormat::Bc3RgbaSrgb,
+            83 => TextureFormat::Bc5Rg,
+            87 => TextureFormat::Bgra8,
+            98 => TextureFormat::Bc7Rgba,
+            99 => TextureFormat::Bc7RgbaSrgb,
+            _ => Err(gpu_api_err!(
+                "texture container dds dxgi format {} is not supported",
+                dxgi_format
+            ))?,
+        };
+        let face_count = if misc_flag & DDS_RESOURCE_MISC_TEXTURECUBE != 0 {
+            6
+        } else {
+            1
+        };
+        if array_size > 1 && face_count == 6 {
+            Err(gpu_api_err!(
+                "texture container dds cubemap arrays are not supported"
+            ))?
+        }
+        let array_layers = (array_size > 1).then_some(array_size);
+        (format, face_count, array_layers, 148)
+    } else {
+        if caps2 & DDSCAPS2_VOLUME != 0 {
+            Err(gpu_api_err!(
+                "texture container dds volume textures are not supported"
+            ))?
+        }
+
+        let format = if pixel_format_flags & DDPF_FOURCC != 0 {
+            match four_cc {
+                b"DXT1" => TextureFormat::Bc1Rgba,
+                b"DXT5" => TextureFormat::Bc3Rgba,
+                b"ATI2" | b"BC5U" => TextureFormat::Bc5Rg,
+                //  D3DFMT codes stored directly in the fourCC field.
+                [111, 0, 0, 0] => TextureFormat::R16F,
+                [113, 0, 0, 0] => TextureFormat::Rgba16F,
+                [114, 0, 0, 0] => TextureFormat::R32F,
+                [116, 0, 0, 0] => TextureFormat::Rgba32F,
+                _ => Err(gpu_api_err!(
+                    "texture container dds fourCC {:?} is not supported",
+                    four_cc
+                ))?,
+            }
+        } else if pixel_format_flags & (DDPF_RGB | DDPF_ALPHAPIXELS) != 0 && rgb_bit_count == 32 {
+            match masks {
+                (0xff, 0xff00, 0xff0000, 0xff000000) => TextureFormat::Rgba,
+                (0xff0000, 0xff00, 0xff, 0xff000000) => TextureFormat::Bgra8,
+                _ => Err(gpu_api_err!(
+                    "texture container dds pixel masks {:x?} are not supported",
+                    masks
+                ))?,
+            }
+        } else {
+            Err(gpu_api_err!(
+                "texture container dds pixel format flags {:x} are not supported",
+                pixel_format_flags
+            ))?
+        };
+        let face_count = if caps2 & DDSCAPS2_CUBEMAP != 0 { 6 } else { 1 };
+        (format, face_count, None, 128)
+    };
+
+    check_dimensions(width, height, mip_levels)?;
+
+    //  DDS already stores every level of a layer or face together.
+    //  Every level is at most as large as the first, which bounds the sum.
+    checked_images_byte_size(
+        checked_image_byte_size(format, width, height)?,
+        mip_levels as usize,
+    )?;
+    let image_size = format.mip_chain_byte_size(width, height, mip_levels);
+    let image_count = face_count * array_layers.unwrap_or(1) as usize;
+    let images = read_bytes(
+        data,
+        data_offset,
+        checked_images_byte_size(image_size, image_count)?,
+    )?
+    .chunks_exact(image_size)
+    .map(|image| image.to_vec())
+    .collect();
+
+    Ok(TextureContainer {
+        width,
+        height,
+        format,
+        mip_levels,
+        generate_mipmaps: false,
+        is_cubemap: face_count == 6,
+        array_layers,
+        images,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_u32(data: &mut [u8], offset: usize, value: u32) {
+        data[offset..offset + 4].copy_from_slice(&value.to_le_bytes());
+    }
+
+    fn write_u64(data: &mut [u8], offset: usize, value: u64) {
+        data[offset..offset + 8].copy_from_slice(&value.to_le_bytes());
+    }
+
+    //  A 4x4 rgba8 image with a single mip level.
+    fn ktx2() -> Vec<u8> {
+        let mut data = vec![0; 104 + 64];
+        data[..12].copy_from_slice(&KTX2_IDENTIFIER);
+        write_u32(&mut data, 12, 37);
+        write_u32(&mut data, 20, 4);
+        write_u32(&mut data, 24, 4);
+        write_u32(&mut data, 36, 1);
+        write_u32(&mut data, 40, 1);
+        write_u64(&mut data, 80, 104);
+        write_u64(&mut data, 88, 64);
+        data
+    }
+
+    //  A 4x4 rgba8 image with a single mip level.
+    fn dds() -> Vec<u8> {
+        let mut data = vec![0; 128 + 64];
+        data[..4].copy_from_slice(&DDS_MAGIC);
+        write_u32(&mut data, 12, 4);
+        write_u32(&mut data, 16, 4);
+        write_u32(&mut data, 80, DDPF_RGB | DDPF_ALPHAPIXELS);
+        write_u32(&mut data, 88, 32);
+        write_u32(&mut data, 92, 0xff);
+        write_u32(&mut data, 96, 0xff00);
+        write_u32(&mut data, 100, 0xff0000);
+        write_u32(&mut data, 104, 0xff000000);
+        data
+    }
+
+    #[test]
+    fn ktx2_valid() {
+        let container = parse_ktx2(&ktx2()).unwrap();
+        assert_eq!(container.format, TextureFormat::Rgba);
+        assert_eq!(container.mip_levels, 1);
+        assert_eq!(container.images.len(), 1);
+        assert_eq!(container.images[0].len(), 64);
+    }
+
+    #[test]
+    fn ktx2_truncated() {
+        let data = ktx2();
+        assert!(parse_ktx2(&data[..30]).is_err());
+        assert!(parse_ktx2(&data[..90]).is_err());
+        assert!(parse_ktx2(&data[..data.len() - 1]).is_err());
+    }
+
+    #[test]
+    fn ktx2_too_many_levels() {
+        for level_count in [4, 64, u32::MAX] {
+            let mut data = ktx2();
+            write_u32(&mut data, 40, level_count);
+            assert!(parse_ktx2(&data).is_err());
+        }
+    }
+
+    #[test]
+    fn ktx2_zero_dimensions() {
+        let mut data = ktx2();
+        write_u32(&mut data, 20, 0);
+        assert!(parse_ktx2(&data).is_err());
+    }
+
+    #[test]
+    fn ktx2_huge_values() {
+        let mut data = ktx2();
+        write_u64(&mut data, 80, u64::MAX);
+        assert!(parse_ktx2(&data).is_err());
+
+        let mut data = ktx2();
+        write_u64(&mut data, 88, u64::MAX);
+        write_u64(&mut data, 80, u64::MAX - 8);
+        assert!(parse_ktx2(&data).is_err());
+
+        let mut data = ktx2();
+        write_u32(&mut data, 32, u32::MAX);
+        assert!(parse_ktx2(&data).is_err());
+
+        let mut data = ktx2();
+        write_u32(&mut data, 20, u32::MAX);
+        write_u32(&mut data, 24, u32::MAX);
+        assert!(parse_ktx2(&data).is_err());
+    }
+
+    #[test]
+    fn dds_valid() {
+        let container = parse_dds(&dds()).unwrap();
+        assert_eq!(container.format, TextureFormat::Rgba);
+        assert_eq!(container.mip_levels, 1);
+        assert_eq!(container.images.len(), 1);
+        assert_eq!(container.images[0].len(), 64);
+    }
+
+    #[test]
+    fn dds_truncated() {
+        let data = dds();
+        assert!(parse_dds(&data[..50]).is_err());
+        assert!(parse_dds(&data[..data.len() - 1]).is_err());
+    }
+
+    #[test]
+    fn dds_too_many_levels() {
+        for mip_map_count in [4, 64, u32::MAX] {
+            let mut data = dds();
+            write_u32(&mut data, 8, DDSD_MIPMAPCOUNT);
+            write_u32(&mut data, 28, mip_map_count);
+            assert!(parse_dds(&data).is_err());
+        }
+    }
+
+    #[test]
+    fn dds_huge_values() {
+        let mut data = dds();
+        write_u32(&mut data, 12, u32::MAX);
+        write_u32(&mut data, 16, u32::MAX);
+        assert!(parse_dds(&data).is_err());
+
+        let mut data = dds();
+        write_u32(&mut data, 12, 0);
+        assert!(parse_dds(&data).is_err());
+    }
+}
@@ -20,6 +20,7 @@ mod program;
 mod sampler;
 mod submit;
 mod texture;
+mod upload;
 
 #[macro_export]
 macro_rules! def_id_ty {
@@ -36,6 +37,8 @@ macro_rules! def_id_ty {
     };
 }
 
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct BufferId(usize);
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
 pub struct VertexBufferId(usize);
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
@@ -53,6 +56,8 @@ pub struct SamplerId(usize);
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
 pub struct TextureId(usize);
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct TextureViewId(usize);
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
 pub struct AttachmentImageId(usize);
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, Default)]
 pub struct PassStepDependency(usize);
@@ -62,7 +67,10 @@ pub struct PassLocalAttachment(usize);
 pub struct CompiledPassId(usize);
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
 pub struct CompiledComputePassId(usize);
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct UploadId(usize);
 
+def_id_ty!(BufferId);
 def_id_ty!(VertexBufferId);
 def_id_ty!(IndexBufferId);
 def_id_ty!(UniformBufferId);
@@ -71,11 +79,13 @@ def_id_ty!(ProgramId);
 def_id_ty!(ComputeProgramId);
 def_id_ty!(SamplerId);
 def_id_ty!(TextureId);
+def_id_ty!(TextureViewId);
 def_id_ty!(AttachmentImageId);
 def_id_ty!(PassStepDependency);
 def_id_ty!(PassLocalAttachment);
 def_id_ty!(CompiledPassId);
 def_id_ty!(CompiledComputePassId);
+def_id_ty!(UploadId);
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Api {
@@ -99,8 +109,9 @@ pub enum Context {
 }
 
 pub use buffer::{
-    BufferStorageType, DynamicUniformBufferTypeGuard, IndexBufferElement,
-    NewDynamicUniformBufferExt, NewIndexBufferExt, NewUniformBufferExt, NewVertexBufferExt,
+    BufferStorageType, BufferUsage, DynamicUniformBufferTypeGuard, GenericIndexBufferId,
+    GenericVertexBufferId, IndexBufferElement, NewBufferExt, NewDynamicUniformBufferExt,
+    NewIndexBufferExt, NewUniformBufferExt, NewVertexBufferExt, ReadSyncedBufferExt,
     UniformBufferTypeGuard, VertexBufferElement, VertexBufferInput,
 };
 pub use extensions::{Extension, Extensions};
@@ -111,15 +122,25 @@ pub use pass::{
 pub use pass_step::PassStep;
 pub use program::{
     NewProgramExt, ShaderBlendFactor, ShaderBlendOperation, ShaderCompareOp, ShaderCullFrontFace,
-    ShaderCullMode, ShaderPrimitiveTopology, ShaderSet, ShaderStage, ShaderStencilOp, ShaderType,
-    ShaderUniform, ShaderUniformType,
+    ShaderCullMode, ShaderPrimitiveTopology, ShaderSet, ShaderStage, ShaderStencilOp,
+    ShaderStorageTextureAccess, ShaderStorageTextureFormat, ShaderType, ShaderUniform,
+    ShaderUniformType,
+};
+pub use sampler::{
+    GetSamplerExt, MipSamplerFilter, SamplerBorderColor, SamplerFilter, SamplerMode,
 };
-pub use sampler::{GetSamplerExt, MipSamplerFilter, SamplerFilter, SamplerMode};
 pub use submit::{
-    ClearColor, ClearDepthStencil, Draw, DrawScissor, DrawType, DrawViewport,
+    BufferTextureCopy, ClearColor, ClearDepthStencil, Draw, DrawScissor, DrawType, DrawViewport,
     DynamicGenericBufferId, PassSubmitData, StepSubmitData, Submit, SubmitExt, SubmitPassType,
+    TextureCopy,
 };
+pub(crate) use texture::{mip_level_extent, TextureViewRange};
 pub use texture::{
-    AttachmentImageColorFormat, AttachmentImageUsage, CubemapTextureUpload, NewAttachmentImageExt,
-    NewTextureExt, TextureFormat, UploadCubemapTextureExt, UploadTextureExt,
+    AttachmentImageColorFormat, AttachmentImageDepthFormat, AttachmentImageSubresource,
+    AttachmentImageUsage, CopyBufferTextureExt, CubemapTextureUpload, GenericTextureId,
+    NewAttachmentImageExt, NewTextureExt, NewTextureViewExt, ReadSyncedAttachmentImageExt,
+    ReadTextureExt, TextureComponentSwizzle, TextureCompression, TextureFormat, TextureRegion,
+    TextureViewDimension, TextureViewSwizzle, UploadCubemapTextureExt, UploadTextureExt,
+    UploadTextureRegionExt,
 };
+pub use upload::{AsyncUploadBufferExt, AsyncUploadTextureExt};
@@ -36,6 +36,7 @@ pub struct PassAttachment {
     //  Could technically be replaced with `.iter().enumerate()`.
     pub(crate) local_attachment_idx: usize,
     pub(crate) output_image: Option<AttachmentImageId>,
+    pub(crate) output_subresource: AttachmentImageSubresource,
 }
 
 //  `surface_attachment` is always index 0 if set.
@@ -74,6 +75,7 @@ impl Pass {
                 local_attachment_idx: 0,
                 //  Will be ignored.
                 output_image: None,
+                output_subresource: Default::default(),
             })
         }
         pass
@@ -101,7 +103,19 @@ impl Pass {
         color: AttachmentImageId,
         load_op: PassInputLoadOpColorType,
     ) -> PassLocalAttachment {
-        self.add_attachment(color, PassInputType::Color(load_op))
+        self.add_attachment(color, Default::default(), PassInputType::Color(load_op))
+    }
+
+    /// Get a [`PassLocalAttachment`] from a single layer and mip level of a color
+    /// [`AttachmentImageId`].
+    /// The pass's render size should match the size of the mip level.
+    pub fn add_attachment_color_image_subresource(
+        &mut self,
+        color: AttachmentImageId,
+        subresource: AttachmentImageSubresource,
+        load_op: PassInputLoadOpColorType,
+    ) -> PassLocalAttachment {
+        self.add_attachment(color, subresource, PassInputType::Color(load_op))
     }
 
     /// Get a [`PassLocalAttachment`] from an depth [`AttachmentImageId`].
@@ -110,17 +124,39 @@ impl Pass {
         depth: AttachmentImageId,
         load_op: PassInputLoadOpDepthStencilType,
     ) -> PassLocalAttachment {
-        self.add_attachment(depth, PassInputType::Depth(load_op))
+        self.add_attachment(depth, Default::default(), PassInputType::Depth(load_op))
+    }
+
+    /// Get a [`PassLocalAttachment`] from a single layer and mip level of a depth
+    /// [`AttachmentImageId`].
+    /// The pass's render size should match the size of the mip level.
+    pub fn add_attachment_depth_image_subresource(
+        &mut self,
+        depth: AttachmentImageId,
+        subresource: AttachmentImageSubresource,
+        load_op: PassInputLoadOpDepthStencilType,
+    ) -> PassLocalAttachment {
+        self.add_attachment(depth, subresource, PassInputType::Depth(load_op))
+    }
+
+    pub(crate) fn uses_any_attachment_image(&self, images: &[AttachmentImageId]) -> bool {
+        self.attachments.iter().any(|attachment| {
+            attachment
+                .output_image
+                .is_some_and(|image| images.contains(&image))
+        })
     }
 
     fn add_attachment(
         &mut self,
         image: AttachmentImageId,
+        subresource: AttachmentImageSubresource,
         ty: PassInputType,
     ) -> PassLocalAttachment {
         self.attachments.push(PassAttachment {
             ty,
             output_image: Some(image),
+            output_subresource: subresource,
             local_attachment_idx: self.attachments.len(),
         });
         PassLocalAttachment::from_id(self.attachments.len() - 1)
@@ -129,12 +165,18 @@ impl Pass {
 
 /// Allows:
 /// - The enabling of MSAA
+/// - Skipping the MSAA resolve
 #[derive(Default, Debug, Clone)]
 pub struct CompilePassExt {
     /// Enable multisample anti-aliasing.
     pub enable_msaa: Option<()>,
     /// Optionally include the amount of multisample anti-aliasing samples.
     pub msaa_samples: Option<MsaaSampleCount>,
+    /// Render directly into multisampled color attachment images instead of resolving into them.
+    /// Requires `enable_msaa`, and every attachment image must be created with the same
+    /// [`NewAttachmentImageExt::msaa_samples`]. The pass cannot render to the surface.
+    /// Read the samples in a later pass with [`ShaderUniformType::MultisampledAttachmentTexture`].
+    pub skip_msaa_resolve: Option<()>,
 }
 
 impl Context {
@@ -4,8 +4,8 @@ use super::*;
 pub struct PassStep {
     pub(crate) step_dependency: PassStepDependency,
 
-    pub(crate) vertex_buffers: Vec<VertexBufferId>,
-    pub(crate) index_buffer: Option<IndexBufferId>,
+    pub(crate) vertex_buffers: Vec<GenericVertexBufferId>,
+    pub(crate) index_buffer: Option<GenericIndexBufferId>,
 
     pub(crate) programs: Vec<ProgramId>,
 
@@ -19,13 +19,15 @@ pub struct PassStep {
 }
 
 impl PassStep {
-    pub fn add_vertex_buffer(&mut self, vbo: VertexBufferId) -> &mut Self {
-        self.vertex_buffers.push(vbo);
+    /// Accepts either a [`VertexBufferId`] or a [`BufferId`] created with [`BufferUsage::VERTEX`].
+    pub fn add_vertex_buffer(&mut self, vbo: impl Into<GenericVertexBufferId>) -> &mut Self {
+        self.vertex_buffers.push(vbo.into());
         self
     }
 
-    pub fn set_index_buffer(&mut self, ibo: IndexBufferId) -> &mut Self {
-        self.index_buffer = Some(ibo);
+    /// Accepts either an [`IndexBufferId`] or a [`BufferId`] created with [`BufferUsage::INDEX`].
+    pub fn set_index_buffer(&mut self, ibo: impl Into<GenericIndexBufferId>) -> &mut Self {
+        self.index_buffer = Some(ibo.into());
         self
     }
 
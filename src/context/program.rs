@@ -87,16 +87,79 @@ pub enum ShaderPrimitiveTopology {
     TriangleStrip,
 }
 
+/// Used in [`ShaderUniformType::StorageTexture`] to declare how the shader accesses the image.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum ShaderStorageTextureAccess {
+    ReadOnly,
+    WriteOnly,
+    /// On WebGpu, only [`ShaderStorageTextureFormat::R32SFloat`] supports this.
+    ReadWrite,
+}
+
+/// Used in [`ShaderUniformType::StorageTexture`].
+/// Must match the format of the bound texture or attachment image.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum ShaderStorageTextureFormat {
+    /// Matches [`TextureFormat::Rgba`] and [`AttachmentImageColorFormat::R8G8B8A8UNorm`].
+    Rgba8UNorm,
+    /// Matches [`TextureFormat::Rgba16F`] and [`AttachmentImageColorFormat::R16G16B16A16SFloat`].
+    Rgba16SFloat,
+    /// Matches [`TextureFormat::R32F`] and [`AttachmentImageColorFormat::R32SFloat`].
+    R32SFloat,
+    /// Matches [`TextureFormat::Rgba32F`] and [`AttachmentImageColorFormat::R32G32B32A32SFloat`].
+    Rgba32SFloat,
+}
+
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
 pub enum ShaderUniformType {
     Sampler(SamplerId),
+    /// Requires a sampler created with [`GetSamplerExt::compare_op`].
+    /// Use with [`ShaderUniformType::DepthAttachmentTexture`].
+    ComparisonSampler(SamplerId),
     Texture(TextureId),
     CubemapTexture(TextureId),
+    /// Requires a texture created with [`NewTextureExt::depth`].
+    Texture3D(TextureId),
+    /// Requires a texture created with [`NewTextureExt::array_layers`].
+    TextureArray(TextureId),
+    /// Requires a view created with [`Context::new_texture_view`].
+    TextureView(TextureViewId),
     UniformBuffer(UniformBufferId),
     DynamicUniformBuffer(DynamicUniformBufferId),
     InputAttachment(AttachmentImageId),
+    /// Sample an attachment image written by an earlier pass like a regular texture.
+    /// Depth images are sampled as their depth value.
+    /// The image cannot be multisampled, and the pass that samples it can only render into
+    /// other layers or mip levels of it.
+    AttachmentTexture(AttachmentImageId),
+    /// Read individual samples of a multisampled attachment image written by an earlier pass,
+    /// such as with `texture2DMS` in glsl or `texture_multisampled_2d` in wgsl.
+    /// Requires [`NewAttachmentImageExt::msaa_samples`], and the pass that renders into the image
+    /// should be compiled with [`CompilePassExt::skip_msaa_resolve`].
+    /// The image cannot be written by the pass that samples it.
+    MultisampledAttachmentTexture(AttachmentImageId),
+    /// Sample the depth of a depth attachment image written by an earlier pass,
+    /// such as with `texture_depth_2d` in wgsl.
+    /// Pair with [`ShaderUniformType::ComparisonSampler`] for hardware shadow filtering.
+    /// The image cannot be written by the pass that samples it, cannot be multisampled,
+    /// and cannot use [`AttachmentImageDepthFormat::S8UInt`].
+    DepthAttachmentTexture(AttachmentImageId),
+    /// Load and store texels without a sampler, only the first mip level is bound.
+    /// Requires [`NewTextureExt::enable_storage`] or [`NewAttachmentImageExt::enable_storage`].
+    /// The image cannot be sampled or rendered to by the pass that stores to it.
+    StorageTexture(
+        GenericTextureId,
+        ShaderStorageTextureAccess,
+        ShaderStorageTextureFormat,
+    ),
     ShaderStorageBuffer(extensions::ShaderStorageBufferId),
     ShaderStorageBufferReadOnly(extensions::ShaderStorageBufferId),
+    /// Requires [`BufferUsage::UNIFORM`].
+    GenericUniformBuffer(BufferId),
+    /// Requires [`BufferUsage::STORAGE`].
+    GenericShaderStorageBuffer(BufferId),
+    /// Requires [`BufferUsage::STORAGE`].
+    GenericShaderStorageBufferReadOnly(BufferId),
 }
 
 #[derive(Debug, Clone, Hash, PartialEq, Eq)]
@@ -26,12 +26,25 @@ pub enum MipSamplerFilter {
     Linear,
 }
 
+/// Used in [`GetSamplerExt`].
+/// The color returned when sampling outside of a texture using [`SamplerMode::ClampToBorder`].
+#[derive(Debug, Clone, Copy, Hash, Default, PartialEq, Eq)]
+pub enum SamplerBorderColor {
+    #[default]
+    TransparentBlack,
+    OpaqueBlack,
+    OpaqueWhite,
+}
+
 /// Allows the configuration of:
 /// - Min filter
 /// - Mag filter
 /// - Mip filter
-/// - uv overflow behavior
-/// - LOD
+/// - uvw overflow behavior
+/// - Border color
+/// - LOD and LOD bias
+/// - Anisotropic filtering
+/// - Depth comparison
 #[derive(Default, Debug)]
 pub struct GetSamplerExt {
     /// The minification filter to use.
@@ -44,12 +57,31 @@ pub struct GetSamplerExt {
     pub u_mode: SamplerMode,
     /// Overflow behavior of the texture on the v (y) axis.
     pub v_mode: SamplerMode,
+    /// Overflow behavior of the texture on the w (z) axis.
+    /// Relevant for cubemaps and 3d textures.
+    pub w_mode: SamplerMode,
+    /// Border color used with [`SamplerMode::ClampToBorder`].
+    /// WebGPU has no border colors and clamps to the edge instead.
+    pub border_color: SamplerBorderColor,
 
     /// Specify the min lod.
     pub min_lod: Option<f32>,
     /// Specify the max lod.
     /// This value can be obtained for textures using [`Context::get_texture_max_lod`]
     pub max_lod: Option<f32>,
+    /// Specify the bias added to the computed lod.
+    /// This is clamped to the device limit and ignored on WebGPU.
+    pub lod_bias: Option<f32>,
+
+    /// Specify the max anisotropy, clamped to the device limit.
+    /// Unsupported devices simply ignore this.
+    /// On WebGPU, all filters must be linear for this to take effect.
+    pub max_anisotropy: Option<u16>,
+
+    /// Create a comparison sampler for hardware shadow filtering,
+    /// such as `sampler2DShadow` in glsl or `sampler_comparison` in wgsl.
+    /// Bind with [`ShaderUniformType::ComparisonSampler`].
+    pub compare_op: Option<ShaderCompareOp>,
 }
 
 impl Context {
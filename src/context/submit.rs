@@ -177,6 +177,30 @@ impl PassSubmitData {
 pub enum SubmitPassType {
     Render(PassSubmitData),
     Compute(extensions::ComputePassSubmitData),
+    TextureCopy(TextureCopy),
+    BufferTextureCopy(BufferTextureCopy),
+    GenerateMipmaps(GenericTextureId),
+}
+
+/// Recorded by [`Submit::copy_texture`] and [`Submit::blit_texture`].
+#[derive(Debug, Clone)]
+pub struct TextureCopy {
+    pub(crate) src: GenericTextureId,
+    pub(crate) src_region: TextureRegion,
+    pub(crate) dst: GenericTextureId,
+    pub(crate) dst_region: TextureRegion,
+    //  `None` for exact copies.
+    pub(crate) blit_filter: Option<SamplerFilter>,
+}
+
+/// Recorded by [`Submit::copy_buffer_to_texture`] and [`Submit::copy_texture_to_buffer`].
+#[derive(Debug, Clone)]
+pub struct BufferTextureCopy {
+    pub(crate) buffer: BufferId,
+    pub(crate) texture: GenericTextureId,
+    pub(crate) region: TextureRegion,
+    pub(crate) to_texture: bool,
+    pub(crate) ext: CopyBufferTextureExt,
 }
 
 #[derive(Default, Debug, Clone)]
@@ -187,6 +211,15 @@ pub struct Submit<'transfer> {
     pub(crate) ubo_transfers: Vec<(UniformBufferId, &'transfer [u8])>,
     pub(crate) dyn_ubo_transfers: Vec<(DynamicUniformBufferId, &'transfer [u8], usize)>,
     pub(crate) ssbo_copy_backs: Vec<extensions::ShaderStorageBufferId>,
+    pub(crate) buffer_transfers: Vec<(BufferId, &'transfer [u8])>,
+    pub(crate) buffer_copy_backs: Vec<BufferId>,
+    pub(crate) attachment_image_copy_backs: Vec<AttachmentImageId>,
+    pub(crate) texture_transfers: Vec<(
+        TextureId,
+        TextureRegion,
+        &'transfer [u8],
+        UploadTextureRegionExt,
+    )>,
 }
 
 impl<'transfer> Submit<'transfer> {
@@ -198,6 +231,10 @@ impl<'transfer> Submit<'transfer> {
             ubo_transfers: vec![],
             dyn_ubo_transfers: vec![],
             ssbo_copy_backs: vec![],
+            buffer_transfers: vec![],
+            buffer_copy_backs: vec![],
+            attachment_image_copy_backs: vec![],
+            texture_transfers: vec![],
         }
     }
 
@@ -281,6 +318,153 @@ impl<'transfer> Submit<'transfer> {
         self
     }
 
+    /// The buffer must use `BufferStorageType::Dynamic`,
+    /// or have been created with [`BufferUsage::COPY_SRC`] or [`BufferUsage::COPY_DST`].
+    /// `data` is written to the start of the buffer and must not be larger than it.
+    pub fn transfer_into_buffer<T: Copy>(
+        &mut self,
+        buffer: BufferId,
+        data: &'transfer [T],
+    ) -> &mut Self {
+        let untyped_slice = unsafe {
+            std::slice::from_raw_parts(data.as_ptr() as *const u8, std::mem::size_of_val(data))
+        };
+        self.buffer_transfers.push((buffer, untyped_slice));
+        self
+    }
+
+    /// Copy a region between textures or attachment images of the same format.
+    /// Both regions should be the same size, and neither image can be multisampled.
+    /// The copy happens in order with the passes of this submit.
+    pub fn copy_texture(
+        &mut self,
+        src: impl Into<GenericTextureId>,
+        src_region: TextureRegion,
+        dst: impl Into<GenericTextureId>,
+        dst_region: TextureRegion,
+    ) -> &mut Self {
+        self.passes.push(SubmitPassType::TextureCopy(TextureCopy {
+            src: src.into(),
+            src_region,
+            dst: dst.into(),
+            dst_region,
+            blit_filter: None,
+        }));
+        self
+    }
+
+    /// Copy a region between color textures or attachment images, scaling it to fill `dst_region`.
+    /// Only 2D regions are supported, and neither image can be multisampled.
+    /// On WebGpu, blits are rendered, so the source must be filterable when using
+    /// [`SamplerFilter::Linear`] and the destination cannot be a compressed or 3D texture.
+    /// The blit happens in order with the passes of this submit.
+    pub fn blit_texture(
+        &mut self,
+        src: impl Into<GenericTextureId>,
+        src_region: TextureRegion,
+        dst: impl Into<GenericTextureId>,
+        dst_region: TextureRegion,
+        filter: SamplerFilter,
+    ) -> &mut Self {
+        self.passes.push(SubmitPassType::TextureCopy(TextureCopy {
+            src: src.into(),
+            src_region,
+            dst: dst.into(),
+            dst_region,
+            blit_filter: Some(filter),
+        }));
+        self
+    }
+
+    /// Copy pixel data from a buffer into a region of a texture or attachment image.
+    /// The buffer must have been created with [`BufferUsage::COPY_SRC`].
+    /// Rows are laid out as in [`Context::upload_texture_region`].
+    /// For combined depth-stencil attachment images, only the depth is copied.
+    /// On WebGpu, `D24UNormS8UInt` and `D32SFloat*` attachment images cannot be copied into.
+    /// The copy happens in order with the passes of this submit.
+    pub fn copy_buffer_to_texture(
+        &mut self,
+        buffer: BufferId,
+        dst: impl Into<GenericTextureId>,
+        region: TextureRegion,
+        ext: Option<CopyBufferTextureExt>,
+    ) -> &mut Self {
+        self.passes
+            .push(SubmitPassType::BufferTextureCopy(BufferTextureCopy {
+                buffer,
+                texture: dst.into(),
+                region,
+                to_texture: true,
+                ext: ext.unwrap_or_default(),
+            }));
+        self
+    }
+
+    /// Copy a region of a texture or attachment image into a buffer.
+    /// The buffer must have been created with [`BufferUsage::COPY_DST`].
+    /// For combined depth-stencil attachment images, only the depth is copied.
+    /// On WebGpu, `D24UNormS8UInt` attachment images cannot be copied from.
+    /// The copy happens in order with the passes of this submit.
+    pub fn copy_texture_to_buffer(
+        &mut self,
+        src: impl Into<GenericTextureId>,
+        region: TextureRegion,
+        buffer: BufferId,
+        ext: Option<CopyBufferTextureExt>,
+    ) -> &mut Self {
+        self.passes
+            .push(SubmitPassType::BufferTextureCopy(BufferTextureCopy {
+                buffer,
+                texture: src.into(),
+                region,
+                to_texture: false,
+                ext: ext.unwrap_or_default(),
+            }));
+        self
+    }
+
+    /// Regenerate every mip level of a texture or attachment image from its first mip level,
+    /// such as after a pass has rendered into it.
+    /// Every layer is regenerated.
+    /// The image should be an uncompressed 2d color image with mip levels and without msaa.
+    /// The mip levels are generated in order with the passes of this submit.
+    pub fn generate_mipmaps(&mut self, image: impl Into<GenericTextureId>) -> &mut Self {
+        self.passes
+            .push(SubmitPassType::GenerateMipmaps(image.into()));
+        self
+    }
+
+    /// Overwrite part of a texture before rendering, see [`Context::upload_texture_region`].
+    /// Unlike uploads, the copy is recorded alongside the rest of this submit.
+    pub fn transfer_into_texture(
+        &mut self,
+        texture: TextureId,
+        region: TextureRegion,
+        data: &'transfer [u8],
+        ext: Option<UploadTextureRegionExt>,
+    ) -> &mut Self {
+        self.texture_transfers
+            .push((texture, region, data, ext.unwrap_or_default()));
+        self
+    }
+
+    /// Write the buffer back into CPU memory after rendering.
+    /// The buffer must have been created with [`BufferUsage::COPY_SRC`].
+    /// This is essential for [`Context::read_synced_buffer`]
+    pub fn sync_buffer(&mut self, buffer: BufferId) -> &mut Self {
+        self.buffer_copy_backs.push(buffer);
+        self
+    }
+
+    /// Write the attachment image back into CPU memory after rendering.
+    /// The image should have been written to by a pass in this submit or a previous one.
+    /// This is essential for [`Context::read_synced_attachment_image`]
+    /// Only the first layer and mip level of the image is synced.
+    pub fn sync_attachment_image(&mut self, image: AttachmentImageId) -> &mut Self {
+        self.attachment_image_copy_backs.push(image);
+        self
+    }
+
     /// Write the shader storage buffer back into CPU memory after rendering.
     /// This is essential for [`Context::read_synced_shader_storage_buffer`]
     pub fn sync_shader_storage_buffer(
@@ -1,10 +1,142 @@
 use super::*;
 
+/// Note that `R32F` and `Rgba32F` cannot be linearly filtered on every device.
+/// On WebGPU, they must be sampled with [`SamplerFilter::Nearest`] and
+/// [`MipSamplerFilter::Nearest`] filters, and cannot have their mipmaps generated.
+/// Block-compressed formats are only available if the device supports their
+/// [`TextureCompression`] family.
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
 pub enum TextureFormat {
     //  80% of gpus with vulkan don't support Rgb.
     //  Rgb,
+    /// 8 bit unsigned normalized RGBA, no gamma decoding.
     Rgba,
+    /// 8 bit unsigned normalized RGBA, decoded from sRGB when sampled.
+    Rgba8Srgb,
+    /// 8 bit unsigned normalized BGRA.
+    Bgra8,
+    R8,
+    Rg8,
+    R16F,
+    Rgba16F,
+    R32F,
+    Rgba32F,
+
+    Bc1Rgba,
+    Bc1RgbaSrgb,
+    Bc3Rgba,
+    Bc3RgbaSrgb,
+    Bc5Rg,
+    Bc7Rgba,
+    Bc7RgbaSrgb,
+
+    Etc2Rgb8,
+    Etc2Rgb8Srgb,
+    Etc2Rgba8,
+    Etc2Rgba8Srgb,
+
+    Astc4x4,
+    Astc4x4Srgb,
+}
+
+/// Families of block-compressed texture formats.
+/// Use [`Context::get_supported_texture_compressions`] to see which ones the device can sample.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum TextureCompression {
+    /// BC1 through BC7, usually available on desktop.
+    Bc,
+    /// ETC2, usually available on mobile.
+    Etc2,
+    /// ASTC LDR, usually available on mobile.
+    Astc,
+}
+
+impl TextureFormat {
+    pub fn compression(&self) -> Option<TextureCompression> {
+        match self {
+            TextureFormat::Bc1Rgba
+            | TextureFormat::Bc1RgbaSrgb
+            | TextureFormat::Bc3Rgba
+            | TextureFormat::Bc3RgbaSrgb
+            | TextureFormat::Bc5Rg
+            | TextureFormat::Bc7Rgba
+            | TextureFormat::Bc7RgbaSrgb => Some(TextureCompression::Bc),
+            TextureFormat::Etc2Rgb8
+            | TextureFormat::Etc2Rgb8Srgb
+            | TextureFormat::Etc2Rgba8
+            | TextureFormat::Etc2Rgba8Srgb => Some(TextureCompression::Etc2),
+            TextureFormat::Astc4x4 | TextureFormat::Astc4x4Srgb => Some(TextureCompression::Astc),
+            _ => None,
+        }
+    }
+
+    /// The width and height of a block in pixels.
+    /// Uncompressed formats use single pixel blocks.
+    pub fn block_dimensions(&self) -> (usize, usize) {
+        if self.compression().is_some() {
+            (4, 4)
+        } else {
+            (1, 1)
+        }
+    }
+
+    /// The size of a single block in bytes.
+    pub fn block_byte_size(&self) -> usize {
+        match self {
+            TextureFormat::R8 => 1,
+            TextureFormat::Rg8 | TextureFormat::R16F => 2,
+            TextureFormat::Rgba
+            | TextureFormat::Rgba8Srgb
+            | TextureFormat::Bgra8
+            | TextureFormat::R32F => 4,
+            TextureFormat::Rgba16F => 8,
+            TextureFormat::Rgba32F => 16,
+
+            TextureFormat::Bc1Rgba
+            | TextureFormat::Bc1RgbaSrgb
+            | TextureFormat::Etc2Rgb8
+            | TextureFormat::Etc2Rgb8Srgb => 8,
+            TextureFormat::Bc3Rgba
+            | TextureFormat::Bc3RgbaSrgb
+            | TextureFormat::Bc5Rg
+            | TextureFormat::Bc7Rgba
+            | TextureFormat::Bc7RgbaSrgb
+            | TextureFormat::Etc2Rgba8
+            | TextureFormat::Etc2Rgba8Srgb
+            | TextureFormat::Astc4x4
+            | TextureFormat::Astc4x4Srgb => 16,
+        }
+    }
+
+    /// The size of one tightly packed row of blocks in bytes.
+    pub fn row_byte_size(&self, width: usize) -> usize {
+        let (block_width, _) = self.block_dimensions();
+        width.div_ceil(block_width) * self.block_byte_size()
+    }
+
+    /// The size of a tightly packed `width` by `height` image in bytes.
+    pub fn image_byte_size(&self, width: usize, height: usize) -> usize {
+        let (_, block_height) = self.block_dimensions();
+        self.row_byte_size(width) * height.div_ceil(block_height)
+    }
+
+    /// The size of every mip level of a `width` by `height` image in bytes,
+    /// packed one after another from largest to smallest.
+    pub fn mip_chain_byte_size(&self, width: usize, height: usize, mip_levels: u32) -> usize {
+        (0..mip_levels)
+            .map(|level| {
+                let (mip_width, mip_height) = mip_level_extent(width, height, level);
+                self.image_byte_size(mip_width, mip_height)
+            })
+            .sum()
+    }
+}
+
+pub(crate) fn mip_level_extent(width: usize, height: usize, level: u32) -> (usize, usize) {
+    (
+        std::cmp::max(width >> level, 1),
+        std::cmp::max(height >> level, 1),
+    )
 }
 
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
@@ -15,6 +147,10 @@ pub enum AttachmentImageColorFormat {
     //  R8G8B8UNorm,
     R8G8B8A8UNorm,
 
+    /// Unlike the 32 bit float formats, this can be linearly filtered on every device.
+    R16G16B16A16SFloat,
+
+    /// The 32 bit float formats follow the same filtering rules as [`TextureFormat::R32F`].
     R32SFloat,
     R32G32SFloat,
     //  85% of gpus with vulkan don't support Rgb.
@@ -22,6 +158,43 @@ pub enum AttachmentImageColorFormat {
     R32G32B32A32SFloat,
 }
 
+/// Falls back to the closest supported format, see [`Context::get_attachment_image_depth_format`].
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum AttachmentImageDepthFormat {
+    D16UNorm,
+    D24UNormS8UInt,
+    D32SFloat,
+    D32SFloatS8UInt,
+    S8UInt,
+}
+
+impl AttachmentImageDepthFormat {
+    //  Formats to try in order, preferring ones that keep the same aspects.
+    pub(crate) fn fallbacks(&self) -> &'static [AttachmentImageDepthFormat] {
+        use AttachmentImageDepthFormat::*;
+        match self {
+            D16UNorm => &[D16UNorm, D32SFloat, D24UNormS8UInt, D32SFloatS8UInt],
+            D24UNormS8UInt => &[D24UNormS8UInt, D32SFloatS8UInt, D32SFloat, D16UNorm],
+            D32SFloat => &[D32SFloat, D32SFloatS8UInt, D24UNormS8UInt, D16UNorm],
+            D32SFloatS8UInt => &[D32SFloatS8UInt, D24UNormS8UInt, D32SFloat, D16UNorm],
+            S8UInt => &[S8UInt, D24UNormS8UInt, D32SFloatS8UInt],
+        }
+    }
+
+    pub(crate) fn has_depth(&self) -> bool {
+        *self != AttachmentImageDepthFormat::S8UInt
+    }
+
+    pub(crate) fn has_stencil(&self) -> bool {
+        matches!(
+            self,
+            AttachmentImageDepthFormat::D24UNormS8UInt
+                | AttachmentImageDepthFormat::D32SFloatS8UInt
+                | AttachmentImageDepthFormat::S8UInt
+        )
+    }
+}
+
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
 pub enum AttachmentImageUsage {
     ColorAttachment,
@@ -40,22 +213,88 @@ pub struct CubemapTextureUpload<'a> {
 /// Allows for the configuration of:
 /// - Mipmaps
 /// - Cubemap
+/// - 3D textures
+/// - Texture arrays
+/// - Storage usage
 #[derive(Default, Debug)]
 pub struct NewTextureExt {
     pub enable_mipmaps: Option<()>,
     pub mip_levels: Option<u32>,
     pub enable_cubemap: Option<()>,
+    /// Create a 3D texture with this many slices.
+    /// [`Context::upload_texture`] data should contain every slice, tightly packed from front to back.
+    /// To upload individual slices, use [`Context::upload_texture_region`] with
+    /// [`TextureRegion::z`] set to the first slice and [`TextureRegion::depth`] to the slice count.
+    /// Not compatible with mipmaps or cubemaps.
+    pub depth: Option<usize>,
+    /// Create a 2D texture array with this many layers, which must be at least 1.
+    /// Upload each layer with [`Context::upload_texture_layer`].
+    /// Not compatible with cubemaps or 3D textures.
+    pub array_layers: Option<u32>,
+    /// Allow the texture to be bound with [`ShaderUniformType::StorageTexture`].
+    /// The format must be one of [`ShaderStorageTextureFormat`].
+    pub enable_storage: Option<()>,
 }
 
 /// Allows for the configuration of:
 /// - Whether to generate mipmaps
+/// - Whether the data includes mipmaps
 #[derive(Default, Debug, Clone)]
 pub struct UploadTextureExt {
     /// Generate mipmips.
     /// The mipmap count can be obtained with [`Context::get_texture_max_lod`].
+    /// Not available for compressed formats.
     pub generate_mipmaps: Option<()>,
+    /// The uploaded data contains every mip level, tightly packed from largest to smallest.
+    /// For cubemaps, this applies to each face.
+    /// See [`TextureFormat::mip_chain_byte_size`].
+    pub includes_mip_chain: Option<()>,
+}
+
+/// A box within a single mip level and layer of a texture.
+/// For 3D textures, `z` and `depth` select slices, otherwise `z` should be 0 and `depth` should be 1.
+/// For cubemaps, `layer` selects the face in the order of [`CubemapTextureUpload`].
+/// For compressed formats, the box should be aligned to whole blocks unless it touches the edge
+/// of the mip level.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct TextureRegion {
+    pub x: usize,
+    pub y: usize,
+    pub z: usize,
+    pub width: usize,
+    pub height: usize,
+    pub depth: usize,
+    pub mip_level: u32,
+    pub layer: u32,
 }
 
+/// Allows for the configuration of:
+/// - Row pitch
+#[derive(Default, Debug, Clone)]
+pub struct UploadTextureRegionExt {
+    /// The number of bytes from the start of one row of pixels (or blocks) to the next.
+    /// Defaults to tightly packed rows, see [`TextureFormat::row_byte_size`].
+    pub row_pitch: Option<usize>,
+}
+
+/// Allows for the configuration of:
+/// - Buffer offset
+/// - Row pitch
+#[derive(Default, Debug, Clone)]
+pub struct CopyBufferTextureExt {
+    /// The byte offset of the first row within the buffer.
+    /// Should be a multiple of the texel (or block) byte size.
+    pub buffer_offset: Option<usize>,
+    /// The number of bytes from the start of one row of pixels (or blocks) to the next.
+    /// Defaults to tightly packed rows, see [`TextureFormat::row_byte_size`].
+    /// On WebGpu, this must be a multiple of 256 if the region spans more than one row.
+    pub row_pitch: Option<usize>,
+}
+
+/// Currently has extra extension options.
+#[derive(Default, Debug, Clone)]
+pub struct ReadTextureExt {}
+
 /// Allows for the configuration of:
 /// - Whether to generate mipmaps
 #[derive(Default, Debug, Clone)]
@@ -68,6 +307,9 @@ pub struct UploadCubemapTextureExt {
 /// Allows for the configuration of:
 /// - MSAA samples
 /// - (Color) attachment image format.
+/// - Layers, mip levels, and cubemaps
+/// - Fixed size
+/// - Storage usage
 #[derive(Default, Debug, Clone)]
 pub struct NewAttachmentImageExt {
     /// Should match the mssa samples used in [`CompilePassExt`].
@@ -76,6 +318,216 @@ pub struct NewAttachmentImageExt {
     /// Optionaly specify the format used by the attachment image if the image is used as a color
     /// attachment.
     pub color_format: Option<AttachmentImageColorFormat>,
+    /// Optionaly specify the format used by the attachment image if the image is used as a depth
+    /// attachment.
+    /// Defaults to `D32SFloatS8UInt` on Vulkan and `D24UNormS8UInt` on WebGpu.
+    pub depth_format: Option<AttachmentImageDepthFormat>,
+    /// Create an attachment image with this many layers, which must be at least 1.
+    /// Render into a single layer with [`Pass::add_attachment_color_image_subresource`].
+    pub array_layers: Option<u32>,
+    /// Create an attachment image with this many mip levels.
+    /// Must be between 1 and the full mip chain of the initial size.
+    /// Render into a single mip level with [`Pass::add_attachment_color_image_subresource`].
+    /// A pass may sample the other mip levels and layers of the image it renders into.
+    pub mip_levels: Option<u32>,
+    /// Create a cubemap attachment image with 6 layers, one per face in the order of
+    /// [`CubemapTextureUpload`].
+    /// Not compatible with `array_layers`.
+    pub enable_cubemap: Option<()>,
+    /// Keep the initial size when the surface is resized, such as for shadow maps.
+    /// By default, attachment images are resized along with the surface.
+    pub fixed_size: Option<()>,
+    /// Allow the attachment image to be bound with [`ShaderUniformType::StorageTexture`].
+    /// The color format must be one of [`ShaderStorageTextureFormat`].
+    pub enable_storage: Option<()>,
+}
+
+/// Anything that can be bound as a storage texture or copied with [`Submit::copy_texture`] and
+/// [`Submit::copy_buffer_to_texture`].
+/// You shouldn't need to construct this yourself since [`TextureId`] and [`AttachmentImageId`]
+/// both convert into it.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum GenericTextureId {
+    Texture(TextureId),
+    AttachmentImage(AttachmentImageId),
+}
+
+impl From<TextureId> for GenericTextureId {
+    fn from(texture: TextureId) -> Self {
+        Self::Texture(texture)
+    }
+}
+
+impl From<AttachmentImageId> for GenericTextureId {
+    fn from(attachment_image: AttachmentImageId) -> Self {
+        Self::AttachmentImage(attachment_image)
+    }
+}
+
+/// A single layer and mip level of an attachment image to render into.
+#[derive(Default, Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct AttachmentImageSubresource {
+    pub layer: u32,
+    pub mip_level: u32,
+}
+
+/// Currently has extra extension options.
+#[derive(Default, Debug, Clone)]
+pub struct ReadSyncedAttachmentImageExt {}
+
+/// The shape of a view created with [`Context::new_texture_view`].
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum TextureViewDimension {
+    D2,
+    D2Array,
+    /// Requires a cubemap texture and all 6 faces.
+    Cube,
+    /// Requires a 3D texture.
+    D3,
+}
+
+/// Where a single channel of a texture view is read from.
+#[derive(Default, Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum TextureComponentSwizzle {
+    /// Read from the same channel.
+    #[default]
+    Identity,
+    Zero,
+    One,
+    R,
+    G,
+    B,
+    A,
+}
+
+/// Remaps the channels of a view created with [`Context::new_texture_view`].
+#[derive(Default, Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct TextureViewSwizzle {
+    pub r: TextureComponentSwizzle,
+    pub g: TextureComponentSwizzle,
+    pub b: TextureComponentSwizzle,
+    pub a: TextureComponentSwizzle,
+}
+
+impl TextureViewSwizzle {
+    /// Read a single channel texture such as `R8` as an opaque grayscale image.
+    pub const LUMINANCE: Self = TextureViewSwizzle {
+        r: TextureComponentSwizzle::R,
+        g: TextureComponentSwizzle::R,
+        b: TextureComponentSwizzle::R,
+        a: TextureComponentSwizzle::One,
+    };
+
+    /// Whether every channel is read from itself.
+    pub fn is_identity(&self) -> bool {
+        [
+            (self.r, TextureComponentSwizzle::R),
+            (self.g, TextureComponentSwizzle::G),
+            (self.b, TextureComponentSwizzle::B),
+            (self.a, TextureComponentSwizzle::A),
+        ]
+        .into_iter()
+        .all(|(swizzle, channel)| {
+            swizzle == TextureComponentSwizzle::Identity || swizzle == channel
+        })
+    }
+}
+
+/// Allows for the configuration of:
+/// - Mip level range
+/// - Layer range
+/// - View dimension
+/// - Channel swizzle
+#[derive(Default, Debug, Clone)]
+pub struct NewTextureViewExt {
+    /// Defaults to 0.
+    pub base_mip_level: Option<u32>,
+    /// Defaults to every mip level from `base_mip_level` onwards.
+    pub mip_level_count: Option<u32>,
+    /// For cubemaps, this selects the face in the order of [`CubemapTextureUpload`].
+    /// Defaults to 0.
+    pub base_layer: Option<u32>,
+    /// Defaults to 1 for `D2` views, otherwise every layer from `base_layer` onwards.
+    pub layer_count: Option<u32>,
+    /// Defaults to the dimension of the texture.
+    /// Use `D2` to view a single cubemap face or array layer as a regular texture.
+    pub dimension: Option<TextureViewDimension>,
+    /// Not supported on WebGpu.
+    pub swizzle: Option<TextureViewSwizzle>,
+}
+
+//  The validated subresource range of a texture view.
+pub(crate) struct TextureViewRange {
+    pub base_mip_level: u32,
+    pub mip_level_count: u32,
+    pub base_layer: u32,
+    pub layer_count: u32,
+    pub dimension: TextureViewDimension,
+}
+
+impl NewTextureViewExt {
+    pub(crate) fn resolve_range(
+        &self,
+        texture_dimension: TextureViewDimension,
+        mip_levels: u32,
+        array_layers: u32,
+    ) -> GResult<TextureViewRange> {
+        let dimension = self.dimension.unwrap_or(texture_dimension);
+        let base_mip_level = self.base_mip_level.unwrap_or(0);
+        let mip_level_count = self
+            .mip_level_count
+            .unwrap_or(mip_levels.saturating_sub(base_mip_level));
+        let base_layer = self.base_layer.unwrap_or(0);
+        let layer_count = self.layer_count.unwrap_or(match dimension {
+            TextureViewDimension::D2 => 1,
+            _ => array_layers.saturating_sub(base_layer),
+        });
+
+        let mip_level_end = base_mip_level.checked_add(mip_level_count);
+        if mip_level_count == 0 || mip_level_end.is_none_or(|end| end > mip_levels) {
+            Err(gpu_api_err!(
+                "texture view {} mip levels from {} are out of range for {} mip levels",
+                mip_level_count,
+                base_mip_level,
+                mip_levels
+            ))?;
+        }
+        let layer_end = base_layer.checked_add(layer_count);
+        if layer_count == 0 || layer_end.is_none_or(|end| end > array_layers) {
+            Err(gpu_api_err!(
+                "texture view {} layers from {} are out of range for {} layers",
+                layer_count,
+                base_layer,
+                array_layers
+            ))?;
+        }
+
+        let is_3d = texture_dimension == TextureViewDimension::D3;
+        let is_valid = match dimension {
+            TextureViewDimension::D2 => !is_3d && layer_count == 1,
+            TextureViewDimension::D2Array => !is_3d,
+            TextureViewDimension::Cube => {
+                texture_dimension == TextureViewDimension::Cube && layer_count == 6
+            }
+            TextureViewDimension::D3 => is_3d,
+        };
+        if !is_valid {
+            Err(gpu_api_err!(
+                "texture view dimension {:?} with {} layers is not compatible with a {:?} texture",
+                dimension,
+                layer_count,
+                texture_dimension
+            ))?;
+        }
+
+        Ok(TextureViewRange {
+            base_mip_level,
+            mip_level_count,
+            base_layer,
+            layer_count,
+            dimension,
+        })
+    }
 }
 
 impl Context {
@@ -116,6 +568,88 @@ impl Context {
         }
     }
 
+    /// Overwrite part of a single mip level and layer, leaving the rest of the texture intact.
+    /// For 3D textures, this uploads a range of slices.
+    pub fn upload_texture_region(
+        &mut self,
+        texture: TextureId,
+        region: TextureRegion,
+        data: &[u8],
+        ext: Option<UploadTextureRegionExt>,
+    ) -> GResult<()> {
+        match self {
+            Self::Vulkan(vk) => vk.upload_texture_region(texture, region, data, ext),
+            Self::WebGpu(wgpu) => wgpu.upload_texture_region(texture, region, data, ext),
+        }
+    }
+
+    /// Upload a single layer of a texture array.
+    pub fn upload_texture_layer(
+        &mut self,
+        texture: TextureId,
+        layer: u32,
+        data: &[u8],
+        ext: Option<UploadTextureExt>,
+    ) -> GResult<()> {
+        match self {
+            Self::Vulkan(vk) => vk.upload_texture_layer(texture, layer, data, ext),
+            Self::WebGpu(wgpu) => wgpu.upload_texture_layer(texture, layer, data, ext),
+        }
+    }
+
+    /// Read a single mip level and layer of a texture back into CPU memory.
+    /// The returned data is tightly packed, see [`TextureFormat::image_byte_size`].
+    /// For 3D textures, `layer` should be 0 and every slice is returned from front to back.
+    ///
+    /// This method is **not** compatible with WebGpu.
+    /// Use [`Context::async_read_texture`] instead.
+    pub fn read_texture(
+        &mut self,
+        texture: TextureId,
+        mip_level: u32,
+        layer: u32,
+        ext: Option<ReadTextureExt>,
+    ) -> GResult<Vec<u8>> {
+        match self {
+            Self::Vulkan(vk) => vk.read_texture(texture, mip_level, layer, ext),
+            Self::WebGpu(wgpu) => wgpu.read_texture(texture, mip_level, layer, ext),
+        }
+    }
+
+    /// Read a single mip level and layer of a texture back into CPU memory.
+    /// See [`Context::read_texture`].
+    ///
+    /// This method is compatible with WebGpu.
+    pub async fn async_read_texture(
+        &mut self,
+        texture: TextureId,
+        mip_level: u32,
+        layer: u32,
+        ext: Option<ReadTextureExt>,
+    ) -> GResult<Vec<u8>> {
+        match self {
+            Self::Vulkan(vk) => vk.async_read_texture(texture, mip_level, layer, ext).await,
+            Self::WebGpu(wgpu) => {
+                wgpu.async_read_texture(texture, mip_level, layer, ext)
+                    .await
+            }
+        }
+    }
+
+    /// Create an extra view of part of a texture, such as a single mip level, a range of layers,
+    /// or a single cubemap face.
+    /// Bind it with [`ShaderUniformType::TextureView`].
+    pub fn new_texture_view(
+        &mut self,
+        texture: TextureId,
+        ext: Option<NewTextureViewExt>,
+    ) -> GResult<TextureViewId> {
+        match self {
+            Self::Vulkan(vk) => vk.new_texture_view(texture, ext),
+            Self::WebGpu(wgpu) => wgpu.new_texture_view(texture, ext),
+        }
+    }
+
     pub fn get_texture_max_lod(&self, texture: TextureId) -> GResult<f32> {
         match self {
             Self::Vulkan(vk) => vk.get_texture_max_lod(texture),
@@ -123,6 +657,16 @@ impl Context {
         }
     }
 
+    /// Compressed texture families that can be used with [`Context::new_texture`].
+    /// On WebGPU with [`extensions::WebGpuInitFromWindow`], these depend on the features
+    /// requested when creating the device.
+    pub fn get_supported_texture_compressions(&self) -> GResult<Vec<TextureCompression>> {
+        match self {
+            Self::Vulkan(vk) => vk.get_supported_texture_compressions(),
+            Self::WebGpu(wgpu) => wgpu.get_supported_texture_compressions(),
+        }
+    }
+
     pub fn new_attachment_image(
         &mut self,
         initial_width: usize,
@@ -139,4 +683,52 @@ impl Context {
             }
         }
     }
+
+    /// The depth format chosen for a depth attachment image.
+    /// This may differ from [`NewAttachmentImageExt::depth_format`] if that format is not supported.
+    pub fn get_attachment_image_depth_format(
+        &self,
+        image: AttachmentImageId,
+    ) -> GResult<AttachmentImageDepthFormat> {
+        match self {
+            Self::Vulkan(vk) => vk.get_attachment_image_depth_format(image),
+            Self::WebGpu(wgpu) => wgpu.get_attachment_image_depth_format(image),
+        }
+    }
+
+    /// Read from a synced attachment image after rendering.
+    /// Sync an attachment image using [`Submit::sync_attachment_image`].
+    /// Color images are returned as tightly packed texels of their color format.
+    /// Depth images are returned without stencil as one texel of their depth format,
+    /// `u16` for D16, a `u32` with 24 bits of depth for D24, `f32` for D32, and `u8` for S8.
+    /// Depth images cannot be multisampled.
+    /// On WebGpu, only D16, D32 and D32S8 depth images can be read.
+    ///
+    /// This method is **not** compatible with WebGpu.
+    /// Use [`Context::async_read_synced_attachment_image`] instead.
+    pub fn read_synced_attachment_image(
+        &self,
+        image: AttachmentImageId,
+        ext: Option<ReadSyncedAttachmentImageExt>,
+    ) -> GResult<Vec<u8>> {
+        match self {
+            Self::Vulkan(vk) => vk.read_synced_attachment_image(image, ext),
+            Self::WebGpu(wgpu) => wgpu.read_synced_attachment_image(image, ext),
+        }
+    }
+
+    /// Read from a synced attachment image after rendering.
+    /// See [`Context::read_synced_attachment_image`].
+    ///
+    /// This method is compatible with WebGpu.
+    pub async fn async_read_synced_attachment_image(
+        &self,
+        image: AttachmentImageId,
+        ext: Option<ReadSyncedAttachmentImageExt>,
+    ) -> GResult<Vec<u8>> {
+        match self {
+            Self::Vulkan(vk) => vk.async_read_synced_attachment_image(image, ext).await,
+            Self::WebGpu(wgpu) => wgpu.async_read_synced_attachment_image(image, ext).await,
+        }
+    }
 }
@@ -0,0 +1,57 @@
+use super::*;
+
+/// Allows for the configuration of:
+/// - Nothing for now
+#[derive(Default, Debug, Clone)]
+pub struct AsyncUploadTextureExt {}
+
+/// Allows for the configuration of:
+/// - Nothing for now
+#[derive(Default, Debug, Clone)]
+pub struct AsyncUploadBufferExt {}
+
+impl Context {
+    /// Upload texture data without blocking.
+    /// On Vulkan, this runs on a dedicated transfer queue if the gpu has one,
+    /// after every previously submitted frame has finished on the gpu.
+    /// `data` must be exactly the size of mip level 0, see [`TextureFormat::image_byte_size`].
+    /// The texture should not be used until [`Context::poll_upload`] returns `true`.
+    /// Mipmap generation is not available here, use [`Context::upload_texture`] instead.
+    pub fn async_upload_texture(
+        &mut self,
+        texture: TextureId,
+        data: &[u8],
+        ext: Option<AsyncUploadTextureExt>,
+    ) -> GResult<UploadId> {
+        match self {
+            Self::Vulkan(vk) => vk.async_upload_texture(texture, data, ext),
+            Self::WebGpu(wgpu) => wgpu.async_upload_texture(texture, data, ext),
+        }
+    }
+
+    /// Upload buffer data starting at the beginning of `buffer` without blocking.
+    /// On Vulkan, this runs on a dedicated transfer queue if the gpu has one,
+    /// after every previously submitted frame has finished on the gpu.
+    /// The rest of the buffer keeps its contents.
+    /// The buffer should not be used until [`Context::poll_upload`] returns `true`.
+    pub fn async_upload_buffer<T: Copy>(
+        &mut self,
+        buffer: BufferId,
+        data: &[T],
+        ext: Option<AsyncUploadBufferExt>,
+    ) -> GResult<UploadId> {
+        match self {
+            Self::Vulkan(vk) => vk.async_upload_buffer(buffer, data, ext),
+            Self::WebGpu(wgpu) => wgpu.async_upload_buffer(buffer, data, ext),
+        }
+    }
+
+    /// Returns `true` once the upload has completed.
+    /// Upload ids are never reused, so polling a finished upload again keeps returning `true`.
+    pub fn poll_upload(&mut self, upload: UploadId) -> GResult<bool> {
+        match self {
+            Self::Vulkan(vk) => vk.poll_upload(upload),
+            Self::WebGpu(wgpu) => wgpu.poll_upload(upload),
+        }
+    }
+}
@@ -16,6 +16,32 @@ impl MockContext {
         unimplemented!("No backend chosen")
     }
 
+    pub fn new_buffer<T: Copy>(
+        &mut self,
+        _data: &[T],
+        _usage: BufferUsage,
+        _storage_type: BufferStorageType,
+        _ext: Option<NewBufferExt>,
+    ) -> GResult<BufferId> {
+        unimplemented!("No backend chosen")
+    }
+
+    pub fn read_synced_buffer(
+        &self,
+        _buffer: BufferId,
+        _ext: Option<ReadSyncedBufferExt>,
+    ) -> GResult<Vec<u8>> {
+        unimplemented!("No backend chosen")
+    }
+
+    pub async fn async_read_synced_buffer(
+        &self,
+        _buffer: BufferId,
+        _ext: Option<ReadSyncedBufferExt>,
+    ) -> GResult<Vec<u8>> {
+        unimplemented!("No backend chosen")
+    }
+
     pub fn new_vertex_buffer(
         &mut self,
         _data: &[VertexBufferElement],
@@ -82,10 +108,84 @@ impl MockContext {
         unimplemented!("No backend chosen")
     }
 
+    pub fn upload_texture_region(
+        &mut self,
+        _texture: TextureId,
+        _region: TextureRegion,
+        _data: &[u8],
+        _ext: Option<UploadTextureRegionExt>,
+    ) -> GResult<()> {
+        unimplemented!("No backend chosen")
+    }
+
+    pub fn upload_texture_layer(
+        &mut self,
+        _texture: TextureId,
+        _layer: u32,
+        _data: &[u8],
+        _ext: Option<UploadTextureExt>,
+    ) -> GResult<()> {
+        unimplemented!("No backend chosen")
+    }
+
+    pub fn read_texture(
+        &mut self,
+        _texture: TextureId,
+        _mip_level: u32,
+        _layer: u32,
+        _ext: Option<ReadTextureExt>,
+    ) -> GResult<Vec<u8>> {
+        unimplemented!("No backend chosen")
+    }
+
+    pub async fn async_read_texture(
+        &mut self,
+        _texture: TextureId,
+        _mip_level: u32,
+        _layer: u32,
+        _ext: Option<ReadTextureExt>,
+    ) -> GResult<Vec<u8>> {
+        unimplemented!("No backend chosen")
+    }
+
+    pub fn new_texture_view(
+        &mut self,
+        _texture: TextureId,
+        _ext: Option<NewTextureViewExt>,
+    ) -> GResult<TextureViewId> {
+        unimplemented!("No backend chosen")
+    }
+
     pub fn get_texture_max_lod(&self, _texture: TextureId) -> GResult<f32> {
         unimplemented!("No backend chosen")
     }
 
+    pub fn get_supported_texture_compressions(&self) -> GResult<Vec<TextureCompression>> {
+        unimplemented!("No backend chosen")
+    }
+
+    pub fn async_upload_texture(
+        &mut self,
+        _texture: TextureId,
+        _data: &[u8],
+        _ext: Option<AsyncUploadTextureExt>,
+    ) -> GResult<UploadId> {
+        unimplemented!("No backend chosen")
+    }
+
+    pub fn async_upload_buffer<T: Copy>(
+        &mut self,
+        _buffer: BufferId,
+        _data: &[T],
+        _ext: Option<AsyncUploadBufferExt>,
+    ) -> GResult<UploadId> {
+        unimplemented!("No backend chosen")
+    }
+
+    pub fn poll_upload(&mut self, _upload: UploadId) -> GResult<bool> {
+        unimplemented!("No backend chosen")
+    }
+
     pub fn new_attachment_image(
         &mut self,
         _initial_width: usize,
@@ -96,6 +196,29 @@ impl MockContext {
         unimplemented!("No backend chosen")
     }
 
+    pub fn get_attachment_image_depth_format(
+        &self,
+        _image: AttachmentImageId,
+    ) -> GResult<AttachmentImageDepthFormat> {
+        unimplemented!("No backend chosen")
+    }
+
+    pub fn read_synced_attachment_image(
+        &self,
+        _image: AttachmentImageId,
+        _ext: Option<ReadSyncedAttachmentImageExt>,
+    ) -> GResult<Vec<u8>> {
+        unimplemented!("No backend chosen")
+    }
+
+    pub async fn async_read_synced_attachment_image(
+        &self,
+        _image: AttachmentImageId,
+        _ext: Option<ReadSyncedAttachmentImageExt>,
+    ) -> GResult<Vec<u8>> {
+        unimplemented!("No backend chosen")
+    }
+
     pub fn compile_pass(
         &mut self,
         _pass: &Pass,
@@ -8,6 +8,37 @@ impl VkContext {
         attachment_usage: AttachmentImageUsage,
         ext: Option<NewAttachmentImageExt>,
     ) -> GResult<AttachmentImageId> {
+        let mut ext = ext.unwrap_or_default();
+        if ext.array_layers == Some(0) {
+            Err(gpu_api_err!(
+                "vulkan attachment image arrays need at least one layer"
+            ))?;
+        }
+        let max_mip_levels = std::cmp::max(initial_width, initial_height).max(1).ilog2() + 1;
+        if let Some(mip_levels) = ext.mip_levels {
+            if mip_levels == 0 || mip_levels > max_mip_levels {
+                Err(gpu_api_err!(
+                    "vulkan attachment image mip levels {} must be between 1 and {}",
+                    mip_levels,
+                    max_mip_levels
+                ))?;
+            }
+        }
+        if attachment_usage == AttachmentImageUsage::DepthAttachment {
+            let depth_format = ext
+                .depth_format
+                .unwrap_or(AttachmentImageDepthFormat::D32SFloatS8UInt);
+            let supported_depth_format = depth_format
+                .fallbacks()
+                .iter()
+                .find(|&&fallback| self.is_depth_format_supported(fallback))
+                .ok_or(gpu_api_err!(
+                    "vulkan no supported depth format could replace {:?}",
+                    depth_format
+                ))?;
+            ext.depth_format = Some(*supported_depth_format);
+        }
+
         let attachment_image = VkAttachmentImage::new(
             &self.core.dev,
             &self.drop_queue,
@@ -15,21 +46,175 @@ impl VkContext {
             initial_width,
             initial_height,
             attachment_usage,
-            ext.unwrap_or_default(),
+            ext,
         )?;
+        {
+            let _misc_command = self.core.misc_command()?;
+            unsafe {
+                attachment_image.cmd_init_layout(&self.core.dev, self.core.misc_command_buffer);
+            }
+        }
         self.attachment_images.push(attachment_image);
 
         Ok(AttachmentImageId::from_id(self.attachment_images.len() - 1))
     }
+
+    pub fn get_attachment_image_depth_format(
+        &self,
+        image: AttachmentImageId,
+    ) -> GResult<AttachmentImageDepthFormat> {
+        let attachment_image = self.attachment_images.get(image.id()).ok_or(gpu_api_err!(
+            "vulkan get attachment image depth format id {:?} does not exist",
+            image
+        ))?;
+        attachment_image.get_depth_format().ok_or(gpu_api_err!(
+            "vulkan get attachment image depth format id {:?} is not a depth attachment",
+            image
+        ))
+    }
+
+    fn is_depth_format_supported(&self, depth_format: AttachmentImageDepthFormat) -> bool {
+        let properties = unsafe {
+            self.core.instance.get_physical_device_format_properties(
+                self.core.physical_dev,
+                depth_format_into_vk(depth_format),
+            )
+        };
+        properties.optimal_tiling_features.contains(
+            vk::FormatFeatureFlags::DEPTH_STENCIL_ATTACHMENT
+                | vk::FormatFeatureFlags::SAMPLED_IMAGE,
+        )
+    }
+
+    pub fn read_synced_attachment_image(
+        &self,
+        image: AttachmentImageId,
+        _ext: Option<ReadSyncedAttachmentImageExt>,
+    ) -> GResult<Vec<u8>> {
+        let attachment_image = self.attachment_images.get(image.id()).ok_or(gpu_api_err!(
+            "vulkan read synced attachment image id {:?} does not exist",
+            image
+        ))?;
+        let readback = attachment_image.readback.as_ref().ok_or(gpu_api_err!(
+            "vulkan read synced attachment image id {:?} was not synced with Submit::sync_attachment_image",
+            image
+        ))?;
+        let mapped_ptr = readback
+            .allocation
+            .mapped_ptr()
+            .ok_or(gpu_api_err!(
+                "vulkan gpu_allocator, this buffer cannot be mapped"
+            ))?
+            .as_ptr() as *const u8;
+        Ok(unsafe { std::slice::from_raw_parts(mapped_ptr, readback.size) }.to_vec())
+    }
+
+    pub async fn async_read_synced_attachment_image(
+        &self,
+        image: AttachmentImageId,
+        ext: Option<ReadSyncedAttachmentImageExt>,
+    ) -> GResult<Vec<u8>> {
+        self.read_synced_attachment_image(image, ext)
+    }
+
+    //  Transitions the attachment images sampled by a pass before and after it.
+    //  Subresources the pass renders into are left in their attachment layout.
+    pub(super) unsafe fn cmd_attachment_textures_barrier(
+        &self,
+        cmd: vk::CommandBuffer,
+        images: &[AttachmentImageId],
+        rendered: &[(AttachmentImageId, AttachmentImageSubresource)],
+        to_sampled: bool,
+    ) {
+        for image in images {
+            let attachment_image = &self.attachment_images[image.id()];
+            let rendered_subresources = rendered
+                .iter()
+                .filter(|(rendered_image, _)| rendered_image == image)
+                .map(|&(_, subresource)| subresource)
+                .collect::<Vec<_>>();
+            attachment_image.cmd_sampled_barrier(
+                &self.core.dev,
+                cmd,
+                &rendered_subresources,
+                to_sampled,
+            );
+        }
+    }
+
+    pub(super) fn check_attachment_image_copy_backs(
+        &self,
+        images: &[AttachmentImageId],
+    ) -> GResult<()> {
+        for image in images {
+            let attachment_image = self.attachment_images.get(image.id()).ok_or(gpu_api_err!(
+                "vulkan attachment image sync id {:?} does not exist",
+                image
+            ))?;
+            if attachment_image.samples != vk::SampleCountFlags::TYPE_1 {
+                Err(gpu_api_err!(
+                    "vulkan attachment image sync id {:?} cannot be multisampled",
+                    image
+                ))?;
+            }
+        }
+        Ok(())
+    }
+
+    //  Creates the readback buffer of each image on its first `Submit::sync_attachment_image`.
+    pub(super) fn new_attachment_image_readbacks(
+        &mut self,
+        images: &[AttachmentImageId],
+    ) -> GResult<()> {
+        for image in images {
+            let attachment_image = &mut self.attachment_images[image.id()];
+            if attachment_image.readback.is_none() {
+                let size = attachment_image.width
+                    * attachment_image.height
+                    * texel_byte_size(attachment_image.format);
+                attachment_image.readback = Some(VkBuffer::new(
+                    &self.core.dev,
+                    &self.drop_queue,
+                    &mut self.alloc,
+                    size,
+                    vk::BufferUsageFlags::TRANSFER_DST,
+                    MemoryLocation::GpuToCpu,
+                )?);
+            }
+        }
+        Ok(())
+    }
+
+    //  Records every `Submit::sync_attachment_image` into each image's readback buffer.
+    pub(super) unsafe fn cmd_attachment_image_copy_backs(
+        &self,
+        cmd: vk::CommandBuffer,
+        images: &[AttachmentImageId],
+    ) {
+        for image in images {
+            self.attachment_images[image.id()].cmd_copy_back(&self.core.dev, cmd);
+        }
+    }
 }
 
 pub struct VkAttachmentImage {
     ext: NewAttachmentImageExt,
+    width: usize,
+    height: usize,
+    pub samples: vk::SampleCountFlags,
+    //  Created by the first `Submit::sync_attachment_image`.
+    readback: Option<VkBuffer>,
 
     pub image: VkImage,
     pub format: vk::Format,
     pub image_view: vk::ImageView,
     pub attachment_usage: AttachmentImageUsage,
+    mip_levels: u32,
+    kind: VkImageKind,
+    //  One 2D view per layer and mip level, only for layered or mipmapped images.
+    subresource_views: Vec<vk::ImageView>,
+    //  Only for attachment images created with `NewAttachmentImageExt::enable_storage`.
+    pub storage_image_view: Option<vk::ImageView>,
 
     drop_queue_ref: VkDropQueueRef,
 }
@@ -48,9 +233,12 @@ impl VkAttachmentImage {
             AttachmentImageUsage::ColorAttachment => ext
                 .color_format
                 .map(|color_format| match color_format {
-                    AttachmentImageColorFormat::R8UNorm => vk::Format::R8G8_UNORM,
+                    AttachmentImageColorFormat::R8UNorm => vk::Format::R8_UNORM,
                     AttachmentImageColorFormat::R8G8UNorm => vk::Format::R8G8_UNORM,
                     AttachmentImageColorFormat::R8G8B8A8UNorm => vk::Format::R8G8B8A8_UNORM,
+                    AttachmentImageColorFormat::R16G16B16A16SFloat => {
+                        vk::Format::R16G16B16A16_SFLOAT
+                    }
                     AttachmentImageColorFormat::R32SFloat => vk::Format::R32_SFLOAT,
                     AttachmentImageColorFormat::R32G32SFloat => vk::Format::R32G32_SFLOAT,
                     AttachmentImageColorFormat::R32G32B32A32SFloat => {
@@ -58,18 +246,63 @@ impl VkAttachmentImage {
                     }
                 })
                 .unwrap_or(VK_COLOR_ATTACHMENT_FORMAT),
-            AttachmentImageUsage::DepthAttachment => VK_DEPTH_ATTACHMENT_FORMAT,
+            AttachmentImageUsage::DepthAttachment => ext
+                .depth_format
+                .map(depth_format_into_vk)
+                .unwrap_or(VK_DEPTH_ATTACHMENT_FORMAT),
         };
-        let usages = vk::ImageUsageFlags::INPUT_ATTACHMENT
+        let mut usages = vk::ImageUsageFlags::INPUT_ATTACHMENT
+            | vk::ImageUsageFlags::TRANSFER_SRC
+            | vk::ImageUsageFlags::TRANSFER_DST
+            | vk::ImageUsageFlags::SAMPLED
             | match attachment_usage {
                 AttachmentImageUsage::ColorAttachment => vk::ImageUsageFlags::COLOR_ATTACHMENT,
                 AttachmentImageUsage::DepthAttachment => {
                     vk::ImageUsageFlags::DEPTH_STENCIL_ATTACHMENT
                 }
             };
+        if ext.enable_storage.is_some() {
+            if !is_storage_format(format) {
+                Err(gpu_api_err!(
+                    "vulkan storage attachment image format {:?} is not a storage texture format",
+                    format
+                ))?;
+            }
+            usages |= vk::ImageUsageFlags::STORAGE;
+        }
         let aspect = match attachment_usage {
             AttachmentImageUsage::ColorAttachment => vk::ImageAspectFlags::COLOR,
-            AttachmentImageUsage::DepthAttachment => vk::ImageAspectFlags::DEPTH,
+            AttachmentImageUsage::DepthAttachment => {
+                if ext.depth_format == Some(AttachmentImageDepthFormat::S8UInt) {
+                    vk::ImageAspectFlags::STENCIL
+                } else {
+                    vk::ImageAspectFlags::DEPTH
+                }
+            }
+        };
+
+        let mip_levels = ext.mip_levels.unwrap_or(1);
+        let kind = if ext.enable_cubemap.is_some() {
+            if ext.array_layers.is_some() {
+                Err(gpu_api_err!(
+                    "vulkan attachment image cubemaps cannot have array layers"
+                ))?;
+            }
+            VkImageKind::Cubemap
+        } else if let Some(array_layers) = ext.array_layers {
+            VkImageKind::Image2DArray(array_layers)
+        } else {
+            VkImageKind::Image2D
+        };
+
+        let samples = match ext.msaa_samples.unwrap_or_default() {
+            MsaaSampleCount::Sample1 => vk::SampleCountFlags::TYPE_1,
+            MsaaSampleCount::Sample2 => vk::SampleCountFlags::TYPE_2,
+            MsaaSampleCount::Sample4 => vk::SampleCountFlags::TYPE_4,
+            MsaaSampleCount::Sample8 => vk::SampleCountFlags::TYPE_8,
+            MsaaSampleCount::Sample16 => vk::SampleCountFlags::TYPE_16,
+            MsaaSampleCount::Sample32 => vk::SampleCountFlags::TYPE_32,
+            MsaaSampleCount::Sample64 => vk::SampleCountFlags::TYPE_64,
         };
 
         let image = VkImage::new(
@@ -79,17 +312,9 @@ impl VkAttachmentImage {
             format,
             usages,
             aspect,
-            match ext.msaa_samples.unwrap_or_default() {
-                MsaaSampleCount::Sample1 => vk::SampleCountFlags::TYPE_1,
-                MsaaSampleCount::Sample2 => vk::SampleCountFlags::TYPE_2,
-                MsaaSampleCount::Sample4 => vk::SampleCountFlags::TYPE_4,
-                MsaaSampleCount::Sample8 => vk::SampleCountFlags::TYPE_8,
-                MsaaSampleCount::Sample16 => vk::SampleCountFlags::TYPE_16,
-                MsaaSampleCount::Sample32 => vk::SampleCountFlags::TYPE_32,
-                MsaaSampleCount::Sample64 => vk::SampleCountFlags::TYPE_64,
-            },
-            1,
-            false,
+            samples,
+            mip_levels,
+            kind,
             vk::Extent3D {
                 width: width as u32,
                 height: height as u32,
@@ -97,14 +322,37 @@ impl VkAttachmentImage {
             },
         )?;
 
-        let image_view = new_image_view(dev, image.image, format, aspect, 1, false)?;
+        let image_view = new_image_view(dev, image.image, format, aspect, mip_levels, kind)?;
+        //  Storage images can only be bound with a single mip level.
+        let storage_image_view = ext
+            .enable_storage
+            .map(|_| new_image_view(dev, image.image, format, aspect, 1, kind))
+            .transpose()?;
+        let subresource_views = if kind != VkImageKind::Image2D || mip_levels > 1 {
+            (0..kind.array_layers())
+                .flat_map(|layer| (0..mip_levels).map(move |mip_level| (layer, mip_level)))
+                .map(|(layer, mip_level)| {
+                    new_subresource_image_view(dev, image.image, format, aspect, mip_level, layer)
+                })
+                .collect::<GResult<Vec<_>>>()?
+        } else {
+            vec![]
+        };
 
         Ok(VkAttachmentImage {
             ext,
+            width,
+            height,
+            samples,
+            readback: None,
             attachment_usage,
             format,
             image,
             image_view,
+            mip_levels,
+            kind,
+            subresource_views,
+            storage_image_view,
             drop_queue_ref: Arc::clone(drop_queue_ref),
         })
     }
@@ -130,17 +378,365 @@ impl VkAttachmentImage {
         let _drop_old = std::mem::replace(self, new_attachment_image);
         Ok(())
     }
+
+    pub fn get_render_view(
+        &self,
+        subresource: AttachmentImageSubresource,
+    ) -> GResult<vk::ImageView> {
+        if subresource.layer >= self.kind.array_layers() || subresource.mip_level >= self.mip_levels
+        {
+            Err(gpu_api_err!(
+                "vulkan attachment image subresource {:?} is out of range for {} layers and {} mip levels",
+                subresource,
+                self.kind.array_layers(),
+                self.mip_levels
+            ))?;
+        }
+        Ok(if self.subresource_views.is_empty() {
+            self.image_view
+        } else {
+            self.subresource_views
+                [(subresource.layer * self.mip_levels + subresource.mip_level) as usize]
+        })
+    }
+
+    pub fn transfer_image(&self) -> VkTransferImage {
+        let (layout, aspect, access) = self.attachment_layout();
+        VkTransferImage {
+            image: self.image.image,
+            format: self.format,
+            layout,
+            aspect,
+            access,
+            samples: self.samples,
+            buffer_aspect: self.image.view_aspect,
+            block_dimensions: (1, 1),
+            block_byte_size: texel_byte_size(self.format),
+            mip_levels: self.mip_levels,
+            array_layers: self.kind.array_layers(),
+            width: self.width,
+            height: self.height,
+            depth: 1,
+        }
+    }
+
+    pub fn get_depth_format(&self) -> Option<AttachmentImageDepthFormat> {
+        match self.attachment_usage {
+            AttachmentImageUsage::ColorAttachment => None,
+            AttachmentImageUsage::DepthAttachment => Some(
+                self.ext
+                    .depth_format
+                    .unwrap_or(AttachmentImageDepthFormat::D32SFloatS8UInt),
+            ),
+        }
+    }
+
+    pub fn is_surface_relative(&self) -> bool {
+        self.ext.fixed_size.is_none()
+    }
+
+    //  Attachment images always rest in their attachment layout between passes.
+    fn attachment_layout(&self) -> (vk::ImageLayout, vk::ImageAspectFlags, vk::AccessFlags) {
+        match self.attachment_usage {
+            AttachmentImageUsage::ColorAttachment => (
+                vk::ImageLayout::COLOR_ATTACHMENT_OPTIMAL,
+                vk::ImageAspectFlags::COLOR,
+                vk::AccessFlags::COLOR_ATTACHMENT_WRITE,
+            ),
+            AttachmentImageUsage::DepthAttachment => {
+                let depth_format = self.get_depth_format().unwrap();
+                let mut aspect = vk::ImageAspectFlags::empty();
+                if depth_format.has_depth() {
+                    aspect |= vk::ImageAspectFlags::DEPTH;
+                }
+                if depth_format.has_stencil() {
+                    aspect |= vk::ImageAspectFlags::STENCIL;
+                }
+                (
+                    vk::ImageLayout::DEPTH_STENCIL_ATTACHMENT_OPTIMAL,
+                    aspect,
+                    vk::AccessFlags::DEPTH_STENCIL_ATTACHMENT_WRITE,
+                )
+            }
+        }
+    }
+
+    pub fn sampled_layout(&self) -> vk::ImageLayout {
+        match self.attachment_usage {
+            AttachmentImageUsage::ColorAttachment => vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL,
+            AttachmentImageUsage::DepthAttachment => {
+                vk::ImageLayout::DEPTH_STENCIL_READ_ONLY_OPTIMAL
+            }
+        }
+    }
+
+    pub unsafe fn cmd_init_layout(&self, dev: &Device, cmd: vk::CommandBuffer) {
+        let (layout, range_aspect, access) = self.attachment_layout();
+        let barrier = vk::ImageMemoryBarrier::builder()
+            .old_layout(vk::ImageLayout::UNDEFINED)
+            .new_layout(layout)
+            .image(self.image.image)
+            .subresource_range(
+                vk::ImageSubresourceRange::builder()
+                    .aspect_mask(range_aspect)
+                    .level_count(self.mip_levels)
+                    .layer_count(self.kind.array_layers())
+                    .build(),
+            )
+            .src_access_mask(vk::AccessFlags::empty())
+            .dst_access_mask(access)
+            .build();
+        dev.cmd_pipeline_barrier(
+            cmd,
+            vk::PipelineStageFlags::TOP_OF_PIPE,
+            vk::PipelineStageFlags::ALL_COMMANDS,
+            vk::DependencyFlags::empty(),
+            &[],
+            &[],
+            &[barrier],
+        );
+    }
+
+    pub fn subresource_count(&self) -> u32 {
+        self.mip_levels * self.kind.array_layers()
+    }
+
+    //  Moves between the attachment layout and the sampled layout, skipping `rendered`.
+    unsafe fn cmd_sampled_barrier(
+        &self,
+        dev: &Device,
+        cmd: vk::CommandBuffer,
+        rendered: &[AttachmentImageSubresource],
+        to_sampled: bool,
+    ) {
+        let (layout, range_aspect, access) = self.attachment_layout();
+        let (old_layout, new_layout, src_access, dst_access) = if to_sampled {
+            (
+                layout,
+                self.sampled_layout(),
+                access,
+                vk::AccessFlags::SHADER_READ,
+            )
+        } else {
+            (
+                self.sampled_layout(),
+                layout,
+                vk::AccessFlags::SHADER_READ,
+                access,
+            )
+        };
+        let array_layers = self.kind.array_layers();
+        let ranges = if rendered.is_empty() {
+            vec![(0, self.mip_levels, 0, array_layers)]
+        } else {
+            //  Runs of layers per mip level that are not rendered into.
+            let mut ranges = vec![];
+            for mip_level in 0..self.mip_levels {
+                let mut base_layer = 0;
+                for layer in 0..=array_layers {
+                    let is_rendered =
+                        rendered.contains(&AttachmentImageSubresource { layer, mip_level });
+                    if layer == array_layers || is_rendered {
+                        if layer > base_layer {
+                            ranges.push((mip_level, 1, base_layer, layer - base_layer));
+                        }
+                        base_layer = layer + 1;
+                    }
+                }
+            }
+            ranges
+        };
+        let barriers = ranges
+            .into_iter()
+            .map(|(base_mip, mip_count, base_layer, layer_count)| {
+                vk::ImageMemoryBarrier::builder()
+                    .old_layout(old_layout)
+                    .new_layout(new_layout)
+                    .image(self.image.image)
+                    .subresource_range(
+                        vk::ImageSubresourceRange::builder()
+                            .aspect_mask(range_aspect)
+                            .base_mip_level(base_mip)
+                            .level_count(mip_count)
+                            .base_array_layer(base_layer)
+                            .layer_count(layer_count)
+                            .build(),
+                    )
+                    .src_access_mask(src_access)
+                    .dst_access_mask(dst_access)
+                    .build()
+            })
+            .collect::<Vec<_>>();
+        dev.cmd_pipeline_barrier(
+            cmd,
+            vk::PipelineStageFlags::ALL_COMMANDS,
+            vk::PipelineStageFlags::ALL_COMMANDS,
+            vk::DependencyFlags::empty(),
+            &[],
+            &[],
+            &barriers,
+        );
+    }
+
+    //  Moves between the attachment layout and the general layout used for storage.
+    pub unsafe fn cmd_storage_barrier(
+        &self,
+        dev: &Device,
+        cmd: vk::CommandBuffer,
+        to_storage: bool,
+    ) {
+        let (layout, range_aspect, access) = self.attachment_layout();
+        let storage_access = vk::AccessFlags::SHADER_READ | vk::AccessFlags::SHADER_WRITE;
+        let (old_layout, new_layout, src_access, dst_access) = if to_storage {
+            (layout, vk::ImageLayout::GENERAL, access, storage_access)
+        } else {
+            (vk::ImageLayout::GENERAL, layout, storage_access, access)
+        };
+        let barrier = vk::ImageMemoryBarrier::builder()
+            .old_layout(old_layout)
+            .new_layout(new_layout)
+            .image(self.image.image)
+            .subresource_range(
+                vk::ImageSubresourceRange::builder()
+                    .aspect_mask(range_aspect)
+                    .level_count(self.mip_levels)
+                    .layer_count(self.kind.array_layers())
+                    .build(),
+            )
+            .src_access_mask(src_access)
+            .dst_access_mask(dst_access)
+            .build();
+        dev.cmd_pipeline_barrier(
+            cmd,
+            vk::PipelineStageFlags::ALL_COMMANDS,
+            vk::PipelineStageFlags::ALL_COMMANDS,
+            vk::DependencyFlags::empty(),
+            &[],
+            &[],
+            &[barrier],
+        );
+    }
+
+    unsafe fn cmd_copy_back(&self, dev: &Device, cmd: vk::CommandBuffer) {
+        let (layout, range_aspect, access) = self.attachment_layout();
+        let copy_aspect = self.image.view_aspect;
+
+        let image_transfer_barrier = vk::ImageMemoryBarrier::builder()
+            .old_layout(layout)
+            .new_layout(vk::ImageLayout::TRANSFER_SRC_OPTIMAL)
+            .image(self.image.image)
+            .subresource_range(
+                vk::ImageSubresourceRange::builder()
+                    .aspect_mask(range_aspect)
+                    .level_count(self.mip_levels)
+                    .layer_count(self.kind.array_layers())
+                    .build(),
+            )
+            .src_access_mask(access)
+            .dst_access_mask(vk::AccessFlags::TRANSFER_READ)
+            .build();
+
+        let mut image_use_barrier = image_transfer_barrier;
+        image_use_barrier.old_layout = vk::ImageLayout::TRANSFER_SRC_OPTIMAL;
+        image_use_barrier.new_layout = layout;
+        image_use_barrier.src_access_mask = vk::AccessFlags::TRANSFER_READ;
+        image_use_barrier.dst_access_mask = access;
+
+        let copy_region = vk::BufferImageCopy::builder()
+            .image_subresource(
+                vk::ImageSubresourceLayers::builder()
+                    .aspect_mask(copy_aspect)
+                    .layer_count(1)
+                    .build(),
+            )
+            .image_extent(vk::Extent3D {
+                width: self.width as u32,
+                height: self.height as u32,
+                depth: 1,
+            })
+            .build();
+
+        let readback = self.readback.as_ref().unwrap();
+        let readback_barrier = vk::BufferMemoryBarrier::builder()
+            .buffer(readback.buffer)
+            .size(vk::WHOLE_SIZE)
+            .src_access_mask(vk::AccessFlags::TRANSFER_WRITE)
+            .dst_access_mask(vk::AccessFlags::HOST_READ)
+            .build();
+
+        dev.cmd_pipeline_barrier(
+            cmd,
+            vk::PipelineStageFlags::ALL_COMMANDS,
+            vk::PipelineStageFlags::TRANSFER,
+            vk::DependencyFlags::empty(),
+            &[],
+            &[],
+            &[image_transfer_barrier],
+        );
+        dev.cmd_copy_image_to_buffer(
+            cmd,
+            self.image.image,
+            vk::ImageLayout::TRANSFER_SRC_OPTIMAL,
+            readback.buffer,
+            &[copy_region],
+        );
+        dev.cmd_pipeline_barrier(
+            cmd,
+            vk::PipelineStageFlags::TRANSFER,
+            vk::PipelineStageFlags::ALL_COMMANDS | vk::PipelineStageFlags::HOST,
+            vk::DependencyFlags::empty(),
+            &[],
+            &[readback_barrier],
+            &[image_use_barrier],
+        );
+    }
 }
 
 impl Drop for VkAttachmentImage {
     fn drop(&mut self) {
         let image_view = self.image_view;
+        let subresource_views = std::mem::take(&mut self.subresource_views);
+        let storage_image_view = self.storage_image_view;
 
         self.drop_queue_ref
             .lock()
             .unwrap()
             .push(Box::new(move |dev, _| unsafe {
                 dev.destroy_image_view(image_view, None);
+                subresource_views
+                    .iter()
+                    .for_each(|&view| dev.destroy_image_view(view, None));
+                if let Some(storage_image_view) = storage_image_view {
+                    dev.destroy_image_view(storage_image_view, None);
+                }
             }))
     }
 }
+
+fn depth_format_into_vk(depth_format: AttachmentImageDepthFormat) -> vk::Format {
+    match depth_format {
+        AttachmentImageDepthFormat::D16UNorm => vk::Format::D16_UNORM,
+        AttachmentImageDepthFormat::D24UNormS8UInt => vk::Format::D24_UNORM_S8_UINT,
+        AttachmentImageDepthFormat::D32SFloat => vk::Format::D32_SFLOAT,
+        AttachmentImageDepthFormat::D32SFloatS8UInt => vk::Format::D32_SFLOAT_S8_UINT,
+        AttachmentImageDepthFormat::S8UInt => vk::Format::S8_UINT,
+    }
+}
+
+//  Depth is copied out without its stencil, and D24 is padded to 32 bits.
+fn texel_byte_size(format: vk::Format) -> usize {
+    match format {
+        vk::Format::R8_UNORM => 1,
+        vk::Format::R8G8_UNORM => 2,
+        vk::Format::R8G8B8A8_UNORM | vk::Format::R8G8B8A8_SRGB => 4,
+        vk::Format::R32_SFLOAT => 4,
+        vk::Format::R32G32_SFLOAT | vk::Format::R16G16B16A16_SFLOAT => 8,
+        vk::Format::R32G32B32A32_SFLOAT => 16,
+        vk::Format::D16_UNORM => 2,
+        vk::Format::D24_UNORM_S8_UINT | vk::Format::D32_SFLOAT | vk::Format::D32_SFLOAT_S8_UINT => {
+            4
+        }
+        vk::Format::S8_UINT => 1,
+        _ => unreachable!(),
+    }
+}
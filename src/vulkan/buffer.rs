@@ -8,6 +8,184 @@ use std::mem::ManuallyDrop;
 //  to use the Gpu Cpu shared buffers? I'm not sure if the CpuToGpu option allows for this, so for
 //  now, staging buffers are the norm.
 
+impl VkContext {
+    pub fn new_buffer<T: Copy>(
+        &mut self,
+        data: &[T],
+        usage: BufferUsage,
+        storage_type: BufferStorageType,
+        _ext: Option<NewBufferExt>,
+    ) -> GResult<BufferId> {
+        let mut vkusage = vk::BufferUsageFlags::empty();
+        [
+            (BufferUsage::VERTEX, vk::BufferUsageFlags::VERTEX_BUFFER),
+            (BufferUsage::INDEX, vk::BufferUsageFlags::INDEX_BUFFER),
+            (BufferUsage::UNIFORM, vk::BufferUsageFlags::UNIFORM_BUFFER),
+            (BufferUsage::STORAGE, vk::BufferUsageFlags::STORAGE_BUFFER),
+            (BufferUsage::COPY_SRC, vk::BufferUsageFlags::TRANSFER_SRC),
+            (BufferUsage::COPY_DST, vk::BufferUsageFlags::TRANSFER_DST),
+        ]
+        .into_iter()
+        .for_each(|(usage_bit, vkusage_bit)| {
+            if usage.contains(usage_bit) {
+                vkusage |= vkusage_bit;
+            }
+        });
+
+        //  Reading back and transfers require the staging buffer to stick around.
+        let storage_type =
+            if usage.contains(BufferUsage::COPY_SRC) || usage.contains(BufferUsage::COPY_DST) {
+                BufferStorageType::Dynamic
+            } else {
+                storage_type
+            };
+
+        let (buf, staging) = self.new_generic_buffer(data, storage_type, vkusage)?;
+        let buffer = VkGenericBuffer {
+            buffer: buf,
+            staging,
+            usage,
+        };
+        self.buffers.push(buffer);
+        Ok(BufferId::from_id(self.buffers.len() - 1))
+    }
+
+    pub fn read_synced_buffer(
+        &self,
+        buffer: BufferId,
+        _ext: Option<ReadSyncedBufferExt>,
+    ) -> GResult<Vec<u8>> {
+        self.get_buffer_with_usage(buffer, BufferUsage::COPY_SRC)?;
+        //  Buffers with `BufferUsage::COPY_SRC` always keep their staging buffer.
+        let staging = self.buffers[buffer.id()].staging.as_ref().unwrap();
+        let mapped_ptr = staging
+            .allocation
+            .mapped_ptr()
+            .ok_or(gpu_api_err!(
+                "vulkan gpu_allocator, this buffer cannot be mapped"
+            ))?
+            .as_ptr() as *const u8;
+        Ok(unsafe { std::slice::from_raw_parts(mapped_ptr, staging.size) }.to_vec())
+    }
+
+    pub async fn async_read_synced_buffer(
+        &self,
+        buffer: BufferId,
+        ext: Option<ReadSyncedBufferExt>,
+    ) -> GResult<Vec<u8>> {
+        self.read_synced_buffer(buffer, ext)
+    }
+
+    pub fn async_upload_buffer<T: Copy>(
+        &mut self,
+        buffer: BufferId,
+        data: &[T],
+        _ext: Option<AsyncUploadBufferExt>,
+    ) -> GResult<UploadId> {
+        let size = std::mem::size_of_val(data);
+        let generic = self.buffers.get(buffer.id()).ok_or(gpu_api_err!(
+            "vulkan async upload buffer id {:?} does not exist",
+            buffer
+        ))?;
+        if size > generic.buffer.size {
+            Err(gpu_api_err!(
+                "vulkan async upload buffer size ({}) > buffer size ({})",
+                size,
+                generic.buffer.size
+            ))?
+        }
+        let dst_buffer = generic.buffer.buffer;
+
+        let mut upload = self.begin_async_upload(size)?;
+        upload
+            .staging
+            .map_copy_data(data.as_ptr() as *const u8, size, 0)?;
+
+        let copy_region = vk::BufferCopy::builder().size(size as u64).build();
+        unsafe {
+            //  The rest of the buffer is kept, so it must be handed over to the transfer queue.
+            upload.cmd_begin_buffer(&self.core.dev, dst_buffer);
+            self.core.dev.cmd_copy_buffer(
+                upload.transfer_command_buffer,
+                upload.staging.buffer,
+                dst_buffer,
+                &[copy_region],
+            );
+            upload.cmd_finish_buffer(&self.core.dev, dst_buffer);
+        }
+
+        self.submit_async_upload(upload)
+    }
+
+    pub fn get_vertex_buffer(&self, vbo: GenericVertexBufferId) -> GResult<&VkBuffer> {
+        match vbo {
+            GenericVertexBufferId::Vertex(vbo) => self
+                .vbos
+                .get(vbo.id())
+                .map(|vbo| &vbo.buffer)
+                .ok_or(gpu_api_err!(
+                    "vulkan vertex buffer id {:?} does not exist",
+                    vbo
+                )),
+            GenericVertexBufferId::Buffer(buffer) => {
+                self.get_buffer_with_usage(buffer, BufferUsage::VERTEX)
+            }
+        }
+    }
+
+    pub fn get_index_buffer(&self, ibo: GenericIndexBufferId) -> GResult<&VkBuffer> {
+        match ibo {
+            GenericIndexBufferId::Index(ibo) => self
+                .ibos
+                .get(ibo.id())
+                .map(|ibo| &ibo.buffer)
+                .ok_or(gpu_api_err!(
+                    "vulkan index buffer id {:?} does not exist",
+                    ibo
+                )),
+            GenericIndexBufferId::Buffer(buffer) => {
+                self.get_buffer_with_usage(buffer, BufferUsage::INDEX)
+            }
+        }
+    }
+
+    //  Only for ids that have already been checked with `get_vertex_buffer`.
+    pub(super) fn vertex_buffer(&self, vbo: GenericVertexBufferId) -> &VkBuffer {
+        match vbo {
+            GenericVertexBufferId::Vertex(vbo) => &self.vbos[vbo.id()].buffer,
+            GenericVertexBufferId::Buffer(buffer) => &self.buffers[buffer.id()].buffer,
+        }
+    }
+
+    //  Only for ids that have already been checked with `get_index_buffer`.
+    pub(super) fn index_buffer(&self, ibo: GenericIndexBufferId) -> &VkBuffer {
+        match ibo {
+            GenericIndexBufferId::Index(ibo) => &self.ibos[ibo.id()].buffer,
+            GenericIndexBufferId::Buffer(buffer) => &self.buffers[buffer.id()].buffer,
+        }
+    }
+
+    pub fn get_buffer_with_usage(
+        &self,
+        buffer: BufferId,
+        usage: BufferUsage,
+    ) -> GResult<&VkBuffer> {
+        let generic = self
+            .buffers
+            .get(buffer.id())
+            .ok_or(gpu_api_err!("vulkan buffer id {:?} does not exist", buffer))?;
+        generic
+            .usage
+            .contains(usage)
+            .then_some(&generic.buffer)
+            .ok_or(gpu_api_err!(
+                "vulkan buffer id {:?} was not created with usage {:?}",
+                buffer,
+                usage
+            ))
+    }
+}
+
 impl VkContext {
     pub fn new_vertex_buffer(
         &mut self,
@@ -120,22 +298,27 @@ pub struct VkShaderStorageBuffer {
     pub staging: Option<VkBuffer>,
 }
 
+pub struct VkGenericBuffer {
+    pub buffer: VkBuffer,
+    pub staging: Option<VkBuffer>,
+    pub usage: BufferUsage,
+}
+
 impl VkVertexBuffer {
-    pub fn cmd_transfer(
-        &mut self,
-        dev: &Device,
-        cmd_buf: vk::CommandBuffer,
-        data: &[VertexBufferElement],
-    ) -> GResult<()> {
+    pub fn check_transfer(&self) -> GResult<()> {
+        check_transfer_generic(&self.staging, "vertex buffer")
+    }
+
+    pub fn write_transfer(&mut self, data: &[VertexBufferElement]) -> GResult<()> {
+        write_transfer_generic(&mut self.staging, data, self.buffer.size, 0)
+    }
+
+    pub fn cmd_transfer(&self, dev: &Device, cmd_buf: vk::CommandBuffer) {
         cmd_transfer_generic(
             dev,
             cmd_buf,
             &self.buffer,
-            self.staging.as_mut().ok_or(gpu_api_err!(
-                "vulkan this vertex buffer does not support transfers"
-            ))?,
-            data,
-            self.buffer.size,
+            &self.staging,
             self.buffer.size,
             0,
         )
@@ -143,21 +326,20 @@ impl VkVertexBuffer {
 }
 
 impl VkIndexBuffer {
-    pub fn cmd_transfer(
-        &mut self,
-        dev: &Device,
-        cmd_buf: vk::CommandBuffer,
-        data: &[IndexBufferElement],
-    ) -> GResult<()> {
+    pub fn check_transfer(&self) -> GResult<()> {
+        check_transfer_generic(&self.staging, "index buffer")
+    }
+
+    pub fn write_transfer(&mut self, data: &[IndexBufferElement]) -> GResult<()> {
+        write_transfer_generic(&mut self.staging, data, self.buffer.size, 0)
+    }
+
+    pub fn cmd_transfer(&self, dev: &Device, cmd_buf: vk::CommandBuffer) {
         cmd_transfer_generic(
             dev,
             cmd_buf,
             &self.buffer,
-            self.staging.as_mut().ok_or(gpu_api_err!(
-                "vulkan this index buffer does not support transfers"
-            ))?,
-            data,
-            self.buffer.size,
+            &self.staging,
             self.buffer.size,
             0,
         )
@@ -165,21 +347,20 @@ impl VkIndexBuffer {
 }
 
 impl VkUniformBuffer {
-    pub fn cmd_transfer<T>(
-        &mut self,
-        dev: &Device,
-        cmd_buf: vk::CommandBuffer,
-        data: &[T],
-    ) -> GResult<()> {
+    pub fn check_transfer(&self) -> GResult<()> {
+        check_transfer_generic(&self.staging, "uniform buffer")
+    }
+
+    pub fn write_transfer<T>(&mut self, data: &[T]) -> GResult<()> {
+        write_transfer_generic(&mut self.staging, data, self.buffer.size, 0)
+    }
+
+    pub fn cmd_transfer(&self, dev: &Device, cmd_buf: vk::CommandBuffer) {
         cmd_transfer_generic(
             dev,
             cmd_buf,
             &self.buffer,
-            self.staging.as_mut().ok_or(gpu_api_err!(
-                "vulkan this uniform buffer does not support transfers"
-            ))?,
-            data,
-            self.buffer.size,
+            &self.staging,
             self.buffer.size,
             0,
         )
@@ -187,41 +368,88 @@ impl VkUniformBuffer {
 }
 
 impl VkDynamicUniformBuffer {
-    pub fn cmd_transfer<T>(
-        &mut self,
-        dev: &Device,
-        cmd_buf: vk::CommandBuffer,
-        data: &[T],
-        index: usize,
-    ) -> GResult<()> {
+    pub fn check_transfer(&self) -> GResult<()> {
+        check_transfer_generic(&self.staging, "dynamic uniform buffer")
+    }
+
+    pub fn write_transfer<T>(&mut self, data: &[T], index: usize) -> GResult<()> {
+        write_transfer_generic(
+            &mut self.staging,
+            data,
+            self.item_size,
+            index * self.per_index_offset,
+        )
+    }
+
+    pub fn cmd_transfer(&self, dev: &Device, cmd_buf: vk::CommandBuffer, index: usize) {
         cmd_transfer_generic(
             dev,
             cmd_buf,
             &self.buffer,
-            self.staging.as_mut().ok_or(gpu_api_err!(
-                "vulkan this dynamic uniform buffer does not support transfers"
-            ))?,
-            data,
+            &self.staging,
             self.per_index_offset,
-            self.item_size,
             index * self.per_index_offset,
         )
     }
 }
 
-fn cmd_transfer_generic<T>(
+impl VkGenericBuffer {
+    pub fn check_transfer(&self, data: &[u8]) -> GResult<()> {
+        if data.len() > self.buffer.size {
+            Err(gpu_api_err!(
+                "vulkan buffer transfer size ({}) > buffer size ({})",
+                data.len(),
+                self.buffer.size
+            ))?
+        }
+        check_transfer_generic(&self.staging, "buffer")
+    }
+
+    pub fn write_transfer(&mut self, data: &[u8]) -> GResult<()> {
+        write_transfer_generic(&mut self.staging, data, data.len(), 0)
+    }
+
+    pub fn cmd_transfer(&self, dev: &Device, cmd_buf: vk::CommandBuffer, size: usize) {
+        cmd_transfer_generic(dev, cmd_buf, &self.buffer, &self.staging, size, 0)
+    }
+}
+
+fn check_transfer_generic(staging: &Option<VkBuffer>, name: &str) -> GResult<()> {
+    if staging.is_none() {
+        Err(gpu_api_err!(
+            "vulkan this {} does not support transfers",
+            name
+        ))?
+    }
+    Ok(())
+}
+
+//  Host writes happen before the swapchain image is acquired, see `VkContext::submit`.
+fn write_transfer_generic<T>(
+    staging: &mut Option<VkBuffer>,
+    data: &[T],
+    item_size: usize,
+    offset: usize,
+) -> GResult<()> {
+    staging
+        .as_mut()
+        .ok_or(gpu_api_err!(
+            "vulkan this buffer does not support transfers"
+        ))?
+        .map_copy_data(data.as_ptr() as *const u8, item_size, offset)
+}
+
+fn cmd_transfer_generic(
     dev: &Device,
     cmd_buf: vk::CommandBuffer,
     buffer: &VkBuffer,
-    staging: &mut VkBuffer,
-    data: &[T],
+    staging: &Option<VkBuffer>,
     size: usize,
-    item_size: usize,
     offset: usize,
-) -> GResult<()> {
-    staging.map_copy_data(data.as_ptr() as *const u8, item_size, offset)?;
-    VkBuffer::cmd_upload_copy_data(staging, buffer, dev, size, offset, cmd_buf);
-    Ok(())
+) {
+    if let Some(staging) = staging {
+        VkBuffer::cmd_upload_copy_data(staging, buffer, dev, size, offset, cmd_buf);
+    }
 }
 
 impl VkContext {
@@ -27,6 +27,7 @@ impl VkDescriptors {
             vk::DescriptorType::STORAGE_BUFFER_DYNAMIC,
             vk::DescriptorType::INPUT_ATTACHMENT,
             vk::DescriptorType::SAMPLED_IMAGE,
+            vk::DescriptorType::STORAGE_IMAGE,
             vk::DescriptorType::SAMPLER,
         ];
 
@@ -63,16 +64,19 @@ impl VkDescriptors {
         //  Descriptor Layouts
         uniforms.iter().for_each(|uniform| {
             let binding_info = match uniform.ty {
-                ShaderUniformType::UniformBuffer(_) => vk::DescriptorSetLayoutBinding::builder()
-                    .binding(uniform.binding as u32)
-                    .stage_flags(
-                        vk::ShaderStageFlags::VERTEX
-                            | vk::ShaderStageFlags::FRAGMENT
-                            | vk::ShaderStageFlags::COMPUTE,
-                    )
-                    .descriptor_type(vk::DescriptorType::UNIFORM_BUFFER)
-                    .descriptor_count(1)
-                    .build(),
+                ShaderUniformType::UniformBuffer(_)
+                | ShaderUniformType::GenericUniformBuffer(_) => {
+                    vk::DescriptorSetLayoutBinding::builder()
+                        .binding(uniform.binding as u32)
+                        .stage_flags(
+                            vk::ShaderStageFlags::VERTEX
+                                | vk::ShaderStageFlags::FRAGMENT
+                                | vk::ShaderStageFlags::COMPUTE,
+                        )
+                        .descriptor_type(vk::DescriptorType::UNIFORM_BUFFER)
+                        .descriptor_count(1)
+                        .build()
+                }
                 ShaderUniformType::DynamicUniformBuffer(id) => {
                     dynamic_indices.push(DynamicGenericBufferId::Uniform(id));
                     vk::DescriptorSetLayoutBinding::builder()
@@ -87,7 +91,9 @@ impl VkDescriptors {
                         .build()
                 }
                 ShaderUniformType::ShaderStorageBuffer(_)
-                | ShaderUniformType::ShaderStorageBufferReadOnly(_) => {
+                | ShaderUniformType::ShaderStorageBufferReadOnly(_)
+                | ShaderUniformType::GenericShaderStorageBuffer(_)
+                | ShaderUniformType::GenericShaderStorageBufferReadOnly(_) => {
                     vk::DescriptorSetLayoutBinding::builder()
                         .binding(uniform.binding as u32)
                         .stage_flags(
@@ -111,10 +117,60 @@ impl VkDescriptors {
                     .descriptor_type(vk::DescriptorType::SAMPLED_IMAGE)
                     .descriptor_count(1)
                     .build(),
-                ShaderUniformType::Sampler(_) => vk::DescriptorSetLayoutBinding::builder()
+                ShaderUniformType::Texture3D(_) => vk::DescriptorSetLayoutBinding::builder()
+                    .binding(uniform.binding as u32)
+                    .stage_flags(vk::ShaderStageFlags::FRAGMENT | vk::ShaderStageFlags::COMPUTE)
+                    .descriptor_type(vk::DescriptorType::SAMPLED_IMAGE)
+                    .descriptor_count(1)
+                    .build(),
+                ShaderUniformType::TextureArray(_) => vk::DescriptorSetLayoutBinding::builder()
+                    .binding(uniform.binding as u32)
+                    .stage_flags(vk::ShaderStageFlags::FRAGMENT | vk::ShaderStageFlags::COMPUTE)
+                    .descriptor_type(vk::DescriptorType::SAMPLED_IMAGE)
+                    .descriptor_count(1)
+                    .build(),
+                ShaderUniformType::TextureView(_) => vk::DescriptorSetLayoutBinding::builder()
+                    .binding(uniform.binding as u32)
+                    .stage_flags(vk::ShaderStageFlags::FRAGMENT | vk::ShaderStageFlags::COMPUTE)
+                    .descriptor_type(vk::DescriptorType::SAMPLED_IMAGE)
+                    .descriptor_count(1)
+                    .build(),
+                ShaderUniformType::Sampler(_) | ShaderUniformType::ComparisonSampler(_) => {
+                    vk::DescriptorSetLayoutBinding::builder()
+                        .binding(uniform.binding as u32)
+                        .stage_flags(vk::ShaderStageFlags::FRAGMENT | vk::ShaderStageFlags::COMPUTE)
+                        .descriptor_type(vk::DescriptorType::SAMPLER)
+                        .descriptor_count(1)
+                        .build()
+                }
+                ShaderUniformType::AttachmentTexture(_) => {
+                    vk::DescriptorSetLayoutBinding::builder()
+                        .binding(uniform.binding as u32)
+                        .stage_flags(vk::ShaderStageFlags::FRAGMENT | vk::ShaderStageFlags::COMPUTE)
+                        .descriptor_type(vk::DescriptorType::SAMPLED_IMAGE)
+                        .descriptor_count(1)
+                        .build()
+                }
+                ShaderUniformType::MultisampledAttachmentTexture(_) => {
+                    vk::DescriptorSetLayoutBinding::builder()
+                        .binding(uniform.binding as u32)
+                        .stage_flags(vk::ShaderStageFlags::FRAGMENT | vk::ShaderStageFlags::COMPUTE)
+                        .descriptor_type(vk::DescriptorType::SAMPLED_IMAGE)
+                        .descriptor_count(1)
+                        .build()
+                }
+                ShaderUniformType::DepthAttachmentTexture(_) => {
+                    vk::DescriptorSetLayoutBinding::builder()
+                        .binding(uniform.binding as u32)
+                        .stage_flags(vk::ShaderStageFlags::FRAGMENT | vk::ShaderStageFlags::COMPUTE)
+                        .descriptor_type(vk::DescriptorType::SAMPLED_IMAGE)
+                        .descriptor_count(1)
+                        .build()
+                }
+                ShaderUniformType::StorageTexture(..) => vk::DescriptorSetLayoutBinding::builder()
                     .binding(uniform.binding as u32)
                     .stage_flags(vk::ShaderStageFlags::FRAGMENT | vk::ShaderStageFlags::COMPUTE)
-                    .descriptor_type(vk::DescriptorType::SAMPLER)
+                    .descriptor_type(vk::DescriptorType::STORAGE_IMAGE)
                     .descriptor_count(1)
                     .build(),
                 ShaderUniformType::InputAttachment(_) => vk::DescriptorSetLayoutBinding::builder()
@@ -260,6 +316,51 @@ impl VkDescriptors {
 
                     Ok(ret)
                 }
+                ShaderUniformType::GenericUniformBuffer(buffer_id) => {
+                    let buffer = context.get_buffer_with_usage(buffer_id, BufferUsage::UNIFORM)?;
+                    let buffer_info = vk::DescriptorBufferInfo::builder()
+                        .buffer(buffer.buffer)
+                        .range(buffer.size as u64)
+                        .offset(0)
+                        .build();
+
+                    let buffer_info_list = vec![buffer_info];
+
+                    let ret = vk::WriteDescriptorSet::builder()
+                        .dst_set(self.descriptor_sets[uniform.set])
+                        .dst_binding(uniform.binding as u32)
+                        .dst_array_element(0)
+                        .descriptor_type(vk::DescriptorType::UNIFORM_BUFFER)
+                        .buffer_info(&buffer_info_list)
+                        .build();
+
+                    buffer_infos.push(buffer_info_list);
+
+                    Ok(ret)
+                }
+                ShaderUniformType::GenericShaderStorageBuffer(buffer_id)
+                | ShaderUniformType::GenericShaderStorageBufferReadOnly(buffer_id) => {
+                    let buffer = context.get_buffer_with_usage(buffer_id, BufferUsage::STORAGE)?;
+                    let buffer_info = vk::DescriptorBufferInfo::builder()
+                        .buffer(buffer.buffer)
+                        .range(buffer.size as u64)
+                        .offset(0)
+                        .build();
+
+                    let buffer_info_list = vec![buffer_info];
+
+                    let ret = vk::WriteDescriptorSet::builder()
+                        .dst_set(self.descriptor_sets[uniform.set])
+                        .dst_binding(uniform.binding as u32)
+                        .dst_array_element(0)
+                        .descriptor_type(vk::DescriptorType::STORAGE_BUFFER)
+                        .buffer_info(&buffer_info_list)
+                        .build();
+
+                    buffer_infos.push(buffer_info_list);
+
+                    Ok(ret)
+                }
                 ShaderUniformType::Texture(texture_id) => {
                     let texture = context.textures.get(texture_id.id()).ok_or(gpu_api_err!(
                         "vulkan uniform texture id {:?} does not exist",
@@ -308,11 +409,108 @@ impl VkDescriptors {
 
                     Ok(ret)
                 }
-                ShaderUniformType::Sampler(sampler_id) => {
+                ShaderUniformType::Texture3D(texture_id) => {
+                    let texture = context.textures.get(texture_id.id()).ok_or(gpu_api_err!(
+                        "vulkan uniform 3d texture id {:?} does not exist",
+                        texture_id
+                    ))?;
+                    if texture.kind != VkImageKind::Image3D {
+                        Err(gpu_api_err!(
+                            "vulkan uniform 3d texture id {:?} is not a 3d texture",
+                            texture_id
+                        ))?;
+                    }
+                    let image_info = vk::DescriptorImageInfo::builder()
+                        .image_view(texture.image_view)
+                        .image_layout(vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL)
+                        .build();
+
+                    let image_info_list = vec![image_info];
+
+                    let ret = vk::WriteDescriptorSet::builder()
+                        .dst_set(self.descriptor_sets[uniform.set])
+                        .dst_binding(uniform.binding as u32)
+                        .dst_array_element(0)
+                        .descriptor_type(vk::DescriptorType::SAMPLED_IMAGE)
+                        .image_info(&image_info_list)
+                        .build();
+
+                    image_infos.push(image_info_list);
+
+                    Ok(ret)
+                }
+                ShaderUniformType::TextureArray(texture_id) => {
+                    let texture = context.textures.get(texture_id.id()).ok_or(gpu_api_err!(
+                        "vulkan uniform texture array id {:?} does not exist",
+                        texture_id
+                    ))?;
+                    if !matches!(texture.kind, VkImageKind::Image2DArray(_)) {
+                        Err(gpu_api_err!(
+                            "vulkan uniform texture array id {:?} is not a texture array",
+                            texture_id
+                        ))?;
+                    }
+                    let image_info = vk::DescriptorImageInfo::builder()
+                        .image_view(texture.image_view)
+                        .image_layout(vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL)
+                        .build();
+
+                    let image_info_list = vec![image_info];
+
+                    let ret = vk::WriteDescriptorSet::builder()
+                        .dst_set(self.descriptor_sets[uniform.set])
+                        .dst_binding(uniform.binding as u32)
+                        .dst_array_element(0)
+                        .descriptor_type(vk::DescriptorType::SAMPLED_IMAGE)
+                        .image_info(&image_info_list)
+                        .build();
+
+                    image_infos.push(image_info_list);
+
+                    Ok(ret)
+                }
+                ShaderUniformType::TextureView(texture_view_id) => {
+                    let texture_view =
+                        context
+                            .texture_views
+                            .get(texture_view_id.id())
+                            .ok_or(gpu_api_err!(
+                                "vulkan uniform texture view id {:?} does not exist",
+                                texture_view_id
+                            ))?;
+                    let image_info = vk::DescriptorImageInfo::builder()
+                        .image_view(texture_view.image_view)
+                        .image_layout(vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL)
+                        .build();
+
+                    let image_info_list = vec![image_info];
+
+                    let ret = vk::WriteDescriptorSet::builder()
+                        .dst_set(self.descriptor_sets[uniform.set])
+                        .dst_binding(uniform.binding as u32)
+                        .dst_array_element(0)
+                        .descriptor_type(vk::DescriptorType::SAMPLED_IMAGE)
+                        .image_info(&image_info_list)
+                        .build();
+
+                    image_infos.push(image_info_list);
+
+                    Ok(ret)
+                }
+                ShaderUniformType::Sampler(sampler_id)
+                | ShaderUniformType::ComparisonSampler(sampler_id) => {
                     let sampler = context.sampler_cache.get(sampler_id).ok_or(gpu_api_err!(
                         "vulkan uniform sampler id {:?} does not exist",
                         sampler_id
                     ))?;
+                    let is_comparison = matches!(uniform.ty, ShaderUniformType::ComparisonSampler(_));
+                    if context.sampler_cache.is_comparison(sampler_id) != Some(is_comparison) {
+                        Err(gpu_api_err!(
+                            "vulkan uniform sampler id {:?} must {}be a comparison sampler",
+                            sampler_id,
+                            if is_comparison { "" } else { "not " }
+                        ))?;
+                    }
                     let image_info = vk::DescriptorImageInfo::builder().sampler(sampler).build();
 
                     let image_info_list = vec![image_info];
@@ -329,6 +527,181 @@ impl VkDescriptors {
 
                     Ok(ret)
                 }
+                ShaderUniformType::AttachmentTexture(attachment_image_id) => {
+                    let attachment_image = context
+                        .attachment_images
+                        .get(attachment_image_id.id())
+                        .ok_or(gpu_api_err!(
+                            "vulkan uniform attachment texture id {:?} does not exist",
+                            attachment_image_id
+                        ))?;
+                    if attachment_image.samples != vk::SampleCountFlags::TYPE_1 {
+                        Err(gpu_api_err!(
+                            "vulkan uniform attachment texture id {:?} cannot be multisampled",
+                            attachment_image_id
+                        ))?;
+                    }
+                    let image_info = vk::DescriptorImageInfo::builder()
+                        .image_view(attachment_image.image_view)
+                        .image_layout(attachment_image.sampled_layout())
+                        .build();
+
+                    let image_info_list = vec![image_info];
+
+                    let ret = vk::WriteDescriptorSet::builder()
+                        .dst_set(self.descriptor_sets[uniform.set])
+                        .dst_binding(uniform.binding as u32)
+                        .dst_array_element(0)
+                        .descriptor_type(vk::DescriptorType::SAMPLED_IMAGE)
+                        .image_info(&image_info_list)
+                        .build();
+
+                    image_infos.push(image_info_list);
+
+                    Ok(ret)
+                }
+                ShaderUniformType::MultisampledAttachmentTexture(attachment_image_id) => {
+                    let attachment_image = context
+                        .attachment_images
+                        .get(attachment_image_id.id())
+                        .ok_or(gpu_api_err!(
+                            "vulkan uniform multisampled attachment texture id {:?} does not exist",
+                            attachment_image_id
+                        ))?;
+                    if attachment_image.samples == vk::SampleCountFlags::TYPE_1 {
+                        Err(gpu_api_err!(
+                            "vulkan uniform multisampled attachment texture id {:?} is not multisampled",
+                            attachment_image_id
+                        ))?;
+                    }
+                    let image_info = vk::DescriptorImageInfo::builder()
+                        .image_view(attachment_image.image_view)
+                        .image_layout(attachment_image.sampled_layout())
+                        .build();
+
+                    let image_info_list = vec![image_info];
+
+                    let ret = vk::WriteDescriptorSet::builder()
+                        .dst_set(self.descriptor_sets[uniform.set])
+                        .dst_binding(uniform.binding as u32)
+                        .dst_array_element(0)
+                        .descriptor_type(vk::DescriptorType::SAMPLED_IMAGE)
+                        .image_info(&image_info_list)
+                        .build();
+
+                    image_infos.push(image_info_list);
+
+                    Ok(ret)
+                }
+                ShaderUniformType::DepthAttachmentTexture(attachment_image_id) => {
+                    let attachment_image = context
+                        .attachment_images
+                        .get(attachment_image_id.id())
+                        .ok_or(gpu_api_err!(
+                            "vulkan uniform depth attachment texture id {:?} does not exist",
+                            attachment_image_id
+                        ))?;
+                    if attachment_image.attachment_usage != AttachmentImageUsage::DepthAttachment
+                        || !attachment_image
+                            .image
+                            .view_aspect
+                            .contains(vk::ImageAspectFlags::DEPTH)
+                    {
+                        Err(gpu_api_err!(
+                            "vulkan uniform depth attachment texture id {:?} has no depth",
+                            attachment_image_id
+                        ))?;
+                    }
+                    if attachment_image.samples != vk::SampleCountFlags::TYPE_1 {
+                        Err(gpu_api_err!(
+                            "vulkan uniform depth attachment texture id {:?} cannot be multisampled",
+                            attachment_image_id
+                        ))?;
+                    }
+                    let image_info = vk::DescriptorImageInfo::builder()
+                        .image_view(attachment_image.image_view)
+                        .image_layout(attachment_image.sampled_layout())
+                        .build();
+
+                    let image_info_list = vec![image_info];
+
+                    let ret = vk::WriteDescriptorSet::builder()
+                        .dst_set(self.descriptor_sets[uniform.set])
+                        .dst_binding(uniform.binding as u32)
+                        .dst_array_element(0)
+                        .descriptor_type(vk::DescriptorType::SAMPLED_IMAGE)
+                        .image_info(&image_info_list)
+                        .build();
+
+                    image_infos.push(image_info_list);
+
+                    Ok(ret)
+                }
+                ShaderUniformType::StorageTexture(storage_texture, _, storage_format) => {
+                    let (format, storage_image_view) = match storage_texture {
+                        GenericTextureId::Texture(texture_id) => {
+                            let texture =
+                                context.textures.get(texture_id.id()).ok_or(gpu_api_err!(
+                                    "vulkan uniform storage texture id {:?} does not exist",
+                                    texture_id
+                                ))?;
+                            (texture.image.format, texture.storage_image_view)
+                        }
+                        GenericTextureId::AttachmentImage(attachment_image_id) => {
+                            let attachment_image = context
+                                .attachment_images
+                                .get(attachment_image_id.id())
+                                .ok_or(gpu_api_err!(
+                                    "vulkan uniform storage texture attachment image id {:?} does not exist",
+                                    attachment_image_id
+                                ))?;
+                            if attachment_image.samples != vk::SampleCountFlags::TYPE_1 {
+                                Err(gpu_api_err!(
+                                    "vulkan uniform storage texture attachment image id {:?} cannot be multisampled",
+                                    attachment_image_id
+                                ))?;
+                            }
+                            (attachment_image.format, attachment_image.storage_image_view)
+                        }
+                    };
+                    let storage_image_view = storage_image_view.ok_or(gpu_api_err!(
+                        "vulkan uniform storage texture {:?} was not created with enable_storage",
+                        storage_texture
+                    ))?;
+                    let expected_format = match storage_format {
+                        ShaderStorageTextureFormat::Rgba8UNorm => vk::Format::R8G8B8A8_UNORM,
+                        ShaderStorageTextureFormat::Rgba16SFloat => vk::Format::R16G16B16A16_SFLOAT,
+                        ShaderStorageTextureFormat::R32SFloat => vk::Format::R32_SFLOAT,
+                        ShaderStorageTextureFormat::Rgba32SFloat => {
+                            vk::Format::R32G32B32A32_SFLOAT
+                        }
+                    };
+                    if format != expected_format {
+                        Err(gpu_api_err!(
+                            "vulkan uniform storage texture {:?} does not have format {:?}",
+                            storage_texture,
+                            storage_format
+                        ))?;
+                    }
+                    let image_info = vk::DescriptorImageInfo::builder()
+                        .image_view(storage_image_view)
+                        .image_layout(vk::ImageLayout::GENERAL)
+                        .build();
+
+                    let image_info_list = vec![image_info];
+
+                    let ret = vk::WriteDescriptorSet::builder()
+                        .dst_set(self.descriptor_sets[uniform.set])
+                        .dst_binding(uniform.binding as u32)
+                        .dst_array_element(0)
+                        .descriptor_type(vk::DescriptorType::STORAGE_IMAGE)
+                        .image_info(&image_info_list)
+                        .build();
+
+                    image_infos.push(image_info_list);
+
+                    Ok(ret)
+                }
                 ShaderUniformType::InputAttachment(attachment_image_id) => {
                     let attachment_image = context
                         .attachment_images
@@ -363,41 +736,87 @@ impl VkDescriptors {
         Ok(())
     }
 
-    pub unsafe fn cmd_bind(
+    pub fn textures<'a>(&'a self, ctx: &'a VkContext) -> impl Iterator<Item = TextureId> + 'a {
+        self.shader_uniforms
+            .iter()
+            .filter_map(|uniform| match uniform.ty {
+                ShaderUniformType::Texture(texture)
+                | ShaderUniformType::CubemapTexture(texture)
+                | ShaderUniformType::Texture3D(texture)
+                | ShaderUniformType::TextureArray(texture) => Some(texture),
+                ShaderUniformType::TextureView(texture_view) => ctx
+                    .texture_views
+                    .get(texture_view.id())
+                    .map(|texture_view| texture_view.texture),
+                _ => None,
+            })
+    }
+
+    pub fn attachment_textures(&self) -> impl Iterator<Item = AttachmentImageId> + '_ {
+        self.shader_uniforms
+            .iter()
+            .filter_map(|uniform| match uniform.ty {
+                ShaderUniformType::AttachmentTexture(attachment_image_id)
+                | ShaderUniformType::MultisampledAttachmentTexture(attachment_image_id)
+                | ShaderUniformType::DepthAttachmentTexture(attachment_image_id) => {
+                    Some(attachment_image_id)
+                }
+                _ => None,
+            })
+    }
+
+    pub fn storage_textures(&self) -> impl Iterator<Item = GenericTextureId> + '_ {
+        self.shader_uniforms
+            .iter()
+            .filter_map(|uniform| match uniform.ty {
+                ShaderUniformType::StorageTexture(storage_texture, ..) => Some(storage_texture),
+                _ => None,
+            })
+    }
+
+    pub fn check_dynamic_indices(
         &self,
-        ctx: &VkContext,
-        cmd_buf: vk::CommandBuffer,
-        bind_point: vk::PipelineBindPoint,
-        layout: vk::PipelineLayout,
         dynamic_indices: &HashMap<DynamicGenericBufferId, usize>,
     ) -> GResult<()> {
-        let mut offsets = vec![0; self.dynamic_indices.len()];
-
         (self.dynamic_indices.len() == dynamic_indices.len())
             .then_some(())
             .ok_or(gpu_api_err!(
                 "vulkan not all dynamic indices provided for draw"
             ))?;
 
-        dynamic_indices
+        dynamic_indices.keys().try_for_each(|id| match id {
+            DynamicGenericBufferId::Uniform(id) => self
+                .dynamic_indices
+                .iter()
+                .any(|p| match p {
+                    DynamicGenericBufferId::Uniform(p) => p == id,
+                })
+                .then_some(())
+                .ok_or(gpu_api_err!(
+                    "vulkan dynamic uniform buffer (for indexing) {:?} does not exist",
+                    id
+                )),
+        })
+    }
+
+    //  `dynamic_indices` must have been checked with `check_dynamic_indices`.
+    pub unsafe fn cmd_bind(
+        &self,
+        ctx: &VkContext,
+        cmd_buf: vk::CommandBuffer,
+        bind_point: vk::PipelineBindPoint,
+        layout: vk::PipelineLayout,
+        dynamic_indices: &HashMap<DynamicGenericBufferId, usize>,
+    ) {
+        let offsets = self
+            .dynamic_indices
             .iter()
-            .try_for_each(|(id, index)| match id {
-                DynamicGenericBufferId::Uniform(id) => {
-                    let offset_index = self
-                        .dynamic_indices
-                        .iter()
-                        .position(|p| match p {
-                            DynamicGenericBufferId::Uniform(p) => p == id,
-                        })
-                        .ok_or(gpu_api_err!(
-                            "vulkan dynamic uniform buffer (for indexing) {:?} does not exist",
-                            id
-                        ))?;
-                    offsets[offset_index] =
-                        (*index * ctx.dyn_ubos.get(id.id()).unwrap().per_index_offset) as u32;
-                    Ok(())
+            .map(|id| match id {
+                DynamicGenericBufferId::Uniform(ubo) => {
+                    (dynamic_indices[id] * ctx.dyn_ubos[ubo.id()].per_index_offset) as u32
                 }
-            })?;
+            })
+            .collect::<Vec<_>>();
 
         ctx.core.dev.cmd_bind_descriptor_sets(
             cmd_buf,
@@ -407,8 +826,6 @@ impl VkDescriptors {
             &self.descriptor_sets,
             &offsets,
         );
-
-        Ok(())
     }
 }
 
@@ -17,6 +17,9 @@ pub fn check_extensions(extensions: &Extensions) -> GResult<()> {
             Extension::NativeDebug(_) => Ok(()),
             Extension::MemoryFlush => Ok(()),
             Extension::NagaTranslation => Ok(()),
+            Extension::TextureContainer => Ok(()),
+            Extension::ImageLoading => Ok(()),
+            Extension::Ibl => Ok(()),
             Extension::WebGpuInitFromWindow(_) => Ok(()),
             Extension::WebGpuInit(_) => Ok(()),
             Extension::Surface(_) => Ok(()),
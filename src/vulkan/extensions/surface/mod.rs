@@ -70,7 +70,11 @@ impl VkContext {
         unsafe { self.core.dev.device_wait_idle() }.unwrap();
 
         //  Resize Attachment Images.
-        for attachment_image in self.attachment_images.iter_mut() {
+        let mut resized_images = vec![];
+        for (idx, attachment_image) in self.attachment_images.iter_mut().enumerate() {
+            if !attachment_image.is_surface_relative() {
+                continue;
+            }
             attachment_image.resize(
                 &self.core.dev,
                 &self.drop_queue,
@@ -78,6 +82,16 @@ impl VkContext {
                 width,
                 height,
             )?;
+            resized_images.push(AttachmentImageId::from_id(idx));
+        }
+        {
+            let _misc_command = self.core.misc_command()?;
+            for image in resized_images.iter() {
+                unsafe {
+                    self.attachment_images[image.id()]
+                        .cmd_init_layout(&self.core.dev, self.core.misc_command_buffer);
+                }
+            }
         }
 
         self.update_descriptors()?;
@@ -96,17 +110,25 @@ impl VkContext {
         )?));
 
         //  Resize Dependent Passes
+        //  Passes that render into resized attachment images also need new framebuffers.
         //  TODO
         let e = unsafe { &mut *(self as *mut VkContext) };
         let patches = self
             .compiled_passes
             .iter()
             .enumerate()
-            .filter(|(_, compiled_pass)| compiled_pass.original_pass.depends_on_surface_size)
+            .filter(|(_, compiled_pass)| {
+                compiled_pass.original_pass.depends_on_surface_size
+                    || compiled_pass
+                        .original_pass
+                        .uses_any_attachment_image(&resized_images)
+            })
             .map(|(idx, compiled_pass)| {
                 let mut original_pass = compiled_pass.original_pass.clone();
-                original_pass.render_width = width;
-                original_pass.render_height = height;
+                if original_pass.depends_on_surface_size {
+                    original_pass.render_width = width;
+                    original_pass.render_height = height;
+                }
                 let new_pass = VkCompiledPass::new(e, &original_pass, &compiled_pass.original_ext)?;
                 Ok((idx, new_pass))
             })
@@ -88,7 +88,7 @@ impl VkSwapchain {
                     format.format,
                     vk::ImageAspectFlags::COLOR,
                     1,
-                    false,
+                    VkImageKind::Image2D,
                 )
             })
             .collect::<GResult<_>>()?;
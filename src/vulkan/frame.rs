@@ -7,6 +7,10 @@ impl<T> VkFrameDependent<T> {
         &self.d[frame.get_current_frame()]
     }
 
+    pub fn get_mut(&mut self, frame: &VkFrame) -> &mut T {
+        &mut self.d[frame.get_current_frame()]
+    }
+
     pub fn take_all(self) -> Vec<T> {
         self.d
     }
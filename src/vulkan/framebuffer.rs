@@ -9,7 +9,7 @@ impl VkFramebuffer {
     pub fn new(
         context: &VkContext,
         render_pass: vk::RenderPass,
-        images: &[AttachmentImageId],
+        images: &[(AttachmentImageId, AttachmentImageSubresource)],
         resolve_images: &[vk::ImageView],
         width: usize,
         height: usize,
@@ -71,7 +71,7 @@ impl VkSingleFramebuffer {
     fn new(
         context: &VkContext,
         render_pass: vk::RenderPass,
-        images: &[AttachmentImageId],
+        images: &[(AttachmentImageId, AttachmentImageSubresource)],
         resolve_images: &[vk::ImageView],
         width: usize,
         height: usize,
@@ -82,13 +82,16 @@ impl VkSingleFramebuffer {
             image_views.push(swapchain_image_view);
         }
 
-        images.iter().for_each(|attachment_image_id| {
-            let attachment_image = context
-                .attachment_images
-                .get(attachment_image_id.id())
-                .unwrap();
-            image_views.push(attachment_image.image_view);
-        });
+        images
+            .iter()
+            .try_for_each(|&(attachment_image_id, subresource)| {
+                let attachment_image = context
+                    .attachment_images
+                    .get(attachment_image_id.id())
+                    .unwrap();
+                image_views.push(attachment_image.get_render_view(subresource)?);
+                Ok(())
+            })?;
 
         resolve_images
             .iter()
@@ -4,6 +4,62 @@ use std::mem::ManuallyDrop;
 pub const VK_COLOR_ATTACHMENT_FORMAT: vk::Format = vk::Format::R8G8B8A8_SRGB;
 pub const VK_DEPTH_ATTACHMENT_FORMAT: vk::Format = vk::Format::D32_SFLOAT_S8_UINT;
 
+//  The formats of `ShaderStorageTextureFormat`.
+pub fn is_storage_format(format: vk::Format) -> bool {
+    matches!(
+        format,
+        vk::Format::R8G8B8A8_UNORM
+            | vk::Format::R16G16B16A16_SFLOAT
+            | vk::Format::R32_SFLOAT
+            | vk::Format::R32G32B32A32_SFLOAT
+    )
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VkImageKind {
+    Image2D,
+    Cubemap,
+    Image3D,
+    Image2DArray(u32),
+}
+
+impl VkImageKind {
+    fn image_type(&self) -> vk::ImageType {
+        match self {
+            VkImageKind::Image2D | VkImageKind::Cubemap | VkImageKind::Image2DArray(_) => {
+                vk::ImageType::TYPE_2D
+            }
+            VkImageKind::Image3D => vk::ImageType::TYPE_3D,
+        }
+    }
+
+    pub fn array_layers(&self) -> u32 {
+        match self {
+            VkImageKind::Image2D | VkImageKind::Image3D => 1,
+            VkImageKind::Cubemap => 6,
+            VkImageKind::Image2DArray(layers) => *layers,
+        }
+    }
+
+    pub fn view_dimension(&self) -> TextureViewDimension {
+        match self {
+            VkImageKind::Image2D => TextureViewDimension::D2,
+            VkImageKind::Cubemap => TextureViewDimension::Cube,
+            VkImageKind::Image3D => TextureViewDimension::D3,
+            VkImageKind::Image2DArray(_) => TextureViewDimension::D2Array,
+        }
+    }
+
+    fn view_type(&self) -> vk::ImageViewType {
+        match self {
+            VkImageKind::Image2D => vk::ImageViewType::TYPE_2D,
+            VkImageKind::Cubemap => vk::ImageViewType::CUBE,
+            VkImageKind::Image3D => vk::ImageViewType::TYPE_3D,
+            VkImageKind::Image2DArray(_) => vk::ImageViewType::TYPE_2D_ARRAY,
+        }
+    }
+}
+
 pub struct VkImage {
     pub image: vk::Image,
     pub format: vk::Format,
@@ -23,17 +79,17 @@ impl VkImage {
         view_aspect: vk::ImageAspectFlags,
         samples: vk::SampleCountFlags,
         mip_levels: u32,
-        is_cubemap: bool,
+        kind: VkImageKind,
         extent: vk::Extent3D,
     ) -> GResult<Self> {
         let image_create = vk::ImageCreateInfo::builder()
             .format(format)
             .usage(usage)
             .extent(extent)
-            .image_type(vk::ImageType::TYPE_2D)
+            .image_type(kind.image_type())
             .mip_levels(mip_levels)
-            .array_layers(if is_cubemap { 6 } else { 1 })
-            .flags(if is_cubemap {
+            .array_layers(kind.array_layers())
+            .flags(if kind == VkImageKind::Cubemap {
                 vk::ImageCreateFlags::CUBE_COMPATIBLE
             } else {
                 vk::ImageCreateFlags::empty()
@@ -92,7 +148,7 @@ pub fn new_image_view(
     format: vk::Format,
     aspect: vk::ImageAspectFlags,
     mip_level: u32,
-    is_cubemap: bool,
+    kind: VkImageKind,
 ) -> GResult<vk::ImageView> {
     let image_view_create = vk::ImageViewCreateInfo::builder()
         .image(image)
@@ -111,15 +167,299 @@ pub fn new_image_view(
                 .base_mip_level(0)
                 .level_count(mip_level)
                 .base_array_layer(0)
-                .layer_count(if is_cubemap { 6 } else { 1 })
+                .layer_count(kind.array_layers())
                 .build(),
         )
-        .view_type(if is_cubemap {
-            vk::ImageViewType::CUBE
-        } else {
-            vk::ImageViewType::TYPE_2D
+        .view_type(kind.view_type())
+        .build();
+    unsafe { dev.create_image_view(&image_view_create, None) }
+        .map_err(|e| gpu_api_err!("vulkan image view init {}", e))
+}
+
+//  A 2D view of a single mip level and layer, such as for rendering into a cubemap face.
+pub fn new_subresource_image_view(
+    dev: &Device,
+    image: vk::Image,
+    format: vk::Format,
+    aspect: vk::ImageAspectFlags,
+    mip_level: u32,
+    layer: u32,
+) -> GResult<vk::ImageView> {
+    let image_view_create = vk::ImageViewCreateInfo::builder()
+        .image(image)
+        .format(format)
+        .components(
+            vk::ComponentMapping::builder()
+                .r(vk::ComponentSwizzle::IDENTITY)
+                .g(vk::ComponentSwizzle::IDENTITY)
+                .b(vk::ComponentSwizzle::IDENTITY)
+                .a(vk::ComponentSwizzle::IDENTITY)
+                .build(),
+        )
+        .subresource_range(
+            vk::ImageSubresourceRange::builder()
+                .aspect_mask(aspect)
+                .base_mip_level(mip_level)
+                .level_count(1)
+                .base_array_layer(layer)
+                .layer_count(1)
+                .build(),
+        )
+        .view_type(vk::ImageViewType::TYPE_2D)
+        .build();
+    unsafe { dev.create_image_view(&image_view_create, None) }
+        .map_err(|e| gpu_api_err!("vulkan image view init {}", e))
+}
+
+//  A view created with `Context::new_texture_view`.
+pub fn new_ranged_image_view(
+    dev: &Device,
+    image: vk::Image,
+    format: vk::Format,
+    aspect: vk::ImageAspectFlags,
+    range: &TextureViewRange,
+    swizzle: TextureViewSwizzle,
+) -> GResult<vk::ImageView> {
+    fn swizzle_into_vk(swizzle: TextureComponentSwizzle) -> vk::ComponentSwizzle {
+        match swizzle {
+            TextureComponentSwizzle::Identity => vk::ComponentSwizzle::IDENTITY,
+            TextureComponentSwizzle::Zero => vk::ComponentSwizzle::ZERO,
+            TextureComponentSwizzle::One => vk::ComponentSwizzle::ONE,
+            TextureComponentSwizzle::R => vk::ComponentSwizzle::R,
+            TextureComponentSwizzle::G => vk::ComponentSwizzle::G,
+            TextureComponentSwizzle::B => vk::ComponentSwizzle::B,
+            TextureComponentSwizzle::A => vk::ComponentSwizzle::A,
+        }
+    }
+
+    let image_view_create = vk::ImageViewCreateInfo::builder()
+        .image(image)
+        .format(format)
+        .components(
+            vk::ComponentMapping::builder()
+                .r(swizzle_into_vk(swizzle.r))
+                .g(swizzle_into_vk(swizzle.g))
+                .b(swizzle_into_vk(swizzle.b))
+                .a(swizzle_into_vk(swizzle.a))
+                .build(),
+        )
+        .subresource_range(
+            vk::ImageSubresourceRange::builder()
+                .aspect_mask(aspect)
+                .base_mip_level(range.base_mip_level)
+                .level_count(range.mip_level_count)
+                .base_array_layer(range.base_layer)
+                .layer_count(range.layer_count)
+                .build(),
+        )
+        .view_type(match range.dimension {
+            TextureViewDimension::D2 => vk::ImageViewType::TYPE_2D,
+            TextureViewDimension::D2Array => vk::ImageViewType::TYPE_2D_ARRAY,
+            TextureViewDimension::Cube => vk::ImageViewType::CUBE,
+            TextureViewDimension::D3 => vk::ImageViewType::TYPE_3D,
         })
         .build();
     unsafe { dev.create_image_view(&image_view_create, None) }
         .map_err(|e| gpu_api_err!("vulkan image view init {}", e))
 }
+
+//  A texture or attachment image as seen by `Submit::copy_texture` and `Submit::blit_texture`.
+//  `layout` and `access` describe where the image rests between submit operations.
+pub struct VkTransferImage {
+    pub image: vk::Image,
+    pub format: vk::Format,
+    pub layout: vk::ImageLayout,
+    pub aspect: vk::ImageAspectFlags,
+    pub access: vk::AccessFlags,
+    pub samples: vk::SampleCountFlags,
+    //  The single aspect copied to and from buffers.
+    pub buffer_aspect: vk::ImageAspectFlags,
+    pub block_dimensions: (usize, usize),
+    pub block_byte_size: usize,
+    pub mip_levels: u32,
+    pub array_layers: u32,
+    pub width: usize,
+    pub height: usize,
+    pub depth: usize,
+}
+
+impl VkTransferImage {
+    pub fn check_region(&self, region: &TextureRegion) -> GResult<()> {
+        if region.mip_level >= self.mip_levels || region.layer >= self.array_layers {
+            Err(gpu_api_err!(
+                "vulkan texture copy region {:?} is out of range for {} mip levels and {} layers",
+                region,
+                self.mip_levels,
+                self.array_layers
+            ))?;
+        }
+        let (mip_width, mip_height) = mip_level_extent(self.width, self.height, region.mip_level);
+        if region.width == 0
+            || region.height == 0
+            || region.depth == 0
+            || region.x + region.width > mip_width
+            || region.y + region.height > mip_height
+            || region.z + region.depth > self.depth
+        {
+            Err(gpu_api_err!(
+                "vulkan texture copy region {:?} is outside of mip level extent ({}, {}, {})",
+                region,
+                mip_width,
+                mip_height,
+                self.depth
+            ))?;
+        }
+        Ok(())
+    }
+
+    //  Compressed regions must cover whole blocks, except at the edge of the mip level.
+    pub fn check_block_alignment(&self, region: &TextureRegion) -> GResult<()> {
+        let (mip_width, mip_height) = mip_level_extent(self.width, self.height, region.mip_level);
+        let (block_width, block_height) = self.block_dimensions;
+        if !region.x.is_multiple_of(block_width)
+            || !region.y.is_multiple_of(block_height)
+            || (!region.width.is_multiple_of(block_width) && region.x + region.width != mip_width)
+            || (!region.height.is_multiple_of(block_height)
+                && region.y + region.height != mip_height)
+        {
+            Err(gpu_api_err!(
+                "vulkan texture copy region {:?} is not aligned to {:?} blocks",
+                region,
+                self.block_dimensions
+            ))?;
+        }
+        Ok(())
+    }
+
+    pub fn is_compressed(&self) -> bool {
+        self.block_dimensions != (1, 1)
+    }
+
+    //  Returns the row pitch of the buffer data.
+    pub fn check_buffer_region(
+        &self,
+        region: &TextureRegion,
+        buffer_size: usize,
+        ext: &CopyBufferTextureExt,
+    ) -> GResult<usize> {
+        self.check_region(region)?;
+        self.check_block_alignment(region)?;
+        let (_, block_height) = self.block_dimensions;
+
+        let buffer_offset = ext.buffer_offset.unwrap_or(0);
+        //  Depth and stencil copies additionally require 4 byte aligned offsets.
+        if !buffer_offset.is_multiple_of(self.block_byte_size)
+            || (self.buffer_aspect != vk::ImageAspectFlags::COLOR
+                && !buffer_offset.is_multiple_of(4))
+        {
+            Err(gpu_api_err!(
+                "vulkan buffer texture copy offset {} is not aligned to {} bytes",
+                buffer_offset,
+                self.block_byte_size
+            ))?;
+        }
+
+        let row_size = self.row_size(region);
+        let row_pitch = ext.row_pitch.unwrap_or(row_size);
+        if row_pitch < row_size || !row_pitch.is_multiple_of(self.block_byte_size) {
+            Err(gpu_api_err!(
+                "vulkan buffer texture copy row pitch {} is invalid for rows of {} bytes",
+                row_pitch,
+                row_size
+            ))?;
+        }
+
+        let rows = region.height.div_ceil(block_height) * region.depth;
+        let expected_size = buffer_offset + row_pitch * (rows - 1) + row_size;
+        if buffer_size < expected_size {
+            Err(gpu_api_err!(
+                "vulkan buffer texture copy buffer size ({}) < expected size ({})",
+                buffer_size,
+                expected_size
+            ))?;
+        }
+
+        Ok(row_pitch)
+    }
+
+    //  Tightly packed size of one row of blocks in `region`.
+    pub fn row_size(&self, region: &TextureRegion) -> usize {
+        region.width.div_ceil(self.block_dimensions.0) * self.block_byte_size
+    }
+
+    pub fn buffer_image_copy(
+        &self,
+        region: &TextureRegion,
+        buffer_offset: usize,
+        row_pitch: usize,
+    ) -> vk::BufferImageCopy {
+        vk::BufferImageCopy::builder()
+            .buffer_offset(buffer_offset as u64)
+            .buffer_row_length((row_pitch / self.block_byte_size * self.block_dimensions.0) as u32)
+            .buffer_image_height(0)
+            .image_offset(vk::Offset3D {
+                x: region.x as i32,
+                y: region.y as i32,
+                z: region.z as i32,
+            })
+            .image_extent(vk::Extent3D {
+                width: region.width as u32,
+                height: region.height as u32,
+                depth: region.depth as u32,
+            })
+            .image_subresource(
+                vk::ImageSubresourceLayers::builder()
+                    .aspect_mask(self.buffer_aspect)
+                    .mip_level(region.mip_level)
+                    .base_array_layer(region.layer)
+                    .layer_count(1)
+                    .build(),
+            )
+            .build()
+    }
+
+    pub fn subresource_layers(&self, region: &TextureRegion) -> vk::ImageSubresourceLayers {
+        vk::ImageSubresourceLayers::builder()
+            .aspect_mask(self.aspect)
+            .mip_level(region.mip_level)
+            .base_array_layer(region.layer)
+            .layer_count(1)
+            .build()
+    }
+
+    //  Moves the region's subresource between its resting layout and `transfer_layout`.
+    pub fn transfer_barrier(
+        &self,
+        region: &TextureRegion,
+        transfer_layout: vk::ImageLayout,
+        transfer_access: vk::AccessFlags,
+        to_transfer: bool,
+    ) -> vk::ImageMemoryBarrier {
+        let (old_layout, new_layout, src_access, dst_access) = if to_transfer {
+            (
+                self.layout,
+                transfer_layout,
+                vk::AccessFlags::MEMORY_WRITE,
+                transfer_access,
+            )
+        } else {
+            (transfer_layout, self.layout, transfer_access, self.access)
+        };
+        vk::ImageMemoryBarrier::builder()
+            .old_layout(old_layout)
+            .new_layout(new_layout)
+            .image(self.image)
+            .subresource_range(
+                vk::ImageSubresourceRange::builder()
+                    .aspect_mask(self.aspect)
+                    .base_mip_level(region.mip_level)
+                    .level_count(1)
+                    .base_array_layer(region.layer)
+                    .layer_count(1)
+                    .build(),
+            )
+            .src_access_mask(src_access)
+            .dst_access_mask(dst_access)
+            .build()
+    }
+}
@@ -7,26 +7,31 @@ use gpu_allocator::{
 };
 use raw_window_handle::{RawDisplayHandle, RawWindowHandle};
 use std::{
+    collections::HashMap,
     mem::ManuallyDrop,
     sync::{Arc, Mutex},
 };
 
 use attachment_image::VkAttachmentImage;
 use buffer::{
-    VkBuffer, VkDynamicUniformBuffer, VkIndexBuffer, VkShaderStorageBuffer, VkUniformBuffer,
-    VkVertexBuffer,
+    VkBuffer, VkDynamicUniformBuffer, VkGenericBuffer, VkIndexBuffer, VkShaderStorageBuffer,
+    VkUniformBuffer, VkVertexBuffer,
 };
 use descriptor::VkDescriptors;
 use drop::VkDropQueue;
 use frame::{VkFrame, VkFrameDependent};
 use framebuffer::VkFramebuffer;
-use image::{new_image_view, VkImage, VK_COLOR_ATTACHMENT_FORMAT, VK_DEPTH_ATTACHMENT_FORMAT};
+use image::{
+    is_storage_format, new_image_view, new_ranged_image_view, new_subresource_image_view, VkImage,
+    VkImageKind, VkTransferImage, VK_COLOR_ATTACHMENT_FORMAT, VK_DEPTH_ATTACHMENT_FORMAT,
+};
 use pass::VkCompiledPass;
-use program::{new_pipeline_layout, VkProgram};
+use program::{compare_op_into_vk, new_pipeline_layout, VkProgram};
 use sampler::VkSamplerCache;
 use shader::VkShader;
 use submit::VkSubmitData;
-use texture::VkTexture;
+use texture::{VkTexture, VkTextureView};
+use upload::VkAsyncUpload;
 use vkcore::{new_fence, new_semaphore, VkCore, VkCoreConfiguration, VkCoreGpuPreference};
 
 use extensions::compute::{VkCompiledComputePass, VkComputeProgram};
@@ -46,6 +51,7 @@ mod sampler;
 mod shader;
 mod submit;
 mod texture;
+mod upload;
 mod vkcore;
 
 pub type VkDropQueueRef = Arc<Mutex<VkDropQueue>>;
@@ -55,15 +61,20 @@ pub struct VkContext {
 
     programs: ManuallyDrop<Vec<VkProgram>>,
     compute_programs: ManuallyDrop<Vec<VkComputeProgram>>,
+    buffers: ManuallyDrop<Vec<VkGenericBuffer>>,
     vbos: ManuallyDrop<Vec<VkVertexBuffer>>,
     ibos: ManuallyDrop<Vec<VkIndexBuffer>>,
     ubos: ManuallyDrop<Vec<VkUniformBuffer>>,
     dyn_ubos: ManuallyDrop<Vec<VkDynamicUniformBuffer>>,
     ssbos: ManuallyDrop<Vec<VkShaderStorageBuffer>>,
     textures: ManuallyDrop<Vec<VkTexture>>,
+    texture_views: ManuallyDrop<Vec<VkTextureView>>,
     attachment_images: ManuallyDrop<Vec<VkAttachmentImage>>,
     compiled_passes: ManuallyDrop<Vec<VkCompiledPass>>,
     compiled_compute_passes: ManuallyDrop<Vec<VkCompiledComputePass>>,
+    //  Only pending uploads, ids are never reused so a finished upload keeps polling `true`.
+    uploads: ManuallyDrop<HashMap<usize, VkAsyncUpload>>,
+    next_upload_id: usize,
     submit: ManuallyDrop<VkSubmitData>,
     sampler_cache: ManuallyDrop<VkSamplerCache>,
     alloc: ManuallyDrop<Allocator>,
@@ -188,15 +199,18 @@ impl VkContext {
 
         let programs = ManuallyDrop::new(vec![]);
         let compute_programs = ManuallyDrop::new(vec![]);
+        let buffers = ManuallyDrop::new(vec![]);
         let vbos = ManuallyDrop::new(vec![]);
         let ibos = ManuallyDrop::new(vec![]);
         let ubos = ManuallyDrop::new(vec![]);
         let dyn_ubos = ManuallyDrop::new(vec![]);
         let ssbos = ManuallyDrop::new(vec![]);
         let textures = ManuallyDrop::new(vec![]);
+        let texture_views = ManuallyDrop::new(vec![]);
         let attachment_images = ManuallyDrop::new(vec![]);
         let compiled_passes = ManuallyDrop::new(vec![]);
         let compiled_compute_passes = ManuallyDrop::new(vec![]);
+        let uploads = ManuallyDrop::new(HashMap::new());
 
         Ok(VkContext {
             core,
@@ -210,15 +224,19 @@ impl VkContext {
 
             programs,
             compute_programs,
+            buffers,
             vbos,
             ibos,
             ubos,
             dyn_ubos,
             ssbos,
             textures,
+            texture_views,
             attachment_images,
             compiled_passes,
             compiled_compute_passes,
+            uploads,
+            next_upload_id: 0,
 
             surface_ext: ManuallyDrop::new(surface_ext),
         })
@@ -250,15 +268,18 @@ impl Drop for VkContext {
 
             let _programs = ManuallyDrop::take(&mut self.programs);
             let _compute_programs = ManuallyDrop::take(&mut self.compute_programs);
+            let _buffers = ManuallyDrop::take(&mut self.buffers);
             let _vbos = ManuallyDrop::take(&mut self.vbos);
             let _ibos = ManuallyDrop::take(&mut self.ibos);
             let _ubos = ManuallyDrop::take(&mut self.ubos);
             let _dyn_ubos = ManuallyDrop::take(&mut self.dyn_ubos);
             let _ssbos = ManuallyDrop::take(&mut self.ssbos);
+            let _texture_views = ManuallyDrop::take(&mut self.texture_views);
             let _textures = ManuallyDrop::take(&mut self.textures);
             let _attachment_images = ManuallyDrop::take(&mut self.attachment_images);
             let _compiled_passes = ManuallyDrop::take(&mut self.compiled_passes);
             let _compiled_compute_passes = ManuallyDrop::take(&mut self.compiled_compute_passes);
+            let _uploads = ManuallyDrop::take(&mut self.uploads);
         }
 
         Arc::get_mut(&mut self.drop_queue)
@@ -126,7 +126,10 @@ fn new_framebuffer(
             if pass.surface_attachment && attachment.local_attachment_idx == 0 {
                 None
             } else {
-                Some(attachment.output_image.unwrap())
+                Some((
+                    attachment.output_image.unwrap(),
+                    attachment.output_subresource,
+                ))
             }
         })
         .collect::<Vec<_>>();
@@ -178,7 +181,12 @@ fn new_render_pass(
                                 attachment.format
                             }
                         }
-                        PassInputType::Depth(_) => VK_DEPTH_ATTACHMENT_FORMAT,
+                        PassInputType::Depth(_) => {
+                            let output_image = attachment.output_image.unwrap();
+                            let attachment = ctx.attachment_images.get(output_image.id())
+                                .ok_or(gpu_api_err!("vulkan compile pass attachment image id {:?} does not exist", output_image))?;
+                            attachment.format
+                        }
                     })
                     .samples(vk::SampleCountFlags::TYPE_1)
                     .load_op(match &attachment.ty {
@@ -240,6 +248,11 @@ fn new_render_pass(
     //  This is later appended onto `pass_input_attachments`.
     let mut i_pass_resolve_attachments = 0;
     let mut resolve_image_offsets = HashMap::new();
+    if ext.skip_msaa_resolve.is_some() && ext.enable_msaa.is_none() {
+        Err(gpu_api_err!(
+            "vulkan compile pass cannot skip the msaa resolve without enable_msaa"
+        ))?;
+    }
     let (pass_resolve_attachments, resolve_images) = if ext.enable_msaa.is_some() {
         let sample_ty = match ext.msaa_samples.unwrap_or_default() {
             MsaaSampleCount::Sample1 => vk::SampleCountFlags::TYPE_1,
@@ -252,64 +265,99 @@ fn new_render_pass(
         };
 
         sample_count = Some(sample_ty);
-        pass_input_attachments
-            .iter_mut()
-            .for_each(|(desc, reference)| {
-                if reference.layout == vk::ImageLayout::DEPTH_STENCIL_ATTACHMENT_OPTIMAL {
+        if ext.skip_msaa_resolve.is_some() {
+            if pass.surface_attachment {
+                Err(gpu_api_err!(
+                    "vulkan compile pass cannot skip the msaa resolve of the surface attachment"
+                ))?;
+            }
+
+            //  Render directly into the multisampled attachment images.
+            pass.attachments
+                .iter()
+                .zip(pass_input_attachments.iter_mut())
+                .try_for_each(|(attachment, (desc, _))| {
+                    let output_image = attachment.output_image.unwrap();
+                    let attachment_image =
+                        ctx.attachment_images
+                            .get(output_image.id())
+                            .ok_or(gpu_api_err!(
+                                "vulkan compile pass attachment image id {:?} does not exist",
+                                output_image
+                            ))?;
+                    if attachment_image.samples != sample_ty {
+                        Err(gpu_api_err!(
+                            "vulkan compile pass attachment image id {:?} does not have {:?}",
+                            output_image,
+                            ext.msaa_samples.unwrap_or_default()
+                        ))?;
+                    }
                     desc.samples = sample_ty;
-                }
-            });
+                    Ok(())
+                })?;
+
+            (vec![], vec![])
+        } else {
+            pass_input_attachments
+                .iter_mut()
+                .for_each(|(desc, reference)| {
+                    if reference.layout == vk::ImageLayout::DEPTH_STENCIL_ATTACHMENT_OPTIMAL {
+                        desc.samples = sample_ty;
+                    }
+                });
 
-        pass_input_attachments
-            .clone()
-            .iter()
-            .enumerate()
-            .filter(|(_, (_, reference))| {
-                matches!(
-                    reference.layout,
-                    vk::ImageLayout::COLOR_ATTACHMENT_OPTIMAL | vk::ImageLayout::PRESENT_SRC_KHR
-                )
-            })
-            .map(|(idx, (mut desc, mut reference))| {
-                let original_attachment_lengths = pass_input_attachments.len();
-                let original = &mut pass_input_attachments[idx].0;
-                desc.samples = sample_ty;
-
-                original.load_op = vk::AttachmentLoadOp::DONT_CARE;
-                original.stencil_load_op = vk::AttachmentLoadOp::DONT_CARE;
-
-                reference.attachment =
-                    (i_pass_resolve_attachments + original_attachment_lengths) as u32;
-
-                let resolve_image = new_resolve_image(
-                    ctx,
-                    vk::Extent3D {
-                        width: pass.render_width as u32,
-                        height: pass.render_height as u32,
-                        depth: 1,
-                    },
-                    original.format,
-                    original.final_layout,
-                    sample_ty,
-                )?;
-
-                let resolve_images_views = new_image_view(
-                    &ctx.core.dev,
-                    resolve_image.image,
-                    resolve_image.format,
-                    resolve_image.view_aspect,
-                    1,
-                    false,
-                )?;
-
-                resolve_image_offsets.insert(idx, i_pass_resolve_attachments);
-                i_pass_resolve_attachments += 1;
-
-                Ok(((desc, reference), (resolve_image, resolve_images_views)))
-            })
-            .collect::<GResult<Vec<_>>>()?
-            .into_iter()
-            .unzip()
+            pass_input_attachments
+                .clone()
+                .iter()
+                .enumerate()
+                .filter(|(_, (_, reference))| {
+                    matches!(
+                        reference.layout,
+                        vk::ImageLayout::COLOR_ATTACHMENT_OPTIMAL
+                            | vk::ImageLayout::PRESENT_SRC_KHR
+                    )
+                })
+                .map(|(idx, (mut desc, mut reference))| {
+                    let original_attachment_lengths = pass_input_attachments.len();
+                    let original = &mut pass_input_attachments[idx].0;
+                    desc.samples = sample_ty;
+
+                    original.load_op = vk::AttachmentLoadOp::DONT_CARE;
+                    original.stencil_load_op = vk::AttachmentLoadOp::DONT_CARE;
+
+                    reference.attachment =
+                        (i_pass_resolve_attachments + original_attachment_lengths) as u32;
+
+                    let resolve_image = new_resolve_image(
+                        ctx,
+                        vk::Extent3D {
+                            width: pass.render_width as u32,
+                            height: pass.render_height as u32,
+                            depth: 1,
+                        },
+                        original.format,
+                        original.final_layout,
+                        sample_ty,
+                    )?;
+
+                    let resolve_images_views = new_image_view(
+                        &ctx.core.dev,
+                        resolve_image.image,
+                        resolve_image.format,
+                        resolve_image.view_aspect,
+                        1,
+                        VkImageKind::Image2D,
+                    )?;
+
+                    resolve_image_offsets.insert(idx, i_pass_resolve_attachments);
+                    i_pass_resolve_attachments += 1;
+
+                    Ok(((desc, reference), (resolve_image, resolve_images_views)))
+                })
+                .collect::<GResult<Vec<_>>>()?
+                .into_iter()
+                .unzip()
+        }
     } else {
         (vec![], vec![])
     };
@@ -354,7 +402,7 @@ fn new_render_pass(
                 each_depth_ptrs.push(depth);
             }
 
-            if ext.enable_msaa.is_some() {
+            if ext.enable_msaa.is_some() && ext.skip_msaa_resolve.is_none() {
                 let old = subpass.p_color_attachments;
                 let resolve_attachments = step
                     .write_colors
@@ -494,7 +542,7 @@ fn new_resolve_image(
         aspect,
         sample_count,
         1,
-        false,
+        VkImageKind::Image2D,
         extent,
     )
 }
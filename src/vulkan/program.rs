@@ -212,19 +212,6 @@ impl VkProgram {
             }
         }
 
-        fn compare_op_into_vk(op: ShaderCompareOp) -> vk::CompareOp {
-            match op {
-                ShaderCompareOp::Never => vk::CompareOp::NEVER,
-                ShaderCompareOp::Less => vk::CompareOp::LESS,
-                ShaderCompareOp::Equal => vk::CompareOp::EQUAL,
-                ShaderCompareOp::LessOrEqual => vk::CompareOp::LESS_OR_EQUAL,
-                ShaderCompareOp::Greater => vk::CompareOp::GREATER,
-                ShaderCompareOp::NotEqual => vk::CompareOp::NOT_EQUAL,
-                ShaderCompareOp::GreaterOrEqual => vk::CompareOp::GREATER_OR_EQUAL,
-                ShaderCompareOp::Always => vk::CompareOp::ALWAYS,
-            }
-        }
-
         let stencil_op_state = vk::StencilOpState::builder()
             .compare_op(compare_op_into_vk(
                 ext.stencil_compare_op.unwrap_or_default(),
@@ -309,3 +296,16 @@ impl Drop for VkProgram {
             }))
     }
 }
+
+pub fn compare_op_into_vk(op: ShaderCompareOp) -> vk::CompareOp {
+    match op {
+        ShaderCompareOp::Never => vk::CompareOp::NEVER,
+        ShaderCompareOp::Less => vk::CompareOp::LESS,
+        ShaderCompareOp::Equal => vk::CompareOp::EQUAL,
+        ShaderCompareOp::LessOrEqual => vk::CompareOp::LESS_OR_EQUAL,
+        ShaderCompareOp::Greater => vk::CompareOp::GREATER,
+        ShaderCompareOp::NotEqual => vk::CompareOp::NOT_EQUAL,
+        ShaderCompareOp::GreaterOrEqual => vk::CompareOp::GREATER_OR_EQUAL,
+        ShaderCompareOp::Always => vk::CompareOp::ALWAYS,
+    }
+}
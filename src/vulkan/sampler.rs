@@ -21,9 +21,14 @@ struct SamplerData {
     mag_filter: SamplerFilter,
     u_mode: SamplerMode,
     v_mode: SamplerMode,
+    w_mode: SamplerMode,
+    border_color: SamplerBorderColor,
     min_lod: Option<HashableF32>,
     max_lod: Option<HashableF32>,
+    lod_bias: Option<HashableF32>,
+    max_anisotropy: Option<HashableF32>,
     mip_filter: MipSamplerFilter,
+    compare_op: Option<ShaderCompareOp>,
 }
 
 pub struct VkSamplerCache {
@@ -47,6 +52,11 @@ impl VkSamplerCache {
         self.samplers.get(data).cloned()
     }
 
+    pub fn is_comparison(&self, sampler_id: SamplerId) -> Option<bool> {
+        let data = self.sampler_datas.get(sampler_id.id())?;
+        Some(data.compare_op.is_some())
+    }
+
     fn get_or_insert(&mut self, dev: &Device, data: SamplerData) -> GResult<usize> {
         if let Some(id) = self
             .sampler_datas
@@ -61,6 +71,8 @@ impl VkSamplerCache {
                 .mipmap_mode(mip_filter_into_vk(data.mip_filter))
                 .address_mode_u(mode_into_vk(data.u_mode))
                 .address_mode_v(mode_into_vk(data.v_mode))
+                .address_mode_w(mode_into_vk(data.w_mode))
+                .border_color(border_color_into_vk(data.border_color))
                 .build();
 
             if let Some(lod) = data.min_lod {
@@ -69,6 +81,17 @@ impl VkSamplerCache {
             if let Some(lod) = data.max_lod {
                 sampler_info.max_lod = lod.get_val()
             }
+            if let Some(bias) = data.lod_bias {
+                sampler_info.mip_lod_bias = bias.get_val()
+            }
+            if let Some(anisotropy) = data.max_anisotropy {
+                sampler_info.anisotropy_enable = vk::TRUE;
+                sampler_info.max_anisotropy = anisotropy.get_val();
+            }
+            if let Some(compare_op) = data.compare_op {
+                sampler_info.compare_enable = vk::TRUE;
+                sampler_info.compare_op = compare_op_into_vk(compare_op);
+            }
 
             let sampler = unsafe { dev.create_sampler(&sampler_info, None) }
                 .map_err(|e| gpu_api_err!("vulkan sampler {}", e))?;
@@ -105,6 +128,14 @@ fn mode_into_vk(mode: SamplerMode) -> vk::SamplerAddressMode {
     }
 }
 
+fn border_color_into_vk(color: SamplerBorderColor) -> vk::BorderColor {
+    match color {
+        SamplerBorderColor::TransparentBlack => vk::BorderColor::FLOAT_TRANSPARENT_BLACK,
+        SamplerBorderColor::OpaqueBlack => vk::BorderColor::FLOAT_OPAQUE_BLACK,
+        SamplerBorderColor::OpaqueWhite => vk::BorderColor::FLOAT_OPAQUE_WHITE,
+    }
+}
+
 impl VkContext {
     pub fn get_sampler(&mut self, ext: Option<GetSamplerExt>) -> GResult<SamplerId> {
         let GetSamplerExt {
@@ -113,17 +144,37 @@ impl VkContext {
             mip_filter,
             u_mode,
             v_mode,
+            w_mode,
+            border_color,
             min_lod,
             max_lod,
+            lod_bias,
+            max_anisotropy,
+            compare_op,
         } = ext.unwrap_or_default();
+        let limits = &self.core.physical_dev_properties.limits;
         let data = SamplerData {
             min_filter,
             mag_filter,
             mip_filter,
             u_mode,
             v_mode,
+            w_mode,
+            border_color,
             min_lod: min_lod.map(HashableF32::from_val),
             max_lod: max_lod.map(HashableF32::from_val),
+            lod_bias: lod_bias.map(|bias| {
+                let max_bias = limits.max_sampler_lod_bias;
+                HashableF32::from_val(bias.clamp(-max_bias, max_bias))
+            }),
+            max_anisotropy: max_anisotropy
+                .filter(|_| self.core.enabled_features.sampler_anisotropy == vk::TRUE)
+                .map(|anisotropy| {
+                    HashableF32::from_val(
+                        (anisotropy as f32).clamp(1.0, limits.max_sampler_anisotropy),
+                    )
+                }),
+            compare_op,
         };
         self.sampler_cache
             .get_or_insert(&self.core.dev, data)
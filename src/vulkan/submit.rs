@@ -1,4 +1,5 @@
 use super::*;
+use std::collections::HashSet;
 use std::mem::ManuallyDrop;
 
 pub struct VkSubmitData {
@@ -7,6 +8,8 @@ pub struct VkSubmitData {
     image_aquire_semaphore: ManuallyDrop<VkFrameDependent<vk::Semaphore>>,
 
     graphics_command_buffer: VkFrameDependent<vk::CommandBuffer>,
+    //  Grown on demand by `Submit::transfer_into_texture`.
+    pub texture_staging: VkFrameDependent<Option<VkBuffer>>,
 
     drop_queue_ref: VkDropQueueRef,
 }
@@ -47,11 +50,13 @@ impl VkSubmitData {
                 })
                 .collect::<GResult<Vec<_>>>()?,
         );
+        let texture_staging = (0..frame.get_flight_frames_count()).map(|_| None).collect();
         Ok(VkSubmitData {
             frame_fence,
             render_semaphore,
             image_aquire_semaphore,
             graphics_command_buffer,
+            texture_staging,
             drop_queue_ref: Arc::clone(drop_queue_ref),
         })
     }
@@ -90,13 +95,21 @@ impl VkContext {
         let image_aquire_semaphore = *self.submit.image_aquire_semaphore.get(&self.frame);
         let graphics_command_buffer = *self.submit.graphics_command_buffer.get(&self.frame);
 
+        //  Nothing after the swapchain image is acquired may fail, so reject bad submits up front.
+        self.check_submit(&submit)?;
+
         //  TODO FIX: Replace unwraps.
         unsafe {
             self.core
                 .dev
                 .wait_for_fences(&[frame_fence], true, std::u64::MAX)
                 .unwrap();
-            self.core.dev.reset_fences(&[frame_fence]).unwrap();
+
+            //  Host writes into staging memory that this frame's fence protected.
+            self.write_buffer_transfers(&submit)?;
+            let texture_transfer_offsets =
+                self.write_texture_transfers(&submit.texture_transfers)?;
+            self.new_attachment_image_readbacks(&submit.attachment_image_copy_backs)?;
 
             let (swapchain_image_index, _suboptimal) = if let Some(surface) = &*self.surface_ext {
                 match surface.swapchain.swapchain_ext.acquire_next_image(
@@ -137,37 +150,12 @@ impl VkContext {
                 .unwrap();
 
             //  Graphics Related Transfers
-            submit.vbo_transfers.iter().for_each(|(vbo, data)| {
-                let vbo = self.vbos.get_mut(vbo.id()).unwrap();
-                vbo.cmd_transfer(&self.core.dev.clone(), graphics_command_buffer, data)
-                    .unwrap();
-            });
-
-            submit.ibo_transfers.iter().for_each(|(ibo, data)| {
-                let ibo = self.ibos.get_mut(ibo.id()).unwrap();
-                ibo.cmd_transfer(&self.core.dev.clone(), graphics_command_buffer, data)
-                    .unwrap();
-            });
-
-            submit.ubo_transfers.iter().for_each(|(ubo, data)| {
-                let ubo = self.ubos.get_mut(ubo.id()).unwrap();
-                ubo.cmd_transfer(&self.core.dev.clone(), graphics_command_buffer, data)
-                    .unwrap();
-            });
-
-            submit
-                .dyn_ubo_transfers
-                .iter()
-                .for_each(|(ubo, data, index)| {
-                    let ubo = self.dyn_ubos.get_mut(ubo.id()).unwrap();
-                    ubo.cmd_transfer(
-                        &self.core.dev.clone(),
-                        graphics_command_buffer,
-                        data,
-                        *index,
-                    )
-                    .unwrap();
-                });
+            self.cmd_buffer_transfers(graphics_command_buffer, &submit);
+            self.cmd_texture_transfers(
+                graphics_command_buffer,
+                &submit.texture_transfers,
+                &texture_transfer_offsets,
+            );
 
             //  Read somewhere that this is actually unneccessary.
             let graphics_memory_barrier = vk::MemoryBarrier::builder()
@@ -194,7 +182,26 @@ impl VkContext {
             for pass_data in submit.passes.iter() {
                 match pass_data {
                     SubmitPassType::Render(pass_data) => {
-                        let pass = self.compiled_passes.get(pass_data.pass.id()).unwrap();
+                        let pass = &self.compiled_passes[pass_data.pass.id()];
+                        let descriptors = pass_data
+                            .steps_datas
+                            .iter()
+                            .flat_map(|step_data| step_data.draws.iter())
+                            .map(|draw| &self.programs[draw.program.id()].descriptors)
+                            .collect::<Vec<_>>();
+                        let (sampled_attachments, storage_textures) = pass_textures(&descriptors);
+                        let rendered_subresources = rendered_subresources(pass);
+                        self.cmd_attachment_textures_barrier(
+                            graphics_command_buffer,
+                            &sampled_attachments,
+                            &rendered_subresources,
+                            true,
+                        );
+                        self.cmd_storage_textures_barrier(
+                            graphics_command_buffer,
+                            &storage_textures,
+                            true,
+                        );
 
                         //  Clear Values
                         let mut clear_values = vec![
@@ -253,10 +260,10 @@ impl VkContext {
                         {
                             //  Index Buffer
                             if let Some(ibo) = step.index_buffer {
-                                let ibo = self.ibos.get(ibo.id()).unwrap();
+                                let ibo = self.index_buffer(ibo);
                                 self.core.dev.cmd_bind_index_buffer(
                                     graphics_command_buffer,
-                                    ibo.buffer.buffer,
+                                    ibo.buffer,
                                     0,
                                     match std::mem::size_of::<IndexBufferElement>() {
                                         4 => vk::IndexType::UINT32,
@@ -272,15 +279,8 @@ impl VkContext {
                             let vbo_buffers = step
                                 .vertex_buffers
                                 .iter()
-                                .map(|vbo| {
-                                    Ok(self
-                                        .vbos
-                                        .get(vbo.id())
-                                        .ok_or(gpu_api_err!("vulkan bad vbo ({})", vbo.id()))?
-                                        .buffer
-                                        .buffer)
-                                })
-                                .collect::<GResult<Vec<_>>>()?;
+                                .map(|&vbo| self.vertex_buffer(vbo).buffer)
+                                .collect::<Vec<_>>();
                             let vbo_offsets = (0..step.vertex_buffers.len())
                                 .map(|_| 0)
                                 .collect::<Vec<_>>();
@@ -339,24 +339,19 @@ impl VkContext {
                                 self.core.dev.cmd_bind_pipeline(
                                     graphics_command_buffer,
                                     vk::PipelineBindPoint::GRAPHICS,
-                                    *pass.pipelines[step_idx].get(&draw.program).ok_or(
-                                        gpu_api_err!(
-                                            "vulkan submit draw missing program id {:?}",
-                                            draw.program
-                                        ),
-                                    )?,
+                                    pass.pipelines[step_idx][&draw.program],
                                 );
 
                                 //  Descriptor Sets
                                 //  TODO OPT: Maybe don't do this.
-                                let program = self.programs.get(draw.program.id()).unwrap();
+                                let program = &self.programs[draw.program.id()];
                                 program.descriptors.cmd_bind(
                                     self,
                                     graphics_command_buffer,
                                     vk::PipelineBindPoint::GRAPHICS,
                                     program.layout,
                                     &draw.dynamic_buffer_indices,
-                                )?;
+                                );
 
                                 //  Draw
                                 match draw.ty {
@@ -391,15 +386,21 @@ impl VkContext {
                             }
                         }
                         self.core.dev.cmd_end_render_pass(graphics_command_buffer);
+                        self.cmd_attachment_textures_barrier(
+                            graphics_command_buffer,
+                            &sampled_attachments,
+                            &rendered_subresources,
+                            false,
+                        );
+                        self.cmd_storage_textures_barrier(
+                            graphics_command_buffer,
+                            &storage_textures,
+                            false,
+                        );
                     }
                     SubmitPassType::Compute(pass_data) => {
-                        let compute_pass = self
-                            .compiled_compute_passes
-                            .get(pass_data.compute_pass.id())
-                            .ok_or(gpu_api_err!(
-                                "vulkan submit compute pass {:?} does not exist",
-                                pass_data.compute_pass,
-                            ))?;
+                        let compute_pass =
+                            &self.compiled_compute_passes[pass_data.compute_pass.id()];
 
                         unsafe fn compute_barrier(
                             dev: &Device,
@@ -421,21 +422,28 @@ impl VkContext {
                             )
                         }
 
+                        let descriptors = pass_data
+                            .dispatches
+                            .iter()
+                            .map(|dispatch| {
+                                &self.compute_programs[dispatch.program.id()].descriptors
+                            })
+                            .collect::<Vec<_>>();
+                        let (sampled_attachments, storage_textures) = pass_textures(&descriptors);
+                        self.cmd_attachment_textures_barrier(
+                            graphics_command_buffer,
+                            &sampled_attachments,
+                            &[],
+                            true,
+                        );
+                        self.cmd_storage_textures_barrier(
+                            graphics_command_buffer,
+                            &storage_textures,
+                            true,
+                        );
+
                         for dispatch in pass_data.dispatches.iter() {
-                            compute_pass
-                                .added_programs
-                                .contains(&dispatch.program)
-                                .then_some(())
-                                .ok_or(gpu_api_err!(
-                                    "vulkan submit compute program {:?} was not added",
-                                    dispatch.program
-                                ))?;
-                            let program = self.compute_programs.get(dispatch.program.id()).ok_or(
-                                gpu_api_err!(
-                                    "vulkan submit compute program {:?}",
-                                    dispatch.program
-                                ),
-                            )?;
+                            let program = &self.compute_programs[dispatch.program.id()];
                             self.core.dev.cmd_bind_pipeline(
                                 graphics_command_buffer,
                                 vk::PipelineBindPoint::COMPUTE,
@@ -447,7 +455,7 @@ impl VkContext {
                                 vk::PipelineBindPoint::COMPUTE,
                                 program.layout,
                                 &dispatch.dynamic_buffer_indices,
-                            )?;
+                            );
                             self.core.dev.cmd_dispatch(
                                 graphics_command_buffer,
                                 dispatch.workgroup_count_x as u32,
@@ -463,70 +471,61 @@ impl VkContext {
                         if compute_pass.set_blocking {
                             compute_barrier(&self.core.dev, graphics_command_buffer);
                         }
+
+                        self.cmd_attachment_textures_barrier(
+                            graphics_command_buffer,
+                            &sampled_attachments,
+                            &[],
+                            false,
+                        );
+                        self.cmd_storage_textures_barrier(
+                            graphics_command_buffer,
+                            &storage_textures,
+                            false,
+                        );
+                    }
+                    SubmitPassType::TextureCopy(copy) => {
+                        self.cmd_texture_copy(graphics_command_buffer, copy);
+                    }
+                    SubmitPassType::BufferTextureCopy(copy) => {
+                        self.cmd_buffer_texture_copy(graphics_command_buffer, copy);
+                    }
+                    SubmitPassType::GenerateMipmaps(texture) => {
+                        self.cmd_generate_mipmaps(graphics_command_buffer, *texture);
                     }
                 }
             }
             //  SSBO Copy Backs
-            submit.ssbo_copy_backs.iter().try_for_each(|ssbo_id| {
-                let ssbo = self.ssbos.get(ssbo_id.id()).ok_or(gpu_api_err!(
-                    "vulkan shader storage buffer sync id {:?} does not exist",
-                    ssbo_id
-                ))?;
+            for ssbo_id in submit.ssbo_copy_backs.iter() {
+                let ssbo = &self.ssbos[ssbo_id.id()];
+                if let Some(staging) = &ssbo.staging {
+                    cmd_copy_back(
+                        &self.core.dev,
+                        graphics_command_buffer,
+                        &ssbo.buffer,
+                        staging,
+                    );
+                }
+            }
 
-                let barrier = vk::BufferMemoryBarrier::builder()
-                    .src_access_mask(vk::AccessFlags::SHADER_WRITE)
-                    .dst_access_mask(vk::AccessFlags::TRANSFER_READ)
-                    .dst_access_mask(vk::AccessFlags::TRANSFER_READ)
-                    .buffer(ssbo.buffer.buffer)
-                    .size(vk::WHOLE_SIZE)
-                    .src_queue_family_index(vk::QUEUE_FAMILY_IGNORED)
-                    .dst_queue_family_index(vk::QUEUE_FAMILY_IGNORED)
-                    .build();
-
-                self.core.dev.cmd_pipeline_barrier(
-                    graphics_command_buffer,
-                    vk::PipelineStageFlags::VERTEX_SHADER
-                        | vk::PipelineStageFlags::FRAGMENT_SHADER
-                        | vk::PipelineStageFlags::COMPUTE_SHADER,
-                    vk::PipelineStageFlags::TRANSFER,
-                    vk::DependencyFlags::empty(),
-                    &[],
-                    &[barrier],
-                    &[],
-                );
-
-                let copy_region = vk::BufferCopy::builder()
-                    .size(ssbo.buffer.size as u64)
-                    .build();
-
-                self.core.dev.cmd_copy_buffer(
-                    graphics_command_buffer,
-                    ssbo.buffer.buffer,
-                    ssbo.staging.as_ref().unwrap().buffer,
-                    &[copy_region],
-                );
-
-                let barrier = vk::BufferMemoryBarrier::builder()
-                    .src_access_mask(vk::AccessFlags::TRANSFER_WRITE)
-                    .dst_access_mask(vk::AccessFlags::HOST_READ)
-                    .buffer(ssbo.staging.as_ref().unwrap().buffer)
-                    .size(vk::WHOLE_SIZE)
-                    .src_queue_family_index(vk::QUEUE_FAMILY_IGNORED)
-                    .dst_queue_family_index(vk::QUEUE_FAMILY_IGNORED)
-                    .build();
-
-                self.core.dev.cmd_pipeline_barrier(
-                    graphics_command_buffer,
-                    vk::PipelineStageFlags::TRANSFER,
-                    vk::PipelineStageFlags::HOST,
-                    vk::DependencyFlags::empty(),
-                    &[],
-                    &[barrier],
-                    &[],
-                );
+            //  Buffer Copy Backs
+            for buffer_id in submit.buffer_copy_backs.iter() {
+                let buffer = &self.buffers[buffer_id.id()];
+                if let Some(staging) = &buffer.staging {
+                    cmd_copy_back(
+                        &self.core.dev,
+                        graphics_command_buffer,
+                        &buffer.buffer,
+                        staging,
+                    );
+                }
+            }
 
-                Ok(())
-            })?;
+            //  Attachment Image Copy Backs
+            self.cmd_attachment_image_copy_backs(
+                graphics_command_buffer,
+                &submit.attachment_image_copy_backs,
+            );
 
             self.core
                 .dev
@@ -559,6 +558,9 @@ impl VkContext {
                 .command_buffers(&[graphics_command_buffer])
                 .build();
 
+            //  Only reset right before submitting so that recording errors don't leave the fence
+            //  unsignaled forever.
+            self.core.dev.reset_fences(&[frame_fence]).unwrap();
             self.core
                 .dev
                 .queue_submit(self.core.graphics_queue, &[submit_create], frame_fence)
@@ -607,4 +609,362 @@ impl VkContext {
     }
 
     pub fn sync_submit() {}
+
+    fn check_submit(&self, submit: &Submit) -> GResult<()> {
+        for (vbo, _) in submit.vbo_transfers.iter() {
+            self.vbos
+                .get(vbo.id())
+                .ok_or(gpu_api_err!(
+                    "vulkan transfer vertex buffer id {:?} does not exist",
+                    vbo
+                ))?
+                .check_transfer()?;
+        }
+        for (ibo, _) in submit.ibo_transfers.iter() {
+            self.ibos
+                .get(ibo.id())
+                .ok_or(gpu_api_err!(
+                    "vulkan transfer index buffer id {:?} does not exist",
+                    ibo
+                ))?
+                .check_transfer()?;
+        }
+        for (ubo, _) in submit.ubo_transfers.iter() {
+            self.ubos
+                .get(ubo.id())
+                .ok_or(gpu_api_err!(
+                    "vulkan transfer uniform buffer id {:?} does not exist",
+                    ubo
+                ))?
+                .check_transfer()?;
+        }
+        for (ubo, _, _) in submit.dyn_ubo_transfers.iter() {
+            self.dyn_ubos
+                .get(ubo.id())
+                .ok_or(gpu_api_err!(
+                    "vulkan transfer dynamic uniform buffer id {:?} does not exist",
+                    ubo
+                ))?
+                .check_transfer()?;
+        }
+        for (buffer, data) in submit.buffer_transfers.iter() {
+            self.buffers
+                .get(buffer.id())
+                .ok_or(gpu_api_err!(
+                    "vulkan transfer buffer id {:?} does not exist",
+                    buffer
+                ))?
+                .check_transfer(data)?;
+        }
+        self.check_texture_transfers(&submit.texture_transfers)?;
+
+        for pass_data in submit.passes.iter() {
+            match pass_data {
+                SubmitPassType::Render(pass_data) => self.check_render_pass(pass_data)?,
+                SubmitPassType::Compute(pass_data) => self.check_compute_pass(pass_data)?,
+                SubmitPassType::TextureCopy(copy) => self.check_texture_copy(copy)?,
+                SubmitPassType::BufferTextureCopy(copy) => self.check_buffer_texture_copy(copy)?,
+                SubmitPassType::GenerateMipmaps(texture) => {
+                    self.check_generate_mipmaps(*texture)?
+                }
+            }
+        }
+
+        for ssbo in submit.ssbo_copy_backs.iter() {
+            self.ssbos.get(ssbo.id()).ok_or(gpu_api_err!(
+                "vulkan shader storage buffer sync id {:?} does not exist",
+                ssbo
+            ))?;
+        }
+        for buffer in submit.buffer_copy_backs.iter() {
+            self.get_buffer_with_usage(*buffer, BufferUsage::COPY_SRC)?;
+        }
+        self.check_attachment_image_copy_backs(&submit.attachment_image_copy_backs)
+    }
+
+    fn check_render_pass(&self, pass_data: &PassSubmitData) -> GResult<()> {
+        let pass = self
+            .compiled_passes
+            .get(pass_data.pass.id())
+            .ok_or(gpu_api_err!(
+                "vulkan submit pass {:?} does not exist",
+                pass_data.pass
+            ))?;
+        if pass.should_present && self.surface_ext.is_none() {
+            Err(gpu_api_err!(
+                "vulkan tried to render to surface without surface extension"
+            ))?;
+        }
+        if pass_data.steps_datas.len() != pass.steps.len() {
+            Err(gpu_api_err!(
+                "vulkan submit pass {:?} has {} steps, got data for {}",
+                pass_data.pass,
+                pass.steps.len(),
+                pass_data.steps_datas.len()
+            ))?;
+        }
+        for attachment in pass_data.clear_colors.keys() {
+            let exists = if pass.resolve_image_offsets.is_empty() {
+                attachment.id() < pass.attachment_count
+            } else {
+                pass.resolve_image_offsets.contains_key(&attachment.id())
+            };
+            if !exists {
+                Err(gpu_api_err!(
+                    "vulkan submit pass {:?} clears color attachment {:?} that does not exist",
+                    pass_data.pass,
+                    attachment
+                ))?;
+            }
+        }
+        if let Some(attachment) = pass_data
+            .clear_depths
+            .keys()
+            .find(|attachment| attachment.id() >= pass.attachment_count)
+        {
+            Err(gpu_api_err!(
+                "vulkan submit pass {:?} clears depth attachment {:?} that does not exist",
+                pass_data.pass,
+                attachment
+            ))?;
+        }
+
+        let mut descriptors = vec![];
+        for (step_idx, (step, step_data)) in pass
+            .steps
+            .iter()
+            .zip(pass_data.steps_datas.iter())
+            .enumerate()
+        {
+            if let Some(ibo) = step.index_buffer {
+                self.get_index_buffer(ibo)?;
+            }
+            for &vbo in step.vertex_buffers.iter() {
+                self.get_vertex_buffer(vbo)?;
+            }
+            for draw in step_data.draws.iter() {
+                if !pass.pipelines[step_idx].contains_key(&draw.program) {
+                    Err(gpu_api_err!(
+                        "vulkan submit draw missing program id {:?}",
+                        draw.program
+                    ))?;
+                }
+                let program = self.programs.get(draw.program.id()).ok_or(gpu_api_err!(
+                    "vulkan submit draw program id {:?} does not exist",
+                    draw.program
+                ))?;
+                program
+                    .descriptors
+                    .check_dynamic_indices(&draw.dynamic_buffer_indices)?;
+                descriptors.push(&program.descriptors);
+            }
+        }
+
+        let (sampled_attachments, storage_textures) = pass_textures(&descriptors);
+        let sampled_textures = descriptors
+            .iter()
+            .flat_map(|descriptors| descriptors.textures(self))
+            .collect::<Vec<_>>();
+        self.check_storage_textures(&storage_textures, &sampled_textures, &sampled_attachments)?;
+
+        let rendered_subresources = rendered_subresources(pass);
+        //  Other layers and mip levels of a rendered image can still be sampled.
+        let is_fully_rendered = |image: AttachmentImageId| {
+            let rendered_count = rendered_subresources
+                .iter()
+                .filter(|(rendered, _)| *rendered == image)
+                .count() as u32;
+            self.attachment_images
+                .get(image.id())
+                .is_some_and(|attachment_image| {
+                    rendered_count >= attachment_image.subresource_count()
+                })
+        };
+        if let Some(attachment) = pass.original_pass.attachments.iter().find(|attachment| {
+            attachment.output_image.is_some_and(|image| {
+                storage_textures.contains(&image.into())
+                    || (sampled_attachments.contains(&image) && is_fully_rendered(image))
+            })
+        }) {
+            Err(gpu_api_err!(
+                "vulkan submit pass {:?} samples or stores to its own attachment image {:?}",
+                pass_data.pass,
+                attachment.output_image
+            ))?;
+        }
+        Ok(())
+    }
+
+    fn check_compute_pass(
+        &self,
+        pass_data: &context::extensions::ComputePassSubmitData,
+    ) -> GResult<()> {
+        let compute_pass = self
+            .compiled_compute_passes
+            .get(pass_data.compute_pass.id())
+            .ok_or(gpu_api_err!(
+                "vulkan submit compute pass {:?} does not exist",
+                pass_data.compute_pass,
+            ))?;
+
+        let mut descriptors = vec![];
+        for dispatch in pass_data.dispatches.iter() {
+            compute_pass
+                .added_programs
+                .contains(&dispatch.program)
+                .then_some(())
+                .ok_or(gpu_api_err!(
+                    "vulkan submit compute program {:?} was not added",
+                    dispatch.program
+                ))?;
+            let program = self
+                .compute_programs
+                .get(dispatch.program.id())
+                .ok_or(gpu_api_err!(
+                    "vulkan submit compute program {:?}",
+                    dispatch.program
+                ))?;
+            program
+                .descriptors
+                .check_dynamic_indices(&dispatch.dynamic_buffer_indices)?;
+            descriptors.push(&program.descriptors);
+        }
+
+        let (sampled_attachments, storage_textures) = pass_textures(&descriptors);
+        let sampled_textures = descriptors
+            .iter()
+            .flat_map(|descriptors| descriptors.textures(self))
+            .collect::<Vec<_>>();
+        self.check_storage_textures(&storage_textures, &sampled_textures, &sampled_attachments)
+    }
+
+    fn write_buffer_transfers(&mut self, submit: &Submit) -> GResult<()> {
+        for (vbo, data) in submit.vbo_transfers.iter() {
+            self.vbos[vbo.id()].write_transfer(data)?;
+        }
+        for (ibo, data) in submit.ibo_transfers.iter() {
+            self.ibos[ibo.id()].write_transfer(data)?;
+        }
+        for (ubo, data) in submit.ubo_transfers.iter() {
+            self.ubos[ubo.id()].write_transfer(data)?;
+        }
+        for (ubo, data, index) in submit.dyn_ubo_transfers.iter() {
+            self.dyn_ubos[ubo.id()].write_transfer(data, *index)?;
+        }
+        for (buffer, data) in submit.buffer_transfers.iter() {
+            self.buffers[buffer.id()].write_transfer(data)?;
+        }
+        Ok(())
+    }
+
+    //  Records the copies written by `write_buffer_transfers`.
+    fn cmd_buffer_transfers(&self, cmd: vk::CommandBuffer, submit: &Submit) {
+        let dev = &self.core.dev;
+        for (vbo, _) in submit.vbo_transfers.iter() {
+            self.vbos[vbo.id()].cmd_transfer(dev, cmd);
+        }
+        for (ibo, _) in submit.ibo_transfers.iter() {
+            self.ibos[ibo.id()].cmd_transfer(dev, cmd);
+        }
+        for (ubo, _) in submit.ubo_transfers.iter() {
+            self.ubos[ubo.id()].cmd_transfer(dev, cmd);
+        }
+        for (ubo, _, index) in submit.dyn_ubo_transfers.iter() {
+            self.dyn_ubos[ubo.id()].cmd_transfer(dev, cmd, *index);
+        }
+        for (buffer, data) in submit.buffer_transfers.iter() {
+            self.buffers[buffer.id()].cmd_transfer(dev, cmd, data.len());
+        }
+    }
+}
+
+//  The attachment images sampled and the storage textures used by a pass, each only once.
+fn pass_textures(
+    descriptors: &[&VkDescriptors],
+) -> (Vec<AttachmentImageId>, Vec<GenericTextureId>) {
+    let sampled_attachments = descriptors
+        .iter()
+        .flat_map(|descriptors| descriptors.attachment_textures())
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect();
+    let storage_textures = descriptors
+        .iter()
+        .flat_map(|descriptors| descriptors.storage_textures())
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect();
+    (sampled_attachments, storage_textures)
+}
+
+fn rendered_subresources(
+    pass: &VkCompiledPass,
+) -> Vec<(AttachmentImageId, AttachmentImageSubresource)> {
+    pass.original_pass
+        .attachments
+        .iter()
+        .filter_map(|attachment| {
+            attachment
+                .output_image
+                .map(|image| (image, attachment.output_subresource))
+        })
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect()
+}
+
+unsafe fn cmd_copy_back(
+    dev: &Device,
+    command_buffer: vk::CommandBuffer,
+    buffer: &VkBuffer,
+    staging: &VkBuffer,
+) {
+    let barrier = vk::BufferMemoryBarrier::builder()
+        .src_access_mask(vk::AccessFlags::SHADER_WRITE)
+        .dst_access_mask(vk::AccessFlags::TRANSFER_READ)
+        .buffer(buffer.buffer)
+        .size(vk::WHOLE_SIZE)
+        .src_queue_family_index(vk::QUEUE_FAMILY_IGNORED)
+        .dst_queue_family_index(vk::QUEUE_FAMILY_IGNORED)
+        .build();
+
+    dev.cmd_pipeline_barrier(
+        command_buffer,
+        vk::PipelineStageFlags::VERTEX_SHADER
+            | vk::PipelineStageFlags::FRAGMENT_SHADER
+            | vk::PipelineStageFlags::COMPUTE_SHADER,
+        vk::PipelineStageFlags::TRANSFER,
+        vk::DependencyFlags::empty(),
+        &[],
+        &[barrier],
+        &[],
+    );
+
+    let copy_region = vk::BufferCopy::builder().size(buffer.size as u64).build();
+
+    dev.cmd_copy_buffer(
+        command_buffer,
+        buffer.buffer,
+        staging.buffer,
+        &[copy_region],
+    );
+
+    let barrier = vk::BufferMemoryBarrier::builder()
+        .src_access_mask(vk::AccessFlags::TRANSFER_WRITE)
+        .dst_access_mask(vk::AccessFlags::HOST_READ)
+        .buffer(staging.buffer)
+        .size(vk::WHOLE_SIZE)
+        .src_queue_family_index(vk::QUEUE_FAMILY_IGNORED)
+        .dst_queue_family_index(vk::QUEUE_FAMILY_IGNORED)
+        .build();
+
+    dev.cmd_pipeline_barrier(
+        command_buffer,
+        vk::PipelineStageFlags::TRANSFER,
+        vk::PipelineStageFlags::HOST,
+        vk::DependencyFlags::empty(),
+        &[],
+        &[barrier],
+        &[],
+    );
 }
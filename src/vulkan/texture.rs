@@ -24,29 +24,18 @@ impl VkContext {
             "vulkan upload texture {:?} doesn't exist",
             texture
         ))?;
+        if let VkImageKind::Image2DArray(_) = texture.kind {
+            Err(gpu_api_err!(
+                "vulkan called upload_texture on a texture array, use upload_texture_layer instead"
+            ))?;
+        }
         let texture_width = texture.width;
         let texture_height = texture.height;
         let texture_image = texture.image.image;
         let texture_mip_level = texture.mip_levels;
 
-        let image_subresource_layers = vk::ImageSubresourceLayers::builder()
-            .aspect_mask(vk::ImageAspectFlags::COLOR)
-            .mip_level(0)
-            .base_array_layer(0)
-            .layer_count(1)
-            .build();
-        let copy_region = vk::BufferImageCopy::builder()
-            .buffer_offset(0)
-            .buffer_row_length(0)
-            .buffer_image_height(0)
-            .image_extent(vk::Extent3D {
-                width: texture.width as u32,
-                height: texture.height as u32,
-                depth: 1,
-            })
-            .image_subresource(image_subresource_layers)
-            .build();
-        texture.upload(&self.core, &[data], &[copy_region], ext.clone())?;
+        let copy_regions = texture.get_copy_regions(&[data], 0, &ext)?;
+        texture.upload(&self.core, &[data], &copy_regions, 0, ext.clone())?;
 
         if ext.generate_mipmaps.is_some() {
             unsafe {
@@ -56,6 +45,8 @@ impl VkContext {
                     texture_height,
                     texture_image,
                     texture_mip_level,
+                    0,
+                    1,
                 )?;
             }
         }
@@ -87,46 +78,916 @@ impl VkContext {
             upload.posz,
             upload.negz,
         ];
-        let mut copy_regions = vec![];
-        let mut offset = 0;
-        for (idx, data) in datas.iter().enumerate() {
-            let image_subresource_layers = vk::ImageSubresourceLayers::builder()
-                .aspect_mask(vk::ImageAspectFlags::COLOR)
-                .mip_level(0)
-                .base_array_layer(idx as u32)
-                .layer_count(1)
-                .build();
-            let copy_region = vk::BufferImageCopy::builder()
-                .buffer_offset(offset as u64)
-                .buffer_row_length(0)
-                .buffer_image_height(0)
-                .image_extent(vk::Extent3D {
-                    width: texture.width as u32,
-                    height: texture.height as u32,
-                    depth: 1,
+        let copy_regions = texture.get_copy_regions(&datas, 0, &ext)?;
+        texture.upload(&self.core, &datas, &copy_regions, 0, ext.clone())?;
+
+        if ext.generate_mipmaps.is_some() {
+            unsafe {
+                generate_mipmaps(
+                    self,
+                    texture_width,
+                    texture_height,
+                    texture_image,
+                    texture_mip_level,
+                    0,
+                    datas.len() as u32,
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn upload_texture_layer(
+        &mut self,
+        texture: TextureId,
+        layer: u32,
+        data: &[u8],
+        ext: Option<UploadTextureExt>,
+    ) -> GResult<()> {
+        let ext = ext.unwrap_or_default();
+        let texture = self.textures.get_mut(texture.id()).ok_or(gpu_api_err!(
+            "vulkan upload texture layer {:?} doesn't exist",
+            texture
+        ))?;
+        let VkImageKind::Image2DArray(array_layers) = texture.kind else {
+            Err(gpu_api_err!("vulkan called upload_texture_layer on a non-array texture, use upload_texture instead"))?
+        };
+        if layer >= array_layers {
+            Err(gpu_api_err!(
+                "vulkan upload texture layer {} >= array layers {}",
+                layer,
+                array_layers
+            ))?;
+        }
+        let texture_width = texture.width;
+        let texture_height = texture.height;
+        let texture_image = texture.image.image;
+        let texture_mip_level = texture.mip_levels;
+
+        let copy_regions = texture.get_copy_regions(&[data], layer, &ext)?;
+        texture.upload(&self.core, &[data], &copy_regions, layer, ext.clone())?;
+
+        if ext.generate_mipmaps.is_some() {
+            unsafe {
+                generate_mipmaps(
+                    self,
+                    texture_width,
+                    texture_height,
+                    texture_image,
+                    texture_mip_level,
+                    layer,
+                    1,
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn upload_texture_region(
+        &mut self,
+        texture: TextureId,
+        region: TextureRegion,
+        data: &[u8],
+        ext: Option<UploadTextureRegionExt>,
+    ) -> GResult<()> {
+        let ext = ext.unwrap_or_default();
+        let texture = self.textures.get(texture.id()).ok_or(gpu_api_err!(
+            "vulkan upload texture region {:?} doesn't exist",
+            texture
+        ))?;
+        let row_pitch = texture.check_region(&region, data.len(), ext.row_pitch)?;
+
+        let mut staging = VkBuffer::new(
+            &self.core.dev,
+            &self.drop_queue,
+            &mut self.alloc,
+            data.len(),
+            vk::BufferUsageFlags::TRANSFER_SRC,
+            MemoryLocation::CpuToGpu,
+        )?;
+        staging.map_copy_data(data.as_ptr(), data.len(), 0)?;
+
+        let _misc_command = self.core.misc_command()?;
+        unsafe {
+            texture.cmd_copy_region(
+                &self.core.dev,
+                self.core.misc_command_buffer,
+                staging.buffer,
+                0,
+                &region,
+                row_pitch,
+            );
+        }
+
+        Ok(())
+    }
+
+    pub fn read_texture(
+        &mut self,
+        texture: TextureId,
+        mip_level: u32,
+        layer: u32,
+        _ext: Option<ReadTextureExt>,
+    ) -> GResult<Vec<u8>> {
+        let texture = self.textures.get(texture.id()).ok_or(gpu_api_err!(
+            "vulkan read texture {:?} doesn't exist",
+            texture
+        ))?;
+        if mip_level >= texture.mip_levels {
+            Err(gpu_api_err!(
+                "vulkan read texture mip level {} >= mip levels {}",
+                mip_level,
+                texture.mip_levels
+            ))?;
+        }
+        if layer >= texture.kind.array_layers() {
+            Err(gpu_api_err!(
+                "vulkan read texture layer {} >= layers {}",
+                layer,
+                texture.kind.array_layers()
+            ))?;
+        }
+
+        let (mip_width, mip_height) = mip_level_extent(texture.width, texture.height, mip_level);
+        let size = texture.format.image_byte_size(mip_width, mip_height) * texture.depth;
+
+        let staging = VkBuffer::new(
+            &self.core.dev,
+            &self.drop_queue,
+            &mut self.alloc,
+            size,
+            vk::BufferUsageFlags::TRANSFER_DST,
+            MemoryLocation::GpuToCpu,
+        )?;
+
+        let range = vk::ImageSubresourceRange::builder()
+            .aspect_mask(vk::ImageAspectFlags::COLOR)
+            .base_mip_level(mip_level)
+            .level_count(1)
+            .base_array_layer(layer)
+            .layer_count(1)
+            .build();
+
+        let image_transfer_barrier = vk::ImageMemoryBarrier::builder()
+            .old_layout(vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL)
+            .new_layout(vk::ImageLayout::TRANSFER_SRC_OPTIMAL)
+            .image(texture.image.image)
+            .subresource_range(range)
+            .src_access_mask(vk::AccessFlags::MEMORY_WRITE)
+            .dst_access_mask(vk::AccessFlags::TRANSFER_READ)
+            .build();
+
+        let mut image_use_barrier = image_transfer_barrier;
+        image_use_barrier.old_layout = vk::ImageLayout::TRANSFER_SRC_OPTIMAL;
+        image_use_barrier.new_layout = vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL;
+        image_use_barrier.src_access_mask = vk::AccessFlags::TRANSFER_READ;
+        image_use_barrier.dst_access_mask = vk::AccessFlags::SHADER_READ;
+
+        let copy_region = vk::BufferImageCopy::builder()
+            .image_subresource(
+                vk::ImageSubresourceLayers::builder()
+                    .aspect_mask(vk::ImageAspectFlags::COLOR)
+                    .mip_level(mip_level)
+                    .base_array_layer(layer)
+                    .layer_count(1)
+                    .build(),
+            )
+            .image_extent(vk::Extent3D {
+                width: mip_width as u32,
+                height: mip_height as u32,
+                depth: texture.depth as u32,
+            })
+            .build();
+
+        {
+            let _misc_command = self.core.misc_command()?;
+            unsafe {
+                self.core.dev.cmd_pipeline_barrier(
+                    self.core.misc_command_buffer,
+                    vk::PipelineStageFlags::ALL_COMMANDS,
+                    vk::PipelineStageFlags::TRANSFER,
+                    vk::DependencyFlags::empty(),
+                    &[],
+                    &[],
+                    &[image_transfer_barrier],
+                );
+                self.core.dev.cmd_copy_image_to_buffer(
+                    self.core.misc_command_buffer,
+                    texture.image.image,
+                    vk::ImageLayout::TRANSFER_SRC_OPTIMAL,
+                    staging.buffer,
+                    &[copy_region],
+                );
+                self.core.dev.cmd_pipeline_barrier(
+                    self.core.misc_command_buffer,
+                    vk::PipelineStageFlags::TRANSFER,
+                    vk::PipelineStageFlags::ALL_COMMANDS,
+                    vk::DependencyFlags::empty(),
+                    &[],
+                    &[],
+                    &[image_use_barrier],
+                );
+            }
+        }
+
+        let mapped_ptr = staging
+            .allocation
+            .mapped_ptr()
+            .ok_or(gpu_api_err!(
+                "vulkan gpu_allocator, this buffer cannot be mapped"
+            ))?
+            .as_ptr() as *const u8;
+        Ok(unsafe { std::slice::from_raw_parts(mapped_ptr, size) }.to_vec())
+    }
+
+    pub async fn async_read_texture(
+        &mut self,
+        texture: TextureId,
+        mip_level: u32,
+        layer: u32,
+        ext: Option<ReadTextureExt>,
+    ) -> GResult<Vec<u8>> {
+        self.read_texture(texture, mip_level, layer, ext)
+    }
+
+    pub(super) fn check_texture_transfers(
+        &self,
+        transfers: &[(TextureId, TextureRegion, &[u8], UploadTextureRegionExt)],
+    ) -> GResult<()> {
+        for (texture_id, region, data, ext) in transfers.iter() {
+            let texture = self.textures.get(texture_id.id()).ok_or(gpu_api_err!(
+                "vulkan transfer into texture {:?} doesn't exist",
+                texture_id
+            ))?;
+            texture.check_region(region, data.len(), ext.row_pitch)?;
+        }
+        Ok(())
+    }
+
+    //  Writes every `Submit::transfer_into_texture` into this frame's staging buffer.
+    //  Returns the staging offset and row pitch of each transfer.
+    pub(super) fn write_texture_transfers(
+        &mut self,
+        transfers: &[(TextureId, TextureRegion, &[u8], UploadTextureRegionExt)],
+    ) -> GResult<Vec<(usize, usize)>> {
+        if transfers.is_empty() {
+            return Ok(vec![]);
+        }
+
+        //  Offsets must be a multiple of the block size and of 4.
+        let mut offsets = vec![];
+        let mut total_size = 0;
+        for (texture_id, region, data, ext) in transfers.iter() {
+            let row_pitch =
+                self.textures[texture_id.id()].check_region(region, data.len(), ext.row_pitch)?;
+            offsets.push((total_size, row_pitch));
+            total_size = (total_size + data.len()).next_multiple_of(16);
+        }
+
+        let staging = self.submit.texture_staging.get_mut(&self.frame);
+        if staging
+            .as_ref()
+            .is_none_or(|staging| staging.size < total_size)
+        {
+            //  This frame's fence has already been waited on, so the old buffer is unused.
+            *staging = Some(VkBuffer::new(
+                &self.core.dev,
+                &self.drop_queue,
+                &mut self.alloc,
+                total_size,
+                vk::BufferUsageFlags::TRANSFER_SRC,
+                MemoryLocation::CpuToGpu,
+            )?);
+        }
+        let staging = staging.as_mut().unwrap();
+
+        for ((_, _, data, _), &(offset, _)) in transfers.iter().zip(offsets.iter()) {
+            staging.map_copy_data(data.as_ptr(), data.len(), offset)?;
+        }
+
+        Ok(offsets)
+    }
+
+    //  Records the copies written by `write_texture_transfers`.
+    pub(super) unsafe fn cmd_texture_transfers(
+        &self,
+        cmd: vk::CommandBuffer,
+        transfers: &[(TextureId, TextureRegion, &[u8], UploadTextureRegionExt)],
+        offsets: &[(usize, usize)],
+    ) {
+        let Some(staging) = self.submit.texture_staging.get(&self.frame) else {
+            return;
+        };
+        for ((texture_id, region, _, _), &(offset, row_pitch)) in transfers.iter().zip(offsets) {
+            self.textures[texture_id.id()].cmd_copy_region(
+                &self.core.dev,
+                cmd,
+                staging.buffer,
+                offset,
+                region,
+                row_pitch,
+            );
+        }
+    }
+
+    pub fn async_upload_texture(
+        &mut self,
+        texture: TextureId,
+        data: &[u8],
+        _ext: Option<AsyncUploadTextureExt>,
+    ) -> GResult<UploadId> {
+        let texture = self.textures.get(texture.id()).ok_or(gpu_api_err!(
+            "vulkan async upload texture {:?} doesn't exist",
+            texture
+        ))?;
+        if texture.kind.array_layers() > 1 {
+            Err(gpu_api_err!(
+                "vulkan called async_upload_texture on a cubemap or array texture"
+            ))?;
+        }
+        //  Level 0 is transitioned from `UNDEFINED`, so all of it must be written.
+        let expected_size = texture
+            .format
+            .image_byte_size(texture.width, texture.height)
+            * texture.depth;
+        if data.len() != expected_size {
+            Err(gpu_api_err!(
+                "vulkan async upload texture size ({}) != mip level 0 size ({})",
+                data.len(),
+                expected_size
+            ))?;
+        }
+        let texture_width = texture.width;
+        let texture_height = texture.height;
+        let texture_depth = texture.depth;
+        let texture_image = texture.image.image;
+
+        let mut upload = self.begin_async_upload(data.len())?;
+        upload.staging.map_copy_data(data.as_ptr(), data.len(), 0)?;
+
+        let range = vk::ImageSubresourceRange::builder()
+            .aspect_mask(vk::ImageAspectFlags::COLOR)
+            .base_mip_level(0)
+            .level_count(1)
+            .layer_count(1)
+            .build();
+
+        //  The previous contents are discarded, so there is no ownership to release beforehand.
+        let image_transfer_barrier = vk::ImageMemoryBarrier::builder()
+            .old_layout(vk::ImageLayout::UNDEFINED)
+            .new_layout(vk::ImageLayout::TRANSFER_DST_OPTIMAL)
+            .image(texture_image)
+            .subresource_range(range)
+            .src_access_mask(vk::AccessFlags::empty())
+            .dst_access_mask(vk::AccessFlags::TRANSFER_WRITE)
+            .build();
+
+        let image_subresource_layers = vk::ImageSubresourceLayers::builder()
+            .aspect_mask(vk::ImageAspectFlags::COLOR)
+            .mip_level(0)
+            .base_array_layer(0)
+            .layer_count(1)
+            .build();
+        let copy_region = vk::BufferImageCopy::builder()
+            .buffer_offset(0)
+            .buffer_row_length(0)
+            .buffer_image_height(0)
+            .image_extent(vk::Extent3D {
+                width: texture_width as u32,
+                height: texture_height as u32,
+                depth: texture_depth as u32,
+            })
+            .image_subresource(image_subresource_layers)
+            .build();
+
+        unsafe {
+            //  Chained to the transfer's wait on the frames submitted before it.
+            self.core.dev.cmd_pipeline_barrier(
+                upload.transfer_command_buffer,
+                vk::PipelineStageFlags::TRANSFER,
+                vk::PipelineStageFlags::TRANSFER,
+                vk::DependencyFlags::empty(),
+                &[],
+                &[],
+                &[image_transfer_barrier],
+            );
+            self.core.dev.cmd_copy_buffer_to_image(
+                upload.transfer_command_buffer,
+                upload.staging.buffer,
+                texture_image,
+                vk::ImageLayout::TRANSFER_DST_OPTIMAL,
+                &[copy_region],
+            );
+            upload.cmd_finish_image(&self.core.dev, texture_image, range);
+        }
+
+        self.submit_async_upload(upload)
+    }
+
+    //  Storage textures are moved to the general layout, so they cannot also be sampled.
+    pub(super) fn check_storage_textures(
+        &self,
+        storage_textures: &[GenericTextureId],
+        sampled_textures: &[TextureId],
+        sampled_attachments: &[AttachmentImageId],
+    ) -> GResult<()> {
+        if let Some(storage_texture) =
+            storage_textures
+                .iter()
+                .find(|&&storage_texture| match storage_texture {
+                    GenericTextureId::Texture(texture) => sampled_textures.contains(&texture),
+                    GenericTextureId::AttachmentImage(attachment_image) => {
+                        sampled_attachments.contains(&attachment_image)
+                    }
                 })
-                .image_subresource(image_subresource_layers)
-                .build();
-            offset += data.len();
-            copy_regions.push(copy_region);
+        {
+            Err(gpu_api_err!(
+                "vulkan submit storage texture {:?} is also sampled by the same pass",
+                storage_texture
+            ))?;
+        }
+        Ok(())
+    }
+
+    //  Transitions the storage textures used by a pass before and after it.
+    pub(super) unsafe fn cmd_storage_textures_barrier(
+        &self,
+        cmd: vk::CommandBuffer,
+        textures: &[GenericTextureId],
+        to_storage: bool,
+    ) {
+        for storage_texture in textures {
+            match *storage_texture {
+                GenericTextureId::Texture(texture_id) => {
+                    self.textures[texture_id.id()].cmd_storage_barrier(
+                        &self.core.dev,
+                        cmd,
+                        to_storage,
+                    );
+                }
+                GenericTextureId::AttachmentImage(attachment_image_id) => {
+                    self.attachment_images[attachment_image_id.id()].cmd_storage_barrier(
+                        &self.core.dev,
+                        cmd,
+                        to_storage,
+                    );
+                }
+            }
         }
-        texture.upload(&self.core, &datas, &copy_regions, ext.clone())?;
+    }
+
+    fn get_transfer_image(&self, texture: GenericTextureId) -> GResult<VkTransferImage> {
+        Ok(match texture {
+            GenericTextureId::Texture(texture_id) => self
+                .textures
+                .get(texture_id.id())
+                .ok_or(gpu_api_err!(
+                    "vulkan texture copy texture id {:?} does not exist",
+                    texture_id
+                ))?
+                .transfer_image(),
+            GenericTextureId::AttachmentImage(attachment_image_id) => self
+                .attachment_images
+                .get(attachment_image_id.id())
+                .ok_or(gpu_api_err!(
+                    "vulkan texture copy attachment image id {:?} does not exist",
+                    attachment_image_id
+                ))?
+                .transfer_image(),
+        })
+    }
+
+    //  Only for ids that have already been checked with `get_transfer_image`.
+    fn transfer_image(&self, texture: GenericTextureId) -> VkTransferImage {
+        match texture {
+            GenericTextureId::Texture(texture_id) => {
+                self.textures[texture_id.id()].transfer_image()
+            }
+            GenericTextureId::AttachmentImage(attachment_image_id) => {
+                self.attachment_images[attachment_image_id.id()].transfer_image()
+            }
+        }
+    }
+
+    pub(super) fn check_texture_copy(&self, copy: &TextureCopy) -> GResult<()> {
+        let src = self.get_transfer_image(copy.src)?;
+        let dst = self.get_transfer_image(copy.dst)?;
+        src.check_region(&copy.src_region)?;
+        dst.check_region(&copy.dst_region)?;
+
+        if src.samples != vk::SampleCountFlags::TYPE_1
+            || dst.samples != vk::SampleCountFlags::TYPE_1
+        {
+            Err(gpu_api_err!(
+                "vulkan texture copy from {:?} to {:?} cannot be multisampled",
+                copy.src,
+                copy.dst
+            ))?;
+        }
+        if copy.src == copy.dst
+            && copy.src_region.mip_level == copy.dst_region.mip_level
+            && copy.src_region.layer == copy.dst_region.layer
+        {
+            Err(gpu_api_err!(
+                "vulkan texture copy from {:?} cannot use the same mip level and layer as its destination",
+                copy.src
+            ))?;
+        }
+        match copy.blit_filter {
+            None => {
+                if src.format != dst.format {
+                    Err(gpu_api_err!(
+                        "vulkan texture copy from {:?} to {:?} requires the same format, got {:?} and {:?}",
+                        copy.src,
+                        copy.dst,
+                        src.format,
+                        dst.format
+                    ))?;
+                }
+                if (
+                    copy.src_region.width,
+                    copy.src_region.height,
+                    copy.src_region.depth,
+                ) != (
+                    copy.dst_region.width,
+                    copy.dst_region.height,
+                    copy.dst_region.depth,
+                ) {
+                    Err(gpu_api_err!(
+                        "vulkan texture copy regions {:?} and {:?} are not the same size",
+                        copy.src_region,
+                        copy.dst_region
+                    ))?;
+                }
+                src.check_block_alignment(&copy.src_region)?;
+                dst.check_block_alignment(&copy.dst_region)?;
+            }
+            Some(filter) => {
+                if src.aspect != vk::ImageAspectFlags::COLOR
+                    || dst.aspect != vk::ImageAspectFlags::COLOR
+                {
+                    Err(gpu_api_err!(
+                        "vulkan texture blit from {:?} to {:?} requires color images",
+                        copy.src,
+                        copy.dst
+                    ))?;
+                }
+                if src.is_compressed() || dst.is_compressed() {
+                    Err(gpu_api_err!(
+                        "vulkan texture blit from {:?} to {:?} cannot use compressed formats",
+                        copy.src,
+                        copy.dst
+                    ))?;
+                }
+                let src_features = if filter == SamplerFilter::Linear {
+                    vk::FormatFeatureFlags::BLIT_SRC
+                        | vk::FormatFeatureFlags::SAMPLED_IMAGE_FILTER_LINEAR
+                } else {
+                    vk::FormatFeatureFlags::BLIT_SRC
+                };
+                if !self.core.has_format_features(src.format, src_features)
+                    || !self
+                        .core
+                        .has_format_features(dst.format, vk::FormatFeatureFlags::BLIT_DST)
+                {
+                    Err(gpu_api_err!(
+                        "vulkan texture blit from {:?} to {:?} with {:?} is not supported for formats {:?} and {:?}",
+                        copy.src,
+                        copy.dst,
+                        filter,
+                        src.format,
+                        dst.format
+                    ))?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    //  Records a `Submit::copy_texture` or `Submit::blit_texture` checked by `check_texture_copy`.
+    pub(super) unsafe fn cmd_texture_copy(&self, cmd: vk::CommandBuffer, copy: &TextureCopy) {
+        let src = self.transfer_image(copy.src);
+        let dst = self.transfer_image(copy.dst);
+
+        let to_transfer_barriers = [
+            src.transfer_barrier(
+                &copy.src_region,
+                vk::ImageLayout::TRANSFER_SRC_OPTIMAL,
+                vk::AccessFlags::TRANSFER_READ,
+                true,
+            ),
+            dst.transfer_barrier(
+                &copy.dst_region,
+                vk::ImageLayout::TRANSFER_DST_OPTIMAL,
+                vk::AccessFlags::TRANSFER_WRITE,
+                true,
+            ),
+        ];
+        let from_transfer_barriers = [
+            src.transfer_barrier(
+                &copy.src_region,
+                vk::ImageLayout::TRANSFER_SRC_OPTIMAL,
+                vk::AccessFlags::TRANSFER_READ,
+                false,
+            ),
+            dst.transfer_barrier(
+                &copy.dst_region,
+                vk::ImageLayout::TRANSFER_DST_OPTIMAL,
+                vk::AccessFlags::TRANSFER_WRITE,
+                false,
+            ),
+        ];
+
+        let src_offset = vk::Offset3D {
+            x: copy.src_region.x as i32,
+            y: copy.src_region.y as i32,
+            z: copy.src_region.z as i32,
+        };
+        let dst_offset = vk::Offset3D {
+            x: copy.dst_region.x as i32,
+            y: copy.dst_region.y as i32,
+            z: copy.dst_region.z as i32,
+        };
+
+        let dev = &self.core.dev;
+        dev.cmd_pipeline_barrier(
+            cmd,
+            vk::PipelineStageFlags::ALL_COMMANDS,
+            vk::PipelineStageFlags::TRANSFER,
+            vk::DependencyFlags::empty(),
+            &[],
+            &[],
+            &to_transfer_barriers,
+        );
+        match copy.blit_filter {
+            None => {
+                let image_copy = vk::ImageCopy::builder()
+                    .src_subresource(src.subresource_layers(&copy.src_region))
+                    .src_offset(src_offset)
+                    .dst_subresource(dst.subresource_layers(&copy.dst_region))
+                    .dst_offset(dst_offset)
+                    .extent(vk::Extent3D {
+                        width: copy.src_region.width as u32,
+                        height: copy.src_region.height as u32,
+                        depth: copy.src_region.depth as u32,
+                    })
+                    .build();
+                dev.cmd_copy_image(
+                    cmd,
+                    src.image,
+                    vk::ImageLayout::TRANSFER_SRC_OPTIMAL,
+                    dst.image,
+                    vk::ImageLayout::TRANSFER_DST_OPTIMAL,
+                    &[image_copy],
+                );
+            }
+            Some(filter) => {
+                let image_blit = vk::ImageBlit::builder()
+                    .src_subresource(src.subresource_layers(&copy.src_region))
+                    .src_offsets([
+                        src_offset,
+                        vk::Offset3D {
+                            x: (copy.src_region.x + copy.src_region.width) as i32,
+                            y: (copy.src_region.y + copy.src_region.height) as i32,
+                            z: (copy.src_region.z + copy.src_region.depth) as i32,
+                        },
+                    ])
+                    .dst_subresource(dst.subresource_layers(&copy.dst_region))
+                    .dst_offsets([
+                        dst_offset,
+                        vk::Offset3D {
+                            x: (copy.dst_region.x + copy.dst_region.width) as i32,
+                            y: (copy.dst_region.y + copy.dst_region.height) as i32,
+                            z: (copy.dst_region.z + copy.dst_region.depth) as i32,
+                        },
+                    ])
+                    .build();
+                dev.cmd_blit_image(
+                    cmd,
+                    src.image,
+                    vk::ImageLayout::TRANSFER_SRC_OPTIMAL,
+                    dst.image,
+                    vk::ImageLayout::TRANSFER_DST_OPTIMAL,
+                    &[image_blit],
+                    match filter {
+                        SamplerFilter::Nearest => vk::Filter::NEAREST,
+                        SamplerFilter::Linear => vk::Filter::LINEAR,
+                    },
+                );
+            }
+        }
+        dev.cmd_pipeline_barrier(
+            cmd,
+            vk::PipelineStageFlags::TRANSFER,
+            vk::PipelineStageFlags::ALL_COMMANDS,
+            vk::DependencyFlags::empty(),
+            &[],
+            &[],
+            &from_transfer_barriers,
+        );
+    }
+
+    pub(super) fn check_buffer_texture_copy(&self, copy: &BufferTextureCopy) -> GResult<()> {
+        let buffer = self.get_buffer_with_usage(
+            copy.buffer,
+            if copy.to_texture {
+                BufferUsage::COPY_SRC
+            } else {
+                BufferUsage::COPY_DST
+            },
+        )?;
+        let image = self.get_transfer_image(copy.texture)?;
+        image.check_buffer_region(&copy.region, buffer.size, &copy.ext)?;
+
+        if image.samples != vk::SampleCountFlags::TYPE_1 {
+            Err(gpu_api_err!(
+                "vulkan buffer texture copy {:?} cannot be multisampled",
+                copy.texture
+            ))?;
+        }
+        Ok(())
+    }
+
+    //  Records a `Submit::copy_buffer_to_texture` or `Submit::copy_texture_to_buffer` checked by
+    //  `check_buffer_texture_copy`.
+    pub(super) unsafe fn cmd_buffer_texture_copy(
+        &self,
+        cmd: vk::CommandBuffer,
+        copy: &BufferTextureCopy,
+    ) {
+        let buffer = &self.buffers[copy.buffer.id()].buffer;
+        let image = self.transfer_image(copy.texture);
+        let row_pitch = copy.ext.row_pitch.unwrap_or(image.row_size(&copy.region));
+
+        let (transfer_layout, transfer_access, buffer_access) = if copy.to_texture {
+            (
+                vk::ImageLayout::TRANSFER_DST_OPTIMAL,
+                vk::AccessFlags::TRANSFER_WRITE,
+                vk::AccessFlags::TRANSFER_READ,
+            )
+        } else {
+            (
+                vk::ImageLayout::TRANSFER_SRC_OPTIMAL,
+                vk::AccessFlags::TRANSFER_READ,
+                vk::AccessFlags::TRANSFER_WRITE,
+            )
+        };
+        let buffer_copy =
+            image.buffer_image_copy(&copy.region, copy.ext.buffer_offset.unwrap_or(0), row_pitch);
+
+        //  The buffer may have been written to by a previous pass, or may be read by the next one.
+        let to_transfer_memory_barrier = vk::MemoryBarrier::builder()
+            .src_access_mask(vk::AccessFlags::MEMORY_WRITE)
+            .dst_access_mask(buffer_access)
+            .build();
+        let from_transfer_memory_barrier = vk::MemoryBarrier::builder()
+            .src_access_mask(buffer_access | vk::AccessFlags::TRANSFER_WRITE)
+            .dst_access_mask(vk::AccessFlags::MEMORY_READ | vk::AccessFlags::MEMORY_WRITE)
+            .build();
 
-        if ext.generate_mipmaps.is_some() {
-            unsafe {
-                generate_mipmaps(
-                    self,
-                    texture_width,
-                    texture_height,
-                    texture_image,
-                    texture_mip_level,
-                )?;
-            }
+        let dev = &self.core.dev;
+        dev.cmd_pipeline_barrier(
+            cmd,
+            vk::PipelineStageFlags::ALL_COMMANDS,
+            vk::PipelineStageFlags::TRANSFER,
+            vk::DependencyFlags::empty(),
+            &[to_transfer_memory_barrier],
+            &[],
+            &[image.transfer_barrier(&copy.region, transfer_layout, transfer_access, true)],
+        );
+        if copy.to_texture {
+            dev.cmd_copy_buffer_to_image(
+                cmd,
+                buffer.buffer,
+                image.image,
+                transfer_layout,
+                &[buffer_copy],
+            );
+        } else {
+            dev.cmd_copy_image_to_buffer(
+                cmd,
+                image.image,
+                transfer_layout,
+                buffer.buffer,
+                &[buffer_copy],
+            );
         }
+        dev.cmd_pipeline_barrier(
+            cmd,
+            vk::PipelineStageFlags::TRANSFER,
+            vk::PipelineStageFlags::ALL_COMMANDS,
+            vk::DependencyFlags::empty(),
+            &[from_transfer_memory_barrier],
+            &[],
+            &[image.transfer_barrier(&copy.region, transfer_layout, transfer_access, false)],
+        );
+    }
 
+    pub(super) fn check_generate_mipmaps(&self, texture: GenericTextureId) -> GResult<()> {
+        let image = self.get_transfer_image(texture)?;
+        if image.mip_levels < 2 {
+            Err(gpu_api_err!(
+                "vulkan generate mipmaps {:?} has no mip levels to generate",
+                texture
+            ))?;
+        }
+        if image.aspect != vk::ImageAspectFlags::COLOR
+            || image.samples != vk::SampleCountFlags::TYPE_1
+            || image.block_dimensions != (1, 1)
+            || image.depth != 1
+        {
+            Err(gpu_api_err!(
+                "vulkan generate mipmaps {:?} requires an uncompressed 2d color image without msaa",
+                texture
+            ))?;
+        }
+        if !supports_mipmap_blits(&self.core, image.format) {
+            Err(gpu_api_err!(
+                "vulkan generate mipmaps {:?} format {:?} does not support linear blits",
+                texture,
+                image.format
+            ))?;
+        }
         Ok(())
     }
 
+    //  Records a `Submit::generate_mipmaps` checked by `check_generate_mipmaps`.
+    pub(super) unsafe fn cmd_generate_mipmaps(
+        &self,
+        cmd: vk::CommandBuffer,
+        texture: GenericTextureId,
+    ) {
+        let image = self.transfer_image(texture);
+        let range = vk::ImageSubresourceRange::builder()
+            .aspect_mask(vk::ImageAspectFlags::COLOR)
+            .base_mip_level(0)
+            .level_count(1)
+            .base_array_layer(0)
+            .layer_count(image.array_layers)
+            .build();
+        let to_transfer_barrier = vk::ImageMemoryBarrier::builder()
+            .image(image.image)
+            .src_access_mask(vk::AccessFlags::MEMORY_WRITE)
+            .dst_access_mask(vk::AccessFlags::TRANSFER_READ)
+            .old_layout(image.layout)
+            .new_layout(vk::ImageLayout::TRANSFER_SRC_OPTIMAL)
+            .subresource_range(range)
+            .build();
+        let mut from_transfer_barrier = to_transfer_barrier;
+        from_transfer_barrier.src_access_mask = vk::AccessFlags::TRANSFER_READ;
+        from_transfer_barrier.dst_access_mask = image.access;
+        from_transfer_barrier.old_layout = vk::ImageLayout::TRANSFER_SRC_OPTIMAL;
+        from_transfer_barrier.new_layout = image.layout;
+        from_transfer_barrier.subresource_range.level_count = image.mip_levels;
+
+        let dev = &self.core.dev;
+        dev.cmd_pipeline_barrier(
+            cmd,
+            vk::PipelineStageFlags::ALL_COMMANDS,
+            vk::PipelineStageFlags::TRANSFER,
+            vk::DependencyFlags::empty(),
+            &[],
+            &[],
+            &[to_transfer_barrier],
+        );
+        cmd_blit_mip_chain(
+            dev,
+            cmd,
+            image.image,
+            image.width,
+            image.height,
+            image.mip_levels,
+            0,
+            image.array_layers,
+        );
+        dev.cmd_pipeline_barrier(
+            cmd,
+            vk::PipelineStageFlags::TRANSFER,
+            vk::PipelineStageFlags::ALL_COMMANDS,
+            vk::DependencyFlags::empty(),
+            &[],
+            &[],
+            &[from_transfer_barrier],
+        );
+    }
+
+    pub fn get_supported_texture_compressions(&self) -> GResult<Vec<TextureCompression>> {
+        let features = &self.core.enabled_features;
+        Ok([
+            (features.texture_compression_bc, TextureCompression::Bc),
+            (features.texture_compression_etc2, TextureCompression::Etc2),
+            (
+                features.texture_compression_astc_ldr,
+                TextureCompression::Astc,
+            ),
+        ]
+        .into_iter()
+        .filter_map(|(enabled, compression)| (enabled == vk::TRUE).then_some(compression))
+        .collect())
+    }
+
     pub fn get_texture_max_lod(&self, texture: TextureId) -> GResult<f32> {
         let texture = self.textures.get(texture.id()).ok_or(gpu_api_err!(
             "vulkan get texture max lod: {:?} does not exist",
@@ -134,17 +995,53 @@ impl VkContext {
         ))?;
         Ok(texture.mip_levels as f32)
     }
+
+    pub fn new_texture_view(
+        &mut self,
+        texture_id: TextureId,
+        ext: Option<NewTextureViewExt>,
+    ) -> GResult<TextureViewId> {
+        let ext = ext.unwrap_or_default();
+        let texture = self.textures.get(texture_id.id()).ok_or(gpu_api_err!(
+            "vulkan new texture view texture id {:?} does not exist",
+            texture_id
+        ))?;
+        let range = ext.resolve_range(
+            texture.kind.view_dimension(),
+            texture.mip_levels,
+            texture.kind.array_layers(),
+        )?;
+        let image_view = new_ranged_image_view(
+            &self.core.dev,
+            texture.image.image,
+            texture.image.format,
+            texture.image.view_aspect,
+            &range,
+            ext.swizzle.unwrap_or_default(),
+        )?;
+
+        self.texture_views.push(VkTextureView {
+            texture: texture_id,
+            image_view,
+            drop_queue_ref: Arc::clone(&self.drop_queue),
+        });
+        Ok(TextureViewId::from_id(self.texture_views.len() - 1))
+    }
 }
 
 pub struct VkTexture {
     width: usize,
     height: usize,
+    depth: usize,
     mip_levels: u32,
+    format: TextureFormat,
 
     pub image: VkImage,
     staging: VkBuffer,
     pub image_view: vk::ImageView,
-    is_cubemap_texture: bool,
+    //  Only for textures created with `NewTextureExt::enable_storage`.
+    pub storage_image_view: Option<vk::ImageView>,
+    pub kind: VkImageKind,
 
     drop_queue_ref: VkDropQueueRef,
 }
@@ -159,21 +1056,87 @@ impl VkTexture {
     ) -> GResult<Self> {
         let mip_levels = if ext.enable_mipmaps.is_some() {
             ext.mip_levels
-                .unwrap_or(std::cmp::max(width, height).ilog2())
+                .unwrap_or(std::cmp::max(width, height).ilog2() + 1)
         } else {
             1
         };
         let enable_cubemap = ext.enable_cubemap.is_some();
+        let depth = ext.depth.unwrap_or(1);
+
+        let kind = if ext.depth.is_some() {
+            if enable_cubemap || ext.enable_mipmaps.is_some() || ext.array_layers.is_some() {
+                Err(gpu_api_err!(
+                    "vulkan 3d textures cannot be cubemaps, arrays, or have mipmaps"
+                ))?;
+            }
+            VkImageKind::Image3D
+        } else if let Some(array_layers) = ext.array_layers {
+            if enable_cubemap {
+                Err(gpu_api_err!("vulkan texture arrays cannot be cubemaps"))?;
+            }
+            if array_layers == 0 {
+                Err(gpu_api_err!(
+                    "vulkan texture arrays need at least one layer"
+                ))?;
+            }
+            VkImageKind::Image2DArray(array_layers)
+        } else if enable_cubemap {
+            VkImageKind::Cubemap
+        } else {
+            VkImageKind::Image2D
+        };
 
         let vkformat = match format {
             // TextureFormat::Rgb => vk::Format::R8G8B8_UNORM,
-            // TODO FIX: This should be R8G8B8A8_SRGB, shouldn't it?
             TextureFormat::Rgba => vk::Format::R8G8B8A8_UNORM,
+            TextureFormat::Rgba8Srgb => vk::Format::R8G8B8A8_SRGB,
+            TextureFormat::Bgra8 => vk::Format::B8G8R8A8_UNORM,
+            TextureFormat::R8 => vk::Format::R8_UNORM,
+            TextureFormat::Rg8 => vk::Format::R8G8_UNORM,
+            TextureFormat::R16F => vk::Format::R16_SFLOAT,
+            TextureFormat::Rgba16F => vk::Format::R16G16B16A16_SFLOAT,
+            TextureFormat::R32F => vk::Format::R32_SFLOAT,
+            TextureFormat::Rgba32F => vk::Format::R32G32B32A32_SFLOAT,
+            TextureFormat::Bc1Rgba => vk::Format::BC1_RGBA_UNORM_BLOCK,
+            TextureFormat::Bc1RgbaSrgb => vk::Format::BC1_RGBA_SRGB_BLOCK,
+            TextureFormat::Bc3Rgba => vk::Format::BC3_UNORM_BLOCK,
+            TextureFormat::Bc3RgbaSrgb => vk::Format::BC3_SRGB_BLOCK,
+            TextureFormat::Bc5Rg => vk::Format::BC5_UNORM_BLOCK,
+            TextureFormat::Bc7Rgba => vk::Format::BC7_UNORM_BLOCK,
+            TextureFormat::Bc7RgbaSrgb => vk::Format::BC7_SRGB_BLOCK,
+            TextureFormat::Etc2Rgb8 => vk::Format::ETC2_R8G8B8_UNORM_BLOCK,
+            TextureFormat::Etc2Rgb8Srgb => vk::Format::ETC2_R8G8B8_SRGB_BLOCK,
+            TextureFormat::Etc2Rgba8 => vk::Format::ETC2_R8G8B8A8_UNORM_BLOCK,
+            TextureFormat::Etc2Rgba8Srgb => vk::Format::ETC2_R8G8B8A8_SRGB_BLOCK,
+            TextureFormat::Astc4x4 => vk::Format::ASTC_4X4_UNORM_BLOCK,
+            TextureFormat::Astc4x4Srgb => vk::Format::ASTC_4X4_SRGB_BLOCK,
         };
 
-        let vkusages = vk::ImageUsageFlags::TRANSFER_DST
+        if let Some(compression) = format.compression() {
+            if !context
+                .get_supported_texture_compressions()?
+                .contains(&compression)
+            {
+                Err(gpu_api_err!(
+                    "vulkan texture format {:?} requires unsupported compression {:?}",
+                    format,
+                    compression
+                ))?;
+            }
+        }
+
+        let mut vkusages = vk::ImageUsageFlags::TRANSFER_DST
             | vk::ImageUsageFlags::TRANSFER_SRC
             | vk::ImageUsageFlags::SAMPLED;
+        if ext.enable_storage.is_some() {
+            if !is_storage_format(vkformat) {
+                Err(gpu_api_err!(
+                    "vulkan storage texture format {:?} is not a storage texture format",
+                    format
+                ))?;
+            }
+            vkusages |= vk::ImageUsageFlags::STORAGE;
+        }
         let aspect = vk::ImageAspectFlags::COLOR;
 
         let image = VkImage::new(
@@ -185,11 +1148,11 @@ impl VkTexture {
             aspect,
             vk::SampleCountFlags::TYPE_1,
             mip_levels,
-            enable_cubemap,
+            kind,
             vk::Extent3D {
                 width: width as u32,
                 height: height as u32,
-                depth: 1,
+                depth: depth as u32,
             },
         )?;
 
@@ -199,19 +1162,53 @@ impl VkTexture {
             vkformat,
             aspect,
             mip_levels,
-            enable_cubemap,
+            kind,
         )?;
 
-        let per_pixel_byte_size = match format {
-            // TextureFormat::Rgb => 3,
-            TextureFormat::Rgba => 4,
-        };
+        //  Storage images can only be bound with a single mip level.
+        let storage_image_view = ext
+            .enable_storage
+            .map(|_| new_image_view(&context.core.dev, image.image, vkformat, aspect, 1, kind))
+            .transpose()?;
+
+        //  Start in a known layout so that region uploads can preserve the existing contents.
+        let image_init_barrier = vk::ImageMemoryBarrier::builder()
+            .old_layout(vk::ImageLayout::UNDEFINED)
+            .new_layout(vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL)
+            .image(image.image)
+            .subresource_range(
+                vk::ImageSubresourceRange::builder()
+                    .aspect_mask(aspect)
+                    .level_count(mip_levels)
+                    .layer_count(kind.array_layers())
+                    .build(),
+            )
+            .src_access_mask(vk::AccessFlags::empty())
+            .dst_access_mask(vk::AccessFlags::SHADER_READ)
+            .build();
+        {
+            let _misc_command = context.core.misc_command()?;
+            unsafe {
+                context.core.dev.cmd_pipeline_barrier(
+                    context.core.misc_command_buffer,
+                    vk::PipelineStageFlags::TOP_OF_PIPE,
+                    vk::PipelineStageFlags::ALL_COMMANDS,
+                    vk::DependencyFlags::empty(),
+                    &[],
+                    &[],
+                    &[image_init_barrier],
+                );
+            }
+        }
 
+        //  Large enough to hold a full mip chain for every uploaded layer or slice.
+        //  Texture arrays are uploaded one layer at a time.
+        let staging_layers = if enable_cubemap { 6 } else { 1 };
         let staging = VkBuffer::new(
             &context.core.dev,
             &context.drop_queue,
             &mut context.alloc,
-            per_pixel_byte_size * width * height * if enable_cubemap { 6 } else { 1 },
+            format.mip_chain_byte_size(width, height, mip_levels) * staging_layers * depth,
             vk::BufferUsageFlags::TRANSFER_SRC,
             MemoryLocation::CpuToGpu,
         )?;
@@ -219,29 +1216,341 @@ impl VkTexture {
         Ok(VkTexture {
             width,
             height,
+            depth,
             mip_levels,
+            format,
 
             image,
             staging,
             image_view,
-            is_cubemap_texture: enable_cubemap,
+            storage_image_view,
+            kind,
             drop_queue_ref: Arc::clone(&context.drop_queue),
         })
     }
 
+    fn transfer_image(&self) -> VkTransferImage {
+        VkTransferImage {
+            image: self.image.image,
+            format: self.image.format,
+            layout: vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL,
+            aspect: vk::ImageAspectFlags::COLOR,
+            access: vk::AccessFlags::SHADER_READ,
+            samples: vk::SampleCountFlags::TYPE_1,
+            buffer_aspect: vk::ImageAspectFlags::COLOR,
+            block_dimensions: self.format.block_dimensions(),
+            block_byte_size: self.format.block_byte_size(),
+            mip_levels: self.mip_levels,
+            array_layers: self.kind.array_layers(),
+            width: self.width,
+            height: self.height,
+            depth: self.depth,
+        }
+    }
+
+    //  Moves between the resting sampled layout and the general layout used for storage.
+    unsafe fn cmd_storage_barrier(&self, dev: &Device, cmd: vk::CommandBuffer, to_storage: bool) {
+        let (old_layout, new_layout, src_access, dst_access) = if to_storage {
+            (
+                vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL,
+                vk::ImageLayout::GENERAL,
+                vk::AccessFlags::SHADER_READ,
+                vk::AccessFlags::SHADER_READ | vk::AccessFlags::SHADER_WRITE,
+            )
+        } else {
+            (
+                vk::ImageLayout::GENERAL,
+                vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL,
+                vk::AccessFlags::SHADER_WRITE,
+                vk::AccessFlags::SHADER_READ,
+            )
+        };
+        let barrier = vk::ImageMemoryBarrier::builder()
+            .old_layout(old_layout)
+            .new_layout(new_layout)
+            .image(self.image.image)
+            .subresource_range(
+                vk::ImageSubresourceRange::builder()
+                    .aspect_mask(vk::ImageAspectFlags::COLOR)
+                    .level_count(self.mip_levels)
+                    .layer_count(self.kind.array_layers())
+                    .build(),
+            )
+            .src_access_mask(src_access)
+            .dst_access_mask(dst_access)
+            .build();
+        dev.cmd_pipeline_barrier(
+            cmd,
+            vk::PipelineStageFlags::ALL_COMMANDS,
+            vk::PipelineStageFlags::ALL_COMMANDS,
+            vk::DependencyFlags::empty(),
+            &[],
+            &[],
+            &[barrier],
+        );
+    }
+
+    //  Transitions only the affected subresource so that the rest of the texture is preserved.
+    unsafe fn cmd_copy_region(
+        &self,
+        dev: &Device,
+        cmd: vk::CommandBuffer,
+        staging: vk::Buffer,
+        offset: usize,
+        region: &TextureRegion,
+        row_pitch: usize,
+    ) {
+        let range = vk::ImageSubresourceRange::builder()
+            .aspect_mask(vk::ImageAspectFlags::COLOR)
+            .base_mip_level(region.mip_level)
+            .level_count(1)
+            .base_array_layer(region.layer)
+            .layer_count(1)
+            .build();
+
+        let image_transfer_barrier = vk::ImageMemoryBarrier::builder()
+            .old_layout(vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL)
+            .new_layout(vk::ImageLayout::TRANSFER_DST_OPTIMAL)
+            .image(self.image.image)
+            .subresource_range(range)
+            .src_access_mask(vk::AccessFlags::SHADER_READ)
+            .dst_access_mask(vk::AccessFlags::TRANSFER_WRITE)
+            .build();
+
+        let mut image_use_barrier = image_transfer_barrier;
+        image_use_barrier.old_layout = vk::ImageLayout::TRANSFER_DST_OPTIMAL;
+        image_use_barrier.new_layout = vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL;
+        image_use_barrier.src_access_mask = vk::AccessFlags::TRANSFER_WRITE;
+        image_use_barrier.dst_access_mask = vk::AccessFlags::SHADER_READ;
+
+        let (block_width, _) = self.format.block_dimensions();
+        let image_subresource_layers = vk::ImageSubresourceLayers::builder()
+            .aspect_mask(vk::ImageAspectFlags::COLOR)
+            .mip_level(region.mip_level)
+            .base_array_layer(region.layer)
+            .layer_count(1)
+            .build();
+        let copy_region = vk::BufferImageCopy::builder()
+            .buffer_offset(offset as u64)
+            .buffer_row_length((row_pitch / self.format.block_byte_size() * block_width) as u32)
+            .buffer_image_height(0)
+            .image_offset(vk::Offset3D {
+                x: region.x as i32,
+                y: region.y as i32,
+                z: region.z as i32,
+            })
+            .image_extent(vk::Extent3D {
+                width: region.width as u32,
+                height: region.height as u32,
+                depth: region.depth as u32,
+            })
+            .image_subresource(image_subresource_layers)
+            .build();
+
+        dev.cmd_pipeline_barrier(
+            cmd,
+            vk::PipelineStageFlags::ALL_COMMANDS,
+            vk::PipelineStageFlags::TRANSFER,
+            vk::DependencyFlags::empty(),
+            &[],
+            &[],
+            &[image_transfer_barrier],
+        );
+        dev.cmd_copy_buffer_to_image(
+            cmd,
+            staging,
+            self.image.image,
+            vk::ImageLayout::TRANSFER_DST_OPTIMAL,
+            &[copy_region],
+        );
+        dev.cmd_pipeline_barrier(
+            cmd,
+            vk::PipelineStageFlags::TRANSFER,
+            vk::PipelineStageFlags::ALL_COMMANDS,
+            vk::DependencyFlags::empty(),
+            &[],
+            &[],
+            &[image_use_barrier],
+        );
+    }
+
+    //  Returns the row pitch of the data.
+    fn check_region(
+        &self,
+        region: &TextureRegion,
+        data_size: usize,
+        row_pitch: Option<usize>,
+    ) -> GResult<usize> {
+        if region.mip_level >= self.mip_levels {
+            Err(gpu_api_err!(
+                "vulkan upload texture region mip level {} >= mip levels {}",
+                region.mip_level,
+                self.mip_levels
+            ))?;
+        }
+        if region.layer >= self.kind.array_layers() {
+            Err(gpu_api_err!(
+                "vulkan upload texture region layer {} >= layers {}",
+                region.layer,
+                self.kind.array_layers()
+            ))?;
+        }
+
+        let (mip_width, mip_height) = mip_level_extent(self.width, self.height, region.mip_level);
+        if region.width == 0
+            || region.height == 0
+            || region.depth == 0
+            || region.x + region.width > mip_width
+            || region.y + region.height > mip_height
+            || region.z + region.depth > self.depth
+        {
+            Err(gpu_api_err!(
+                "vulkan upload texture region {:?} is outside of mip level extent ({}, {}, {})",
+                region,
+                mip_width,
+                mip_height,
+                self.depth
+            ))?;
+        }
+
+        let (block_width, block_height) = self.format.block_dimensions();
+        if !region.x.is_multiple_of(block_width)
+            || !region.y.is_multiple_of(block_height)
+            || (!region.width.is_multiple_of(block_width) && region.x + region.width != mip_width)
+            || (!region.height.is_multiple_of(block_height)
+                && region.y + region.height != mip_height)
+        {
+            Err(gpu_api_err!(
+                "vulkan upload texture region {:?} is not aligned to {:?} blocks",
+                region,
+                self.format
+            ))?;
+        }
+
+        let row_size = self.format.row_byte_size(region.width);
+        let row_pitch = row_pitch.unwrap_or(row_size);
+        if row_pitch < row_size || !row_pitch.is_multiple_of(self.format.block_byte_size()) {
+            Err(gpu_api_err!(
+                "vulkan upload texture region row pitch {} is invalid for rows of {} bytes",
+                row_pitch,
+                row_size
+            ))?;
+        }
+
+        let rows = region.height.div_ceil(block_height) * region.depth;
+        let expected_size = row_pitch * (rows - 1) + row_size;
+        if data_size < expected_size {
+            Err(gpu_api_err!(
+                "vulkan upload texture region data size ({}) < expected size ({})",
+                data_size,
+                expected_size
+            ))?;
+        }
+
+        Ok(row_pitch)
+    }
+
+    //  One copy region per layer, or per layer and mip level if the data includes the mip chain.
+    fn get_copy_regions(
+        &self,
+        datas: &[&[u8]],
+        base_layer: u32,
+        ext: &UploadTextureExt,
+    ) -> GResult<Vec<vk::BufferImageCopy>> {
+        let mip_levels = if ext.includes_mip_chain.is_some() {
+            self.mip_levels
+        } else {
+            1
+        };
+
+        let mut copy_regions = vec![];
+        let mut offset = 0;
+        for (layer, data) in datas.iter().enumerate() {
+            let expected_size =
+                self.format
+                    .mip_chain_byte_size(self.width, self.height, mip_levels)
+                    * self.depth;
+            if data.len() < expected_size {
+                Err(gpu_api_err!(
+                    "vulkan upload texture data size ({}) < expected size ({})",
+                    data.len(),
+                    expected_size
+                ))?;
+            }
+
+            let mut mip_offset = offset;
+            for mip_level in 0..mip_levels {
+                let (mip_width, mip_height) = mip_level_extent(self.width, self.height, mip_level);
+                let image_subresource_layers = vk::ImageSubresourceLayers::builder()
+                    .aspect_mask(vk::ImageAspectFlags::COLOR)
+                    .mip_level(mip_level)
+                    .base_array_layer(base_layer + layer as u32)
+                    .layer_count(1)
+                    .build();
+                let copy_region = vk::BufferImageCopy::builder()
+                    .buffer_offset(mip_offset as u64)
+                    .buffer_row_length(0)
+                    .buffer_image_height(0)
+                    .image_extent(vk::Extent3D {
+                        width: mip_width as u32,
+                        height: mip_height as u32,
+                        depth: self.depth as u32,
+                    })
+                    .image_subresource(image_subresource_layers)
+                    .build();
+                copy_regions.push(copy_region);
+                mip_offset += self.format.image_byte_size(mip_width, mip_height);
+            }
+            offset += data.len();
+        }
+        Ok(copy_regions)
+    }
+
     fn upload(
         &mut self,
         core: &VkCore,
         datas: &[&[u8]],
         regions: &[vk::BufferImageCopy],
+        base_layer: u32,
         ext: UploadTextureExt,
     ) -> GResult<()> {
-        if self.is_cubemap_texture && datas.len() == 1 {
+        let is_cubemap_texture = self.kind == VkImageKind::Cubemap;
+        if is_cubemap_texture && datas.len() == 1 {
             Err(gpu_api_err!("vulkan called upload_texture on a cubemap texture, use upload_cubemap_texture instead"))?;
         }
-        if !self.is_cubemap_texture && datas.len() != 1 {
+        if !is_cubemap_texture && datas.len() != 1 {
             Err(gpu_api_err!("vulkan called upload_cubemap_texture on a non-cubemap texture, use upload_texture instead"))?;
         }
+        if ext.generate_mipmaps.is_some() && self.format.compression().is_some() {
+            Err(gpu_api_err!(
+                "vulkan cannot generate mipmaps for compressed texture format {:?}",
+                self.format
+            ))?;
+        }
+        if ext.generate_mipmaps.is_some() && ext.includes_mip_chain.is_some() {
+            Err(gpu_api_err!(
+                "vulkan cannot generate mipmaps for data that already includes the mip chain"
+            ))?;
+        }
+        if ext.generate_mipmaps.is_some() && self.depth > 1 {
+            Err(gpu_api_err!(
+                "vulkan cannot generate mipmaps for 3d textures"
+            ))?;
+        }
+        if ext.generate_mipmaps.is_some() && !supports_mipmap_blits(core, self.image.format) {
+            Err(gpu_api_err!(
+                "vulkan generate mipmaps texture format {:?} does not support linear blits",
+                self.format
+            ))?;
+        }
+        let total_size = datas.iter().map(|data| data.len()).sum::<usize>();
+        if total_size > self.staging.size {
+            Err(gpu_api_err!(
+                "vulkan upload texture data size ({}) > texture size ({})",
+                total_size,
+                self.staging.size
+            ))?;
+        }
 
         let mut offset = 0;
         for data in datas.iter() {
@@ -255,8 +1564,13 @@ impl VkTexture {
         let range = vk::ImageSubresourceRange::builder()
             .aspect_mask(vk::ImageAspectFlags::COLOR)
             .base_mip_level(0)
-            .level_count(1)
-            .layer_count(if self.is_cubemap_texture { 6 } else { 1 })
+            .level_count(if ext.includes_mip_chain.is_some() {
+                self.mip_levels
+            } else {
+                1
+            })
+            .base_array_layer(base_layer)
+            .layer_count(datas.len() as u32)
             .build();
 
         let image_transfer_barrier = vk::ImageMemoryBarrier::builder()
@@ -327,6 +1641,16 @@ impl VkTexture {
     }
 }
 
+//  Each mip level is linearly blitted from the one before it.
+fn supports_mipmap_blits(core: &VkCore, format: vk::Format) -> bool {
+    core.has_format_features(
+        format,
+        vk::FormatFeatureFlags::BLIT_SRC
+            | vk::FormatFeatureFlags::BLIT_DST
+            | vk::FormatFeatureFlags::SAMPLED_IMAGE_FILTER_LINEAR,
+    )
+}
+
 //  Derived from https://github.com/SaschaWillems/Vulkan/blob/master/examples/texturemipmapgen/texturemipmapgen.cpp
 //  Thank you Sascha Willems! <3
 unsafe fn generate_mipmaps(
@@ -335,6 +1659,8 @@ unsafe fn generate_mipmaps(
     height: usize,
     image: vk::Image,
     mip_levels: u32,
+    base_layer: u32,
+    layer_count: u32,
 ) -> GResult<()> {
     let _misc = context.core.misc_command()?;
 
@@ -342,7 +1668,8 @@ unsafe fn generate_mipmaps(
         .aspect_mask(vk::ImageAspectFlags::COLOR)
         .base_mip_level(0)
         .level_count(1)
-        .layer_count(1)
+        .base_array_layer(base_layer)
+        .layer_count(layer_count)
         .build();
 
     let image_transition_barrier = vk::ImageMemoryBarrier::builder()
@@ -364,35 +1691,93 @@ unsafe fn generate_mipmaps(
         &[image_transition_barrier],
     );
 
+    cmd_blit_mip_chain(
+        &context.core.dev,
+        context.core.misc_command_buffer,
+        image,
+        width,
+        height,
+        mip_levels,
+        base_layer,
+        layer_count,
+    );
+
+    let range = vk::ImageSubresourceRange::builder()
+        .aspect_mask(vk::ImageAspectFlags::COLOR)
+        .level_count(mip_levels)
+        .base_array_layer(base_layer)
+        .layer_count(layer_count)
+        .build();
+
+    let image_transition_barrier = vk::ImageMemoryBarrier::builder()
+        .image(image)
+        .src_access_mask(vk::AccessFlags::empty())
+        .dst_access_mask(vk::AccessFlags::TRANSFER_WRITE)
+        .old_layout(vk::ImageLayout::TRANSFER_SRC_OPTIMAL)
+        .new_layout(vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL)
+        .subresource_range(range)
+        .build();
+
+    context.core.dev.cmd_pipeline_barrier(
+        context.core.misc_command_buffer,
+        vk::PipelineStageFlags::TRANSFER,
+        vk::PipelineStageFlags::TRANSFER,
+        vk::DependencyFlags::empty(),
+        &[],
+        &[],
+        &[image_transition_barrier],
+    );
+
+    Ok(())
+}
+
+//  Blits each mip level from the one before it.
+//  Expects mip level 0 in TRANSFER_SRC_OPTIMAL and leaves every mip level in TRANSFER_SRC_OPTIMAL.
+#[allow(clippy::too_many_arguments)]
+unsafe fn cmd_blit_mip_chain(
+    dev: &Device,
+    cmd: vk::CommandBuffer,
+    image: vk::Image,
+    width: usize,
+    height: usize,
+    mip_levels: u32,
+    base_layer: u32,
+    layer_count: u32,
+) {
     for i in 1..mip_levels {
+        //  Non-power-of-two sizes round down, but never below one pixel.
+        let (src_width, src_height) = mip_level_extent(width, height, i - 1);
+        let (dst_width, dst_height) = mip_level_extent(width, height, i);
         let blit = vk::ImageBlit::builder()
             .src_subresource(
                 vk::ImageSubresourceLayers::builder()
                     .aspect_mask(vk::ImageAspectFlags::COLOR)
-                    .layer_count(1)
+                    .base_array_layer(base_layer)
+                    .layer_count(layer_count)
                     .mip_level(i - 1)
                     .build(),
             )
             .dst_subresource(
                 vk::ImageSubresourceLayers::builder()
                     .aspect_mask(vk::ImageAspectFlags::COLOR)
-                    .layer_count(1)
+                    .base_array_layer(base_layer)
+                    .layer_count(layer_count)
                     .mip_level(i)
                     .build(),
             )
             .src_offsets([
                 vk::Offset3D::default(),
                 vk::Offset3D {
-                    x: (width as u32 >> (i - 1)) as i32,
-                    y: (height as u32 >> (i - 1)) as i32,
+                    x: src_width as i32,
+                    y: src_height as i32,
                     z: 1,
                 },
             ])
             .dst_offsets([
                 vk::Offset3D::default(),
                 vk::Offset3D {
-                    x: (width as u32 >> i) as i32,
-                    y: (height as u32 >> i) as i32,
+                    x: dst_width as i32,
+                    y: dst_height as i32,
                     z: 1,
                 },
             ])
@@ -402,7 +1787,8 @@ unsafe fn generate_mipmaps(
             .aspect_mask(vk::ImageAspectFlags::COLOR)
             .base_mip_level(i)
             .level_count(1)
-            .layer_count(1)
+            .base_array_layer(base_layer)
+            .layer_count(layer_count)
             .build();
 
         let image_transition_barrier = vk::ImageMemoryBarrier::builder()
@@ -414,8 +1800,8 @@ unsafe fn generate_mipmaps(
             .subresource_range(mip_subresource_range)
             .build();
 
-        context.core.dev.cmd_pipeline_barrier(
-            context.core.misc_command_buffer,
+        dev.cmd_pipeline_barrier(
+            cmd,
             vk::PipelineStageFlags::TRANSFER,
             vk::PipelineStageFlags::TRANSFER,
             vk::DependencyFlags::empty(),
@@ -424,8 +1810,8 @@ unsafe fn generate_mipmaps(
             &[image_transition_barrier],
         );
 
-        context.core.dev.cmd_blit_image(
-            context.core.misc_command_buffer,
+        dev.cmd_blit_image(
+            cmd,
             image,
             vk::ImageLayout::TRANSFER_SRC_OPTIMAL,
             image,
@@ -443,8 +1829,8 @@ unsafe fn generate_mipmaps(
             .subresource_range(mip_subresource_range)
             .build();
 
-        context.core.dev.cmd_pipeline_barrier(
-            context.core.misc_command_buffer,
+        dev.cmd_pipeline_barrier(
+            cmd,
             vk::PipelineStageFlags::TRANSFER,
             vk::PipelineStageFlags::TRANSFER,
             vk::DependencyFlags::empty(),
@@ -453,36 +1839,33 @@ unsafe fn generate_mipmaps(
             &[image_transition_barrier],
         );
     }
-    let range = vk::ImageSubresourceRange::builder()
-        .aspect_mask(vk::ImageAspectFlags::COLOR)
-        .level_count(mip_levels)
-        .base_array_layer(0)
-        .layer_count(1)
-        .build();
+}
 
-    let image_transition_barrier = vk::ImageMemoryBarrier::builder()
-        .image(image)
-        .src_access_mask(vk::AccessFlags::empty())
-        .dst_access_mask(vk::AccessFlags::TRANSFER_WRITE)
-        .old_layout(vk::ImageLayout::TRANSFER_SRC_OPTIMAL)
-        .new_layout(vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL)
-        .subresource_range(range)
-        .build();
+impl Drop for VkTexture {
+    fn drop(&mut self) {
+        let image_view = self.image_view;
+        let storage_image_view = self.storage_image_view;
 
-    context.core.dev.cmd_pipeline_barrier(
-        context.core.misc_command_buffer,
-        vk::PipelineStageFlags::TRANSFER,
-        vk::PipelineStageFlags::TRANSFER,
-        vk::DependencyFlags::empty(),
-        &[],
-        &[],
-        &[image_transition_barrier],
-    );
+        self.drop_queue_ref
+            .lock()
+            .unwrap()
+            .push(Box::new(move |dev, _| unsafe {
+                dev.destroy_image_view(image_view, None);
+                if let Some(storage_image_view) = storage_image_view {
+                    dev.destroy_image_view(storage_image_view, None);
+                }
+            }))
+    }
+}
 
-    Ok(())
+pub struct VkTextureView {
+    pub texture: TextureId,
+    pub image_view: vk::ImageView,
+
+    drop_queue_ref: VkDropQueueRef,
 }
 
-impl Drop for VkTexture {
+impl Drop for VkTextureView {
     fn drop(&mut self) {
         let image_view = self.image_view;
 
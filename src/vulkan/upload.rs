@@ -0,0 +1,395 @@
+use super::*;
+
+impl VkContext {
+    pub fn poll_upload(&mut self, upload: UploadId) -> GResult<bool> {
+        if upload.id() >= self.next_upload_id {
+            Err(gpu_api_err!(
+                "vulkan poll upload id {:?} does not exist",
+                upload
+            ))?;
+        }
+        let Some(pending) = self.uploads.get(&upload.id()) else {
+            return Ok(true);
+        };
+        let done = unsafe { self.core.dev.get_fence_status(pending.fence) }
+            .map_err(|e| gpu_api_err!("vulkan poll upload fence status {}", e))?;
+        if done {
+            self.uploads.remove(&upload.id());
+        }
+        Ok(done)
+    }
+
+    pub(super) fn begin_async_upload(&mut self, data_size: usize) -> GResult<VkAsyncUpload> {
+        let staging = VkBuffer::new(
+            &self.core.dev,
+            &self.drop_queue,
+            &mut self.alloc,
+            data_size,
+            vk::BufferUsageFlags::TRANSFER_SRC,
+            MemoryLocation::CpuToGpu,
+        )?;
+
+        let release_command_buffer =
+            allocate_command_buffer(&self.core.dev, self.core.graphics_command_pool)?;
+        let transfer_command_buffer =
+            allocate_command_buffer(&self.core.dev, self.core.transfer_command_pool)?;
+
+        let acquire = if self.core.transfer_queue_family != self.core.graphics_queue_family {
+            Some(VkAsyncUploadAcquire {
+                command_buffer: allocate_command_buffer(
+                    &self.core.dev,
+                    self.core.graphics_command_pool,
+                )?,
+                semaphore: new_semaphore(&self.core.dev)?,
+            })
+        } else {
+            None
+        };
+
+        let upload = VkAsyncUpload {
+            staging,
+            release_command_buffer,
+            release_semaphore: new_semaphore(&self.core.dev)?,
+            transfer_command_buffer,
+            acquire,
+            fence: new_fence(&self.core.dev, false)?,
+            graphics_queue_family: self.core.graphics_queue_family,
+            transfer_queue_family: self.core.transfer_queue_family,
+            graphics_command_pool: self.core.graphics_command_pool,
+            transfer_command_pool: self.core.transfer_command_pool,
+            drop_queue_ref: Arc::clone(&self.drop_queue),
+        };
+
+        let command_begin = vk::CommandBufferBeginInfo::builder()
+            .flags(vk::CommandBufferUsageFlags::ONE_TIME_SUBMIT)
+            .build();
+        unsafe {
+            self.core
+                .dev
+                .begin_command_buffer(upload.release_command_buffer, &command_begin)
+                .map_err(|e| gpu_api_err!("vulkan upload begin command buffer {}", e))?;
+            self.core
+                .dev
+                .begin_command_buffer(upload.transfer_command_buffer, &command_begin)
+                .map_err(|e| gpu_api_err!("vulkan upload begin command buffer {}", e))?;
+            if let Some(acquire) = &upload.acquire {
+                self.core
+                    .dev
+                    .begin_command_buffer(acquire.command_buffer, &command_begin)
+                    .map_err(|e| gpu_api_err!("vulkan upload begin command buffer {}", e))?;
+            }
+        }
+
+        Ok(upload)
+    }
+
+    pub(super) fn submit_async_upload(&mut self, upload: VkAsyncUpload) -> GResult<UploadId> {
+        unsafe {
+            self.core
+                .dev
+                .end_command_buffer(upload.release_command_buffer)
+                .map_err(|e| gpu_api_err!("vulkan upload end command buffer {}", e))?;
+            self.core
+                .dev
+                .end_command_buffer(upload.transfer_command_buffer)
+                .map_err(|e| gpu_api_err!("vulkan upload end command buffer {}", e))?;
+
+            //  The signal covers every frame submitted before it, so the transfer waits for them
+            //  on the gpu instead of the cpu.
+            let release_submit = vk::SubmitInfo::builder()
+                .command_buffers(&[upload.release_command_buffer])
+                .signal_semaphores(&[upload.release_semaphore])
+                .build();
+            self.core
+                .dev
+                .queue_submit(
+                    self.core.graphics_queue,
+                    &[release_submit],
+                    vk::Fence::null(),
+                )
+                .map_err(|e| gpu_api_err!("vulkan upload release submit {}", e))?;
+
+            if let Some(acquire) = &upload.acquire {
+                self.core
+                    .dev
+                    .end_command_buffer(acquire.command_buffer)
+                    .map_err(|e| gpu_api_err!("vulkan upload end command buffer {}", e))?;
+
+                //  Transfer queue does the copy and releases ownership, then the graphics queue
+                //  acquires ownership once the copy is done.
+                let transfer_submit = vk::SubmitInfo::builder()
+                    .command_buffers(&[upload.transfer_command_buffer])
+                    .wait_semaphores(&[upload.release_semaphore])
+                    .wait_dst_stage_mask(&[vk::PipelineStageFlags::TRANSFER])
+                    .signal_semaphores(&[acquire.semaphore])
+                    .build();
+                self.core
+                    .dev
+                    .queue_submit(
+                        self.core.transfer_queue,
+                        &[transfer_submit],
+                        vk::Fence::null(),
+                    )
+                    .map_err(|e| gpu_api_err!("vulkan upload transfer submit {}", e))?;
+
+                let acquire_submit = vk::SubmitInfo::builder()
+                    .command_buffers(&[acquire.command_buffer])
+                    .wait_semaphores(&[acquire.semaphore])
+                    .wait_dst_stage_mask(&[vk::PipelineStageFlags::ALL_COMMANDS])
+                    .build();
+                self.core
+                    .dev
+                    .queue_submit(self.core.graphics_queue, &[acquire_submit], upload.fence)
+                    .map_err(|e| gpu_api_err!("vulkan upload acquire submit {}", e))?;
+            } else {
+                let transfer_submit = vk::SubmitInfo::builder()
+                    .command_buffers(&[upload.transfer_command_buffer])
+                    .wait_semaphores(&[upload.release_semaphore])
+                    .wait_dst_stage_mask(&[vk::PipelineStageFlags::TRANSFER])
+                    .build();
+                self.core
+                    .dev
+                    .queue_submit(self.core.transfer_queue, &[transfer_submit], upload.fence)
+                    .map_err(|e| gpu_api_err!("vulkan upload transfer submit {}", e))?;
+            }
+        }
+
+        let id = self.next_upload_id;
+        self.next_upload_id += 1;
+        self.uploads.insert(id, upload);
+        Ok(UploadId::from_id(id))
+    }
+}
+
+pub struct VkAsyncUploadAcquire {
+    pub command_buffer: vk::CommandBuffer,
+    semaphore: vk::Semaphore,
+}
+
+pub struct VkAsyncUpload {
+    pub staging: VkBuffer,
+    //  Runs on the graphics queue before the transfer.
+    pub release_command_buffer: vk::CommandBuffer,
+    release_semaphore: vk::Semaphore,
+    pub transfer_command_buffer: vk::CommandBuffer,
+    //  Only present when the transfer queue family differs from the graphics queue family.
+    pub acquire: Option<VkAsyncUploadAcquire>,
+
+    fence: vk::Fence,
+    graphics_queue_family: u32,
+    transfer_queue_family: u32,
+    graphics_command_pool: vk::CommandPool,
+    transfer_command_pool: vk::CommandPool,
+
+    drop_queue_ref: VkDropQueueRef,
+}
+
+impl VkAsyncUpload {
+    fn queue_families(&self) -> (u32, u32) {
+        if self.acquire.is_some() {
+            (self.transfer_queue_family, self.graphics_queue_family)
+        } else {
+            (vk::QUEUE_FAMILY_IGNORED, vk::QUEUE_FAMILY_IGNORED)
+        }
+    }
+
+    /// Hands `buffer` over from the graphics queue family to the transfer queue family,
+    /// keeping its contents.
+    pub unsafe fn cmd_begin_buffer(&self, dev: &Device, buffer: vk::Buffer) {
+        if self.acquire.is_none() {
+            return;
+        }
+        let barrier = vk::BufferMemoryBarrier::builder()
+            .buffer(buffer)
+            .size(vk::WHOLE_SIZE)
+            .src_queue_family_index(self.graphics_queue_family)
+            .dst_queue_family_index(self.transfer_queue_family)
+            .build();
+
+        let mut release_barrier = barrier;
+        release_barrier.src_access_mask = vk::AccessFlags::MEMORY_WRITE;
+        release_barrier.dst_access_mask = vk::AccessFlags::empty();
+        dev.cmd_pipeline_barrier(
+            self.release_command_buffer,
+            vk::PipelineStageFlags::ALL_COMMANDS,
+            vk::PipelineStageFlags::BOTTOM_OF_PIPE,
+            vk::DependencyFlags::empty(),
+            &[],
+            &[release_barrier],
+            &[],
+        );
+
+        let mut acquire_barrier = barrier;
+        acquire_barrier.src_access_mask = vk::AccessFlags::empty();
+        acquire_barrier.dst_access_mask = vk::AccessFlags::TRANSFER_WRITE;
+        dev.cmd_pipeline_barrier(
+            self.transfer_command_buffer,
+            vk::PipelineStageFlags::TRANSFER,
+            vk::PipelineStageFlags::TRANSFER,
+            vk::DependencyFlags::empty(),
+            &[],
+            &[acquire_barrier],
+            &[],
+        );
+    }
+
+    /// Transitions `image` from `TRANSFER_DST_OPTIMAL` to `SHADER_READ_ONLY_OPTIMAL`,
+    /// handing ownership over to the graphics queue family when required.
+    pub unsafe fn cmd_finish_image(
+        &self,
+        dev: &Device,
+        image: vk::Image,
+        range: vk::ImageSubresourceRange,
+    ) {
+        let (src_queue_family, dst_queue_family) = self.queue_families();
+        let barrier = vk::ImageMemoryBarrier::builder()
+            .old_layout(vk::ImageLayout::TRANSFER_DST_OPTIMAL)
+            .new_layout(vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL)
+            .image(image)
+            .subresource_range(range)
+            .src_queue_family_index(src_queue_family)
+            .dst_queue_family_index(dst_queue_family)
+            .build();
+
+        if let Some(acquire) = &self.acquire {
+            let mut release_barrier = barrier;
+            release_barrier.src_access_mask = vk::AccessFlags::TRANSFER_WRITE;
+            release_barrier.dst_access_mask = vk::AccessFlags::empty();
+            dev.cmd_pipeline_barrier(
+                self.transfer_command_buffer,
+                vk::PipelineStageFlags::TRANSFER,
+                vk::PipelineStageFlags::BOTTOM_OF_PIPE,
+                vk::DependencyFlags::empty(),
+                &[],
+                &[],
+                &[release_barrier],
+            );
+
+            let mut acquire_barrier = barrier;
+            acquire_barrier.src_access_mask = vk::AccessFlags::empty();
+            acquire_barrier.dst_access_mask = vk::AccessFlags::SHADER_READ;
+            dev.cmd_pipeline_barrier(
+                acquire.command_buffer,
+                vk::PipelineStageFlags::TOP_OF_PIPE,
+                vk::PipelineStageFlags::FRAGMENT_SHADER,
+                vk::DependencyFlags::empty(),
+                &[],
+                &[],
+                &[acquire_barrier],
+            );
+        } else {
+            let mut use_barrier = barrier;
+            use_barrier.src_access_mask = vk::AccessFlags::TRANSFER_WRITE;
+            use_barrier.dst_access_mask = vk::AccessFlags::SHADER_READ;
+            dev.cmd_pipeline_barrier(
+                self.transfer_command_buffer,
+                vk::PipelineStageFlags::TRANSFER,
+                vk::PipelineStageFlags::FRAGMENT_SHADER,
+                vk::DependencyFlags::empty(),
+                &[],
+                &[],
+                &[use_barrier],
+            );
+        }
+    }
+
+    /// Makes the transfer into `buffer` visible to the graphics queue family,
+    /// handing ownership over when required.
+    pub unsafe fn cmd_finish_buffer(&self, dev: &Device, buffer: vk::Buffer) {
+        let (src_queue_family, dst_queue_family) = self.queue_families();
+        let read_access = vk::AccessFlags::INDEX_READ
+            | vk::AccessFlags::VERTEX_ATTRIBUTE_READ
+            | vk::AccessFlags::UNIFORM_READ
+            | vk::AccessFlags::SHADER_READ;
+        let barrier = vk::BufferMemoryBarrier::builder()
+            .buffer(buffer)
+            .size(vk::WHOLE_SIZE)
+            .src_queue_family_index(src_queue_family)
+            .dst_queue_family_index(dst_queue_family)
+            .build();
+
+        if let Some(acquire) = &self.acquire {
+            let mut release_barrier = barrier;
+            release_barrier.src_access_mask = vk::AccessFlags::TRANSFER_WRITE;
+            release_barrier.dst_access_mask = vk::AccessFlags::empty();
+            dev.cmd_pipeline_barrier(
+                self.transfer_command_buffer,
+                vk::PipelineStageFlags::TRANSFER,
+                vk::PipelineStageFlags::BOTTOM_OF_PIPE,
+                vk::DependencyFlags::empty(),
+                &[],
+                &[release_barrier],
+                &[],
+            );
+
+            let mut acquire_barrier = barrier;
+            acquire_barrier.src_access_mask = vk::AccessFlags::empty();
+            acquire_barrier.dst_access_mask = read_access;
+            dev.cmd_pipeline_barrier(
+                acquire.command_buffer,
+                vk::PipelineStageFlags::TOP_OF_PIPE,
+                vk::PipelineStageFlags::ALL_COMMANDS,
+                vk::DependencyFlags::empty(),
+                &[],
+                &[acquire_barrier],
+                &[],
+            );
+        } else {
+            let mut use_barrier = barrier;
+            use_barrier.src_access_mask = vk::AccessFlags::TRANSFER_WRITE;
+            use_barrier.dst_access_mask = read_access;
+            dev.cmd_pipeline_barrier(
+                self.transfer_command_buffer,
+                vk::PipelineStageFlags::TRANSFER,
+                vk::PipelineStageFlags::ALL_COMMANDS,
+                vk::DependencyFlags::empty(),
+                &[],
+                &[use_barrier],
+                &[],
+            );
+        }
+    }
+}
+
+impl Drop for VkAsyncUpload {
+    fn drop(&mut self) {
+        let fence = self.fence;
+        let release_command_buffer = self.release_command_buffer;
+        let release_semaphore = self.release_semaphore;
+        let transfer_command_buffer = self.transfer_command_buffer;
+        let transfer_command_pool = self.transfer_command_pool;
+        let graphics_command_pool = self.graphics_command_pool;
+        let acquire = self
+            .acquire
+            .as_ref()
+            .map(|acquire| (acquire.command_buffer, acquire.semaphore));
+
+        self.drop_queue_ref
+            .lock()
+            .unwrap()
+            .push(Box::new(move |dev, _| unsafe {
+                dev.destroy_fence(fence, None);
+                dev.free_command_buffers(graphics_command_pool, &[release_command_buffer]);
+                dev.destroy_semaphore(release_semaphore, None);
+                dev.free_command_buffers(transfer_command_pool, &[transfer_command_buffer]);
+                if let Some((command_buffer, semaphore)) = acquire {
+                    dev.free_command_buffers(graphics_command_pool, &[command_buffer]);
+                    dev.destroy_semaphore(semaphore, None);
+                }
+            }));
+    }
+}
+
+fn allocate_command_buffer(
+    dev: &Device,
+    command_pool: vk::CommandPool,
+) -> GResult<vk::CommandBuffer> {
+    let command_buffer_alloc = vk::CommandBufferAllocateInfo::builder()
+        .command_pool(command_pool)
+        .command_buffer_count(1)
+        .build();
+    Ok(unsafe {
+        dev.allocate_command_buffers(&command_buffer_alloc)
+            .map_err(|e| gpu_api_err!("vulkan upload new command buffer {}", e))?[0]
+    })
+}
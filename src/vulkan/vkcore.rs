@@ -8,12 +8,18 @@ pub struct VkCore {
     pub dev: Device,
 
     pub physical_dev_properties: vk::PhysicalDeviceProperties,
+    pub enabled_features: vk::PhysicalDeviceFeatures,
 
     pub graphics_queue: vk::Queue,
     pub compute_queue: vk::Queue,
+    pub transfer_queue: vk::Queue,
+
+    pub graphics_queue_family: u32,
+    pub transfer_queue_family: u32,
 
     pub graphics_command_pool: vk::CommandPool,
     pub compute_command_pool: vk::CommandPool,
+    pub transfer_command_pool: vk::CommandPool,
 
     pub misc_fence: vk::Fence,
     pub misc_command_buffer: vk::CommandBuffer,
@@ -192,7 +198,15 @@ impl VkCore {
             unsafe { instance.get_physical_device_properties(physical_dev) };
 
         //  # Make Device
-        let features = vk::PhysicalDeviceFeatures::default();
+        //  Texture compression and anisotropy are enabled whenever they're available.
+        let supported_features = unsafe { instance.get_physical_device_features(physical_dev) };
+        let features = vk::PhysicalDeviceFeatures {
+            sampler_anisotropy: supported_features.sampler_anisotropy,
+            texture_compression_bc: supported_features.texture_compression_bc,
+            texture_compression_etc2: supported_features.texture_compression_etc2,
+            texture_compression_astc_ldr: supported_features.texture_compression_astc_ldr,
+            ..Default::default()
+        };
         let dev_storage_buffer_ext = CString::new("VK_KHR_storage_buffer_storage_class").unwrap();
         let mut dev_extensions_owned = vec![];
         if config.use_ssbo {
@@ -205,18 +219,23 @@ impl VkCore {
             dev_extensions_owned.iter().map(|s| s.as_ptr()).collect();
 
         //  ## The Lazy Way of Creating Queues
-        let mut queues_create = vec![vk::DeviceQueueCreateInfo::builder()
-            .queue_priorities(&[1.0])
-            .queue_family_index(queue_families.graphics)
-            .build()];
-        if queue_families.graphics != queue_families.compute {
-            queues_create.push(
+        let mut unique_queue_families = vec![queue_families.graphics];
+        [queue_families.compute, queue_families.transfer]
+            .into_iter()
+            .for_each(|family| {
+                if !unique_queue_families.contains(&family) {
+                    unique_queue_families.push(family);
+                }
+            });
+        let queues_create = unique_queue_families
+            .into_iter()
+            .map(|family| {
                 vk::DeviceQueueCreateInfo::builder()
                     .queue_priorities(&[1.0])
-                    .queue_family_index(queue_families.compute)
-                    .build(),
-            )
-        }
+                    .queue_family_index(family)
+                    .build()
+            })
+            .collect::<Vec<_>>();
 
         //  ## Actually Make It
         let dev_create = vk::DeviceCreateInfo::builder()
@@ -231,6 +250,7 @@ impl VkCore {
         //  # Get Queues
         let graphics_queue = unsafe { dev.get_device_queue(queue_families.graphics, 0) };
         let compute_queue = unsafe { dev.get_device_queue(queue_families.compute, 0) };
+        let transfer_queue = unsafe { dev.get_device_queue(queue_families.transfer, 0) };
 
         //  # Create Command Pools
         let graphics_command_pool_create = vk::CommandPoolCreateInfo::builder()
@@ -243,12 +263,20 @@ impl VkCore {
             .queue_family_index(queue_families.compute)
             .build();
 
+        let transfer_command_pool_create = vk::CommandPoolCreateInfo::builder()
+            .flags(vk::CommandPoolCreateFlags::RESET_COMMAND_BUFFER)
+            .queue_family_index(queue_families.transfer)
+            .build();
+
         let graphics_command_pool =
             unsafe { dev.create_command_pool(&graphics_command_pool_create, None) }
                 .map_err(|e| gpu_api_err!("vulkan graphics command pool init {}", e))?;
         let compute_command_pool =
             unsafe { dev.create_command_pool(&compute_command_pool_create, None) }
                 .map_err(|e| gpu_api_err!("vulkan compute command pool init {}", e))?;
+        let transfer_command_pool =
+            unsafe { dev.create_command_pool(&transfer_command_pool_create, None) }
+                .map_err(|e| gpu_api_err!("vulkan transfer command pool init {}", e))?;
 
         //  # Create Single Use Fence and Command Buffer
         let misc_fence = new_fence(&dev, false)?;
@@ -267,18 +295,35 @@ impl VkCore {
             instance,
             physical_dev,
             physical_dev_properties,
+            enabled_features: features,
             dev,
             debug,
             entry,
             graphics_queue,
             compute_queue,
+            transfer_queue,
+            graphics_queue_family: queue_families.graphics,
+            transfer_queue_family: queue_families.transfer,
             graphics_command_pool,
             compute_command_pool,
+            transfer_command_pool,
             misc_fence,
             misc_command_buffer,
         })
     }
 
+    pub fn has_format_features(
+        &self,
+        format: vk::Format,
+        features: vk::FormatFeatureFlags,
+    ) -> bool {
+        let properties = unsafe {
+            self.instance
+                .get_physical_device_format_properties(self.physical_dev, format)
+        };
+        properties.optimal_tiling_features.contains(features)
+    }
+
     pub fn misc_command(&self) -> GResult<VkMiscCommand> {
         self.begin_misc_cmd()?;
         Ok(VkMiscCommand { vkcore: self })
@@ -335,6 +380,8 @@ impl Drop for VkCore {
                 .destroy_command_pool(self.graphics_command_pool, None);
             self.dev
                 .destroy_command_pool(self.compute_command_pool, None);
+            self.dev
+                .destroy_command_pool(self.transfer_command_pool, None);
 
             self.dev.destroy_device(None);
 
@@ -351,6 +398,7 @@ impl Drop for VkCore {
 struct QueueFamilies {
     pub graphics: u32,
     pub compute: u32,
+    pub transfer: u32,
 }
 
 impl QueueFamilies {
@@ -371,10 +419,33 @@ impl QueueFamilies {
             res[0].0 as u32
         }
 
+        //  Prefer a transfer only queue family (usually backed by dma hardware),
+        //  then one that at least can't do graphics.
+        fn find_dedicated_transfer_queue_family(
+            queue_families: &[vk::QueueFamilyProperties],
+        ) -> Option<u32> {
+            [
+                vk::QueueFlags::GRAPHICS | vk::QueueFlags::COMPUTE,
+                vk::QueueFlags::GRAPHICS,
+            ]
+            .into_iter()
+            .find_map(|excluded| {
+                queue_families
+                    .iter()
+                    .position(|prop| {
+                        prop.queue_flags.contains(vk::QueueFlags::TRANSFER)
+                            && !prop.queue_flags.intersects(excluded)
+                    })
+                    .map(|idx| idx as u32)
+            })
+        }
+
         //  Find the best graphics queue family
+        let graphics = find_beefiest_queue_family_for(&queue_families, vk::QueueFlags::GRAPHICS);
         Some(QueueFamilies {
-            graphics: find_beefiest_queue_family_for(&queue_families, vk::QueueFlags::GRAPHICS),
+            graphics,
             compute: find_beefiest_queue_family_for(&queue_families, vk::QueueFlags::COMPUTE),
+            transfer: find_dedicated_transfer_queue_family(&queue_families).unwrap_or(graphics),
         })
     }
 }
@@ -8,25 +8,269 @@ impl WebGpuContext {
         attachment_usage: AttachmentImageUsage,
         ext: Option<NewAttachmentImageExt>,
     ) -> GResult<AttachmentImageId> {
+        let mut ext = ext.unwrap_or_default();
+        if ext.enable_cubemap.is_some() && ext.array_layers.is_some() {
+            Err(gpu_api_err!(
+                "webgpu attachment image cubemaps cannot have array layers"
+            ))?;
+        }
+        if ext.array_layers == Some(0) {
+            Err(gpu_api_err!(
+                "webgpu attachment image arrays need at least one layer"
+            ))?;
+        }
+        let max_mip_levels = std::cmp::max(initial_width, initial_height).max(1).ilog2() + 1;
+        if let Some(mip_levels) = ext.mip_levels {
+            if mip_levels == 0 || mip_levels > max_mip_levels {
+                Err(gpu_api_err!(
+                    "webgpu attachment image mip levels {} must be between 1 and {}",
+                    mip_levels,
+                    max_mip_levels
+                ))?;
+            }
+        }
+        if attachment_usage == AttachmentImageUsage::DepthAttachment {
+            let depth_format = ext
+                .depth_format
+                .unwrap_or(AttachmentImageDepthFormat::D24UNormS8UInt);
+            let features = self.device.features();
+            let supported_depth_format = depth_format
+                .fallbacks()
+                .iter()
+                .find(|&&fallback| {
+                    fallback != AttachmentImageDepthFormat::D32SFloatS8UInt
+                        || features.has(WEBGPU_DEPTH32FLOAT_STENCIL8_FEATURE)
+                })
+                .ok_or(gpu_api_err!(
+                    "webgpu no supported depth format could replace {:?}",
+                    depth_format
+                ))?;
+            ext.depth_format = Some(*supported_depth_format);
+        }
+
+        let format = get_format(attachment_usage, &ext);
+        if ext.enable_storage.is_some() && !is_storage_format(format) {
+            Err(gpu_api_err!(
+                "webgpu storage attachment image format {:?} is not a storage texture format",
+                format
+            ))?;
+        }
+
         let attachment_image = WebGpuAttachmentImage::new(
             &self.device,
             initial_width,
             initial_height,
             attachment_usage,
-            ext,
+            Some(ext),
         );
         self.attachment_images.push(attachment_image);
 
         Ok(AttachmentImageId::from_id(self.attachment_images.len() - 1))
     }
+
+    pub fn get_attachment_image_depth_format(
+        &self,
+        image: AttachmentImageId,
+    ) -> GResult<AttachmentImageDepthFormat> {
+        let attachment_image = self.attachment_images.get(image.id()).ok_or(gpu_api_err!(
+            "webgpu get attachment image depth format id {:?} does not exist",
+            image
+        ))?;
+        attachment_image.get_depth_format().ok_or(gpu_api_err!(
+            "webgpu get attachment image depth format id {:?} is not a depth attachment",
+            image
+        ))
+    }
+
+    pub fn read_synced_attachment_image(
+        &self,
+        _image: AttachmentImageId,
+        _ext: Option<ReadSyncedAttachmentImageExt>,
+    ) -> GResult<Vec<u8>> {
+        Err(gpu_api_err!(
+            "webgpu does not support this operation, please use async_read_synced_attachment_image instead"
+        ))
+    }
+
+    pub async fn async_read_synced_attachment_image(
+        &self,
+        image: AttachmentImageId,
+        _ext: Option<ReadSyncedAttachmentImageExt>,
+    ) -> GResult<Vec<u8>> {
+        let attachment_image = self.attachment_images.get(image.id()).ok_or(gpu_api_err!(
+            "webgpu read synced attachment image id {:?} does not exist",
+            image
+        ))?;
+        let readable_buffer = attachment_image
+            .readable_buffer
+            .as_ref()
+            .ok_or(gpu_api_err!(
+                "webgpu read synced attachment image id {:?} was not synced with Submit::sync_attachment_image",
+                image
+            ))?;
+
+        JsFuture::from(readable_buffer.map_async(GpuMapModeFlags::Read as u32))
+            .await
+            .map_err(|e| {
+                gpu_api_err!(
+                    "webgpu failed to map buffer in async_read_synced_attachment_image: {:?}",
+                    e
+                )
+            })?;
+
+        let mapped_buf = readable_buffer.get_mapped_range();
+        let padded_data = Uint8Array::new(&mapped_buf).to_vec();
+        readable_buffer.unmap();
+
+        let row_size = attachment_image.row_byte_size();
+        Ok(padded_data
+            .chunks(padded_row_byte_size(row_size))
+            .flat_map(|row| &row[..row_size])
+            .copied()
+            .collect())
+    }
+}
+
+pub fn submit_attachment_image_copy_backs(
+    context: &mut WebGpuContext,
+    submit: &Submit,
+    command_encoder: &GpuCommandEncoder,
+) -> GResult<()> {
+    submit
+        .attachment_image_copy_backs
+        .iter()
+        .try_for_each(|image| {
+            let attachment_image =
+                context
+                    .attachment_images
+                    .get_mut(image.id())
+                    .ok_or(gpu_api_err!(
+                        "webgpu attachment image sync id {:?} does not exist",
+                        image
+                    ))?;
+            if attachment_image.is_multisampled() {
+                Err(gpu_api_err!(
+                    "webgpu attachment image sync id {:?} cannot be multisampled",
+                    image
+                ))?;
+            }
+            //  Only the depth aspect of D16, D32 and D32S8 can be copied into a buffer.
+            if matches!(
+                attachment_image.format,
+                GpuTextureFormat::Depth24plusStencil8 | GpuTextureFormat::Stencil8
+            ) {
+                Err(gpu_api_err!(
+                    "webgpu attachment image sync id {:?} uses {:?} which cannot be copied",
+                    image,
+                    attachment_image.format
+                ))?;
+            }
+
+            let padded_row_size = padded_row_byte_size(attachment_image.row_byte_size());
+            let buffer_size = padded_row_size * attachment_image.height;
+            let device = &context.device;
+            let readable_buffer = attachment_image.readable_buffer.get_or_insert_with(|| {
+                device.create_buffer(&GpuBufferDescriptor::new(
+                    buffer_size as f64,
+                    GpuBufferUsageFlags::CopyDst as u32 | GpuBufferUsageFlags::MapRead as u32,
+                ))
+            });
+
+            let mut source = GpuImageCopyTexture::new(&attachment_image.texture);
+            if attachment_image.attachment_usage == AttachmentImageUsage::DepthAttachment {
+                source.aspect(GpuTextureAspect::DepthOnly);
+            }
+            let mut destination = GpuImageCopyBuffer::new(readable_buffer);
+            destination.bytes_per_row(padded_row_size as u32);
+
+            let size = Array::new();
+            size.push(&JsValue::from(attachment_image.width));
+            size.push(&JsValue::from(attachment_image.height));
+
+            command_encoder.copy_texture_to_buffer_with_u32_sequence(&source, &destination, &size);
+            Ok(())
+        })
+}
+
+fn get_format(
+    attachment_usage: AttachmentImageUsage,
+    ext: &NewAttachmentImageExt,
+) -> GpuTextureFormat {
+    match attachment_usage {
+        AttachmentImageUsage::ColorAttachment => ext
+            .color_format
+            .map(|color_format| match color_format {
+                AttachmentImageColorFormat::R8UNorm => GpuTextureFormat::R8unorm,
+                AttachmentImageColorFormat::R8G8UNorm => GpuTextureFormat::Rg8unorm,
+                AttachmentImageColorFormat::R8G8B8A8UNorm => GpuTextureFormat::Rgba8unorm,
+                AttachmentImageColorFormat::R16G16B16A16SFloat => GpuTextureFormat::Rgba16float,
+                AttachmentImageColorFormat::R32SFloat => GpuTextureFormat::R32float,
+                AttachmentImageColorFormat::R32G32SFloat => GpuTextureFormat::Rg32float,
+                AttachmentImageColorFormat::R32G32B32A32SFloat => GpuTextureFormat::Rgba32float,
+            })
+            .unwrap_or(WEBGPU_COLOR_ATTACHMENT_FORMAT),
+        AttachmentImageUsage::DepthAttachment => ext
+            .depth_format
+            .map(|depth_format| match depth_format {
+                AttachmentImageDepthFormat::D16UNorm => GpuTextureFormat::Depth16unorm,
+                AttachmentImageDepthFormat::D24UNormS8UInt => GpuTextureFormat::Depth24plusStencil8,
+                AttachmentImageDepthFormat::D32SFloat => GpuTextureFormat::Depth32float,
+                AttachmentImageDepthFormat::D32SFloatS8UInt => {
+                    GpuTextureFormat::Depth32floatStencil8
+                }
+                AttachmentImageDepthFormat::S8UInt => GpuTextureFormat::Stencil8,
+            })
+            .unwrap_or(WEBGPU_DEPTH_ATTACHMENT_FORMAT),
+    }
+}
+
+fn get_array_layers(ext: &NewAttachmentImageExt) -> u32 {
+    if ext.enable_cubemap.is_some() {
+        6
+    } else {
+        ext.array_layers.unwrap_or(1)
+    }
+}
+
+fn get_view_dimension(ext: &NewAttachmentImageExt) -> GpuTextureViewDimension {
+    if ext.enable_cubemap.is_some() {
+        GpuTextureViewDimension::Cube
+    } else if ext.array_layers.is_some() {
+        GpuTextureViewDimension::N2dArray
+    } else {
+        GpuTextureViewDimension::N2d
+    }
+}
+
+fn get_storage_view_dimension(ext: &NewAttachmentImageExt) -> GpuTextureViewDimension {
+    if ext.enable_cubemap.is_some() || ext.array_layers.is_some() {
+        GpuTextureViewDimension::N2dArray
+    } else {
+        GpuTextureViewDimension::N2d
+    }
+}
+
+//  Copies into buffers require rows to be aligned to 256 bytes.
+fn padded_row_byte_size(row_size: usize) -> usize {
+    row_size.next_multiple_of(256)
 }
 
 pub struct WebGpuAttachmentImage {
     attachment_usage: AttachmentImageUsage,
     ext: NewAttachmentImageExt,
+    width: usize,
+    height: usize,
+    //  Created by the first `Submit::sync_attachment_image`.
+    readable_buffer: Option<GpuBuffer>,
 
     texture: GpuTexture,
     pub texture_view: GpuTextureView,
+    //  One 2D view per layer and mip level, only for layered or mipmapped images.
+    subresource_views: Vec<GpuTextureView>,
+    //  Only for attachment images created with `NewAttachmentImageExt::enable_storage`.
+    storage_texture_view: Option<GpuTextureView>,
+    //  Depth-stencil textures can only be sampled through a depth-only or stencil-only view.
+    pub sampled_texture_view: GpuTextureView,
     pub format: GpuTextureFormat,
 }
 
@@ -40,29 +284,28 @@ impl WebGpuAttachmentImage {
     ) -> Self {
         let ext = ext.unwrap_or_default();
 
-        let format = match attachment_usage {
-            AttachmentImageUsage::ColorAttachment => ext
-                .color_format
-                .map(|color_format| match color_format {
-                    AttachmentImageColorFormat::R8UNorm => GpuTextureFormat::R8unorm,
-                    AttachmentImageColorFormat::R8G8UNorm => GpuTextureFormat::Rg8unorm,
-                    AttachmentImageColorFormat::R8G8B8A8UNorm => GpuTextureFormat::Rgba8snorm,
-                    AttachmentImageColorFormat::R32SFloat => GpuTextureFormat::R32float,
-                    AttachmentImageColorFormat::R32G32SFloat => GpuTextureFormat::Rg32float,
-                    AttachmentImageColorFormat::R32G32B32A32SFloat => GpuTextureFormat::Rgba32float,
-                })
-                .unwrap_or(WEBGPU_COLOR_ATTACHMENT_FORMAT),
-            AttachmentImageUsage::DepthAttachment => WEBGPU_DEPTH_ATTACHMENT_FORMAT,
-        };
+        let format = get_format(attachment_usage, &ext);
 
         let size = Array::new();
         size.push(&JsValue::from(initial_width));
         size.push(&JsValue::from(initial_height));
 
-        let usage = GpuTextureUsageFlags::RenderAttachment as u32
-            | GpuTextureUsageFlags::TextureBinding as u32;
+        let array_layers = get_array_layers(&ext);
+        let mip_levels = ext.mip_levels.unwrap_or(1);
+        if ext.enable_cubemap.is_some() || ext.array_layers.is_some() {
+            size.push(&JsValue::from(array_layers));
+        }
+
+        let mut usage = GpuTextureUsageFlags::RenderAttachment as u32
+            | GpuTextureUsageFlags::TextureBinding as u32
+            | GpuTextureUsageFlags::CopySrc as u32
+            | GpuTextureUsageFlags::CopyDst as u32;
+        if ext.enable_storage.is_some() {
+            usage |= GpuTextureUsageFlags::StorageBinding as u32;
+        }
 
         let mut texture_info = GpuTextureDescriptor::new(format, &size, usage);
+        texture_info.mip_level_count(mip_levels);
         texture_info.sample_count(match ext.msaa_samples.unwrap_or_default() {
             MsaaSampleCount::Sample1 => 1,
             MsaaSampleCount::Sample2 => 2,
@@ -74,19 +317,73 @@ impl WebGpuAttachmentImage {
         });
 
         let texture = device.create_texture(&texture_info);
-        let texture_view = texture.create_view();
+        let view_dimension = get_view_dimension(&ext);
+
+        let mut texture_view_desc = GpuTextureViewDescriptor::new();
+        texture_view_desc.dimension(view_dimension);
+        let texture_view = texture.create_view_with_descriptor(&texture_view_desc);
+
+        let mut sampled_view_desc = GpuTextureViewDescriptor::new();
+        sampled_view_desc.dimension(view_dimension);
+        if attachment_usage == AttachmentImageUsage::DepthAttachment {
+            sampled_view_desc.aspect(
+                if ext.depth_format == Some(AttachmentImageDepthFormat::S8UInt) {
+                    GpuTextureAspect::StencilOnly
+                } else {
+                    GpuTextureAspect::DepthOnly
+                },
+            );
+        }
+        let sampled_texture_view = texture.create_view_with_descriptor(&sampled_view_desc);
+
+        //  Storage textures can only be bound with a single mip level, and never as cubemaps.
+        let storage_texture_view = ext.enable_storage.map(|_| {
+            let mut storage_view_desc = GpuTextureViewDescriptor::new();
+            storage_view_desc
+                .dimension(get_storage_view_dimension(&ext))
+                .mip_level_count(1);
+            texture.create_view_with_descriptor(&storage_view_desc)
+        });
+
+        let subresource_views = if view_dimension != GpuTextureViewDimension::N2d || mip_levels > 1
+        {
+            (0..array_layers)
+                .flat_map(|layer| (0..mip_levels).map(move |mip_level| (layer, mip_level)))
+                .map(|(layer, mip_level)| {
+                    let mut view_desc = GpuTextureViewDescriptor::new();
+                    view_desc
+                        .dimension(GpuTextureViewDimension::N2d)
+                        .base_array_layer(layer)
+                        .array_layer_count(1)
+                        .base_mip_level(mip_level)
+                        .mip_level_count(1);
+                    texture.create_view_with_descriptor(&view_desc)
+                })
+                .collect()
+        } else {
+            vec![]
+        };
 
         WebGpuAttachmentImage {
             ext,
+            width: initial_width,
+            height: initial_height,
+            readable_buffer: None,
             attachment_usage,
             texture,
             texture_view,
+            subresource_views,
+            storage_texture_view,
+            sampled_texture_view,
             format,
         }
     }
 
     pub fn recreate_with_new_size(&mut self, device: &GpuDevice, width: usize, height: usize) {
         self.texture.destroy();
+        if let Some(readable_buffer) = &self.readable_buffer {
+            readable_buffer.destroy();
+        }
         let new_attachment_image = WebGpuAttachmentImage::new(
             device,
             width,
@@ -96,4 +393,133 @@ impl WebGpuAttachmentImage {
         );
         *self = new_attachment_image;
     }
+
+    pub fn get_render_view(
+        &self,
+        subresource: AttachmentImageSubresource,
+    ) -> GResult<GpuTextureView> {
+        let array_layers = get_array_layers(&self.ext);
+        let mip_levels = self.ext.mip_levels.unwrap_or(1);
+        if subresource.layer >= array_layers || subresource.mip_level >= mip_levels {
+            Err(gpu_api_err!(
+                "webgpu attachment image subresource {:?} is out of range for {} layers and {} mip levels",
+                subresource,
+                array_layers,
+                mip_levels
+            ))?;
+        }
+        Ok(if self.subresource_views.is_empty() {
+            self.texture_view.clone()
+        } else {
+            self.subresource_views
+                [(subresource.layer * mip_levels + subresource.mip_level) as usize]
+                .clone()
+        })
+    }
+
+    pub fn get_view_dimension(&self) -> GpuTextureViewDimension {
+        get_view_dimension(&self.ext)
+    }
+
+    pub fn get_storage_view_dimension(&self) -> GpuTextureViewDimension {
+        get_storage_view_dimension(&self.ext)
+    }
+
+    pub fn get_storage_texture_view(&self, format: GpuTextureFormat) -> GResult<&GpuTextureView> {
+        if self.is_multisampled() {
+            Err(gpu_api_err!(
+                "webgpu storage attachment image cannot be multisampled"
+            ))?;
+        }
+        if self.format != format {
+            Err(gpu_api_err!(
+                "webgpu storage attachment image has format {:?}, not {:?}",
+                self.format,
+                format
+            ))?;
+        }
+        self.storage_texture_view.as_ref().ok_or(gpu_api_err!(
+            "webgpu storage attachment image was not created with enable_storage"
+        ))
+    }
+
+    pub fn transfer_texture(&self) -> WebGpuTransferTexture<'_> {
+        WebGpuTransferTexture {
+            texture: &self.texture,
+            format: self.format,
+            width: self.width,
+            height: self.height,
+            depth: None,
+            mip_levels: self.ext.mip_levels.unwrap_or(1),
+            array_layers: get_array_layers(&self.ext),
+            is_multisampled: self.is_multisampled(),
+            buffer_aspect: match self.format {
+                GpuTextureFormat::Stencil8 => GpuTextureAspect::StencilOnly,
+                GpuTextureFormat::Depth16unorm
+                | GpuTextureFormat::Depth24plusStencil8
+                | GpuTextureFormat::Depth32float
+                | GpuTextureFormat::Depth32floatStencil8 => GpuTextureAspect::DepthOnly,
+                _ => GpuTextureAspect::All,
+            },
+            block_dimensions: (1, 1),
+            block_byte_size: self.texel_byte_size(),
+        }
+    }
+
+    pub fn get_depth_format(&self) -> Option<AttachmentImageDepthFormat> {
+        match self.attachment_usage {
+            AttachmentImageUsage::ColorAttachment => None,
+            AttachmentImageUsage::DepthAttachment => Some(
+                self.ext
+                    .depth_format
+                    .unwrap_or(AttachmentImageDepthFormat::D24UNormS8UInt),
+            ),
+        }
+    }
+
+    pub fn is_surface_relative(&self) -> bool {
+        self.ext.fixed_size.is_none()
+    }
+
+    pub fn is_multisampled(&self) -> bool {
+        self.msaa_samples() != MsaaSampleCount::Sample1
+    }
+
+    pub fn msaa_samples(&self) -> MsaaSampleCount {
+        self.ext.msaa_samples.unwrap_or_default()
+    }
+
+    pub fn get_sample_type(&self) -> GpuTextureSampleType {
+        match self.format {
+            GpuTextureFormat::Depth16unorm
+            | GpuTextureFormat::Depth24plusStencil8
+            | GpuTextureFormat::Depth32float
+            | GpuTextureFormat::Depth32floatStencil8 => GpuTextureSampleType::Depth,
+            GpuTextureFormat::Stencil8 => GpuTextureSampleType::Uint,
+            GpuTextureFormat::R32float
+            | GpuTextureFormat::Rg32float
+            | GpuTextureFormat::Rgba32float => GpuTextureSampleType::UnfilterableFloat,
+            _ => GpuTextureSampleType::Float,
+        }
+    }
+
+    fn row_byte_size(&self) -> usize {
+        self.width * self.texel_byte_size()
+    }
+
+    //  For depth-stencil formats, this is the size of the copyable aspect.
+    fn texel_byte_size(&self) -> usize {
+        match self.format {
+            GpuTextureFormat::R8unorm | GpuTextureFormat::Stencil8 => 1,
+            GpuTextureFormat::Rg8unorm | GpuTextureFormat::Depth16unorm => 2,
+            GpuTextureFormat::Rgba8unorm
+            | GpuTextureFormat::R32float
+            | GpuTextureFormat::Depth24plusStencil8
+            | GpuTextureFormat::Depth32float
+            | GpuTextureFormat::Depth32floatStencil8 => 4,
+            GpuTextureFormat::Rg32float | GpuTextureFormat::Rgba16float => 8,
+            GpuTextureFormat::Rgba32float => 16,
+            _ => unreachable!(),
+        }
+    }
 }
@@ -26,12 +26,13 @@ impl WebGpuBindGroups {
         } else {
             0
         };
-        uniforms.iter().for_each(|uniform| {
+        uniforms.iter().try_for_each(|uniform| {
             let mut dynamic_indices = vec![];
             let mut entry = GpuBindGroupLayoutEntry::new(uniform.binding as u32, 0);
 
             let visibility = match uniform.ty {
-                ShaderUniformType::UniformBuffer(_) => {
+                ShaderUniformType::UniformBuffer(_)
+                | ShaderUniformType::GenericUniformBuffer(_) => {
                     let mut layout = GpuBufferBindingLayout::new();
                     layout.type_(GpuBufferBindingType::Uniform);
                     entry.buffer(&layout);
@@ -50,7 +51,8 @@ impl WebGpuBindGroups {
                         | GpuShaderStageFlags::Fragment as u8
                         | GpuShaderStageFlags::Compute as u8
                 }
-                ShaderUniformType::ShaderStorageBuffer(_) => {
+                ShaderUniformType::ShaderStorageBuffer(_)
+                | ShaderUniformType::GenericShaderStorageBuffer(_) => {
                     let mut layout = GpuBufferBindingLayout::new();
                     layout.type_(GpuBufferBindingType::Storage);
                     entry.buffer(&layout);
@@ -58,7 +60,8 @@ impl WebGpuBindGroups {
                         | GpuShaderStageFlags::Fragment as u8
                         | GpuShaderStageFlags::Compute as u8
                 }
-                ShaderUniformType::ShaderStorageBufferReadOnly(_) => {
+                ShaderUniformType::ShaderStorageBufferReadOnly(_)
+                | ShaderUniformType::GenericShaderStorageBufferReadOnly(_) => {
                     let mut layout = GpuBufferBindingLayout::new();
                     layout.type_(GpuBufferBindingType::ReadOnlyStorage);
                     entry.buffer(&layout);
@@ -66,22 +69,135 @@ impl WebGpuBindGroups {
                         | GpuShaderStageFlags::Fragment as u8
                         | GpuShaderStageFlags::Compute as u8
                 }
-                ShaderUniformType::Texture(_) => {
-                    let layout = GpuTextureBindingLayout::new();
+                ShaderUniformType::Texture(texture_id) => {
+                    let mut layout = GpuTextureBindingLayout::new();
+                    if let Some(texture) = context.textures.get(texture_id.id()) {
+                        layout.sample_type(texture.get_sample_type());
+                    }
                     entry.texture(&layout);
                     GpuShaderStageFlags::Fragment as u8 | GpuShaderStageFlags::Compute as u8
                 }
-                ShaderUniformType::CubemapTexture(_) => {
+                ShaderUniformType::CubemapTexture(texture_id) => {
                     let mut layout = GpuTextureBindingLayout::new();
                     layout.view_dimension(GpuTextureViewDimension::Cube);
+                    if let Some(texture) = context.textures.get(texture_id.id()) {
+                        layout.sample_type(texture.get_sample_type());
+                    }
+                    entry.texture(&layout);
+                    GpuShaderStageFlags::Fragment as u8 | GpuShaderStageFlags::Compute as u8
+                }
+                ShaderUniformType::Texture3D(texture_id) => {
+                    let mut layout = GpuTextureBindingLayout::new();
+                    layout.view_dimension(GpuTextureViewDimension::N3d);
+                    if let Some(texture) = context.textures.get(texture_id.id()) {
+                        layout.sample_type(texture.get_sample_type());
+                    }
+                    entry.texture(&layout);
+                    GpuShaderStageFlags::Fragment as u8 | GpuShaderStageFlags::Compute as u8
+                }
+                ShaderUniformType::TextureArray(texture_id) => {
+                    let mut layout = GpuTextureBindingLayout::new();
+                    layout.view_dimension(GpuTextureViewDimension::N2dArray);
+                    if let Some(texture) = context.textures.get(texture_id.id()) {
+                        layout.sample_type(texture.get_sample_type());
+                    }
+                    entry.texture(&layout);
+                    GpuShaderStageFlags::Fragment as u8 | GpuShaderStageFlags::Compute as u8
+                }
+                ShaderUniformType::TextureView(texture_view_id) => {
+                    let mut layout = GpuTextureBindingLayout::new();
+                    if let Some(texture_view) = context.texture_views.get(texture_view_id.id()) {
+                        layout.sample_type(texture_view.sample_type);
+                        layout.view_dimension(texture_view.view_dimension);
+                    }
                     entry.texture(&layout);
                     GpuShaderStageFlags::Fragment as u8 | GpuShaderStageFlags::Compute as u8
                 }
-                ShaderUniformType::Sampler(_) => {
-                    let layout = GpuSamplerBindingLayout::new();
+                ShaderUniformType::Sampler(sampler_id) => {
+                    let mut layout = GpuSamplerBindingLayout::new();
+                    if context.sampler_cache.is_filtering(sampler_id) == Some(false) {
+                        layout.type_(GpuSamplerBindingType::NonFiltering);
+                    }
                     entry.sampler(&layout);
                     GpuShaderStageFlags::Fragment as u8 | GpuShaderStageFlags::Compute as u8
                 }
+                ShaderUniformType::ComparisonSampler(_) => {
+                    let mut layout = GpuSamplerBindingLayout::new();
+                    layout.type_(GpuSamplerBindingType::Comparison);
+                    entry.sampler(&layout);
+                    GpuShaderStageFlags::Fragment as u8 | GpuShaderStageFlags::Compute as u8
+                }
+                ShaderUniformType::AttachmentTexture(attachment_image_id) => {
+                    let mut layout = GpuTextureBindingLayout::new();
+                    if let Some(attachment_image) =
+                        context.attachment_images.get(attachment_image_id.id())
+                    {
+                        layout.sample_type(attachment_image.get_sample_type());
+                        layout.view_dimension(attachment_image.get_view_dimension());
+                    }
+                    entry.texture(&layout);
+                    GpuShaderStageFlags::Fragment as u8 | GpuShaderStageFlags::Compute as u8
+                }
+                ShaderUniformType::MultisampledAttachmentTexture(attachment_image_id) => {
+                    let mut layout = GpuTextureBindingLayout::new();
+                    layout.multisampled(true);
+                    if let Some(attachment_image) =
+                        context.attachment_images.get(attachment_image_id.id())
+                    {
+                        //  Multisampled textures cannot be filtered.
+                        layout.sample_type(match attachment_image.get_sample_type() {
+                            GpuTextureSampleType::Float => GpuTextureSampleType::UnfilterableFloat,
+                            sample_type => sample_type,
+                        });
+                    }
+                    entry.texture(&layout);
+                    GpuShaderStageFlags::Fragment as u8 | GpuShaderStageFlags::Compute as u8
+                }
+                ShaderUniformType::DepthAttachmentTexture(attachment_image_id) => {
+                    let mut layout = GpuTextureBindingLayout::new();
+                    layout.sample_type(GpuTextureSampleType::Depth);
+                    if let Some(attachment_image) =
+                        context.attachment_images.get(attachment_image_id.id())
+                    {
+                        layout.view_dimension(attachment_image.get_view_dimension());
+                    }
+                    entry.texture(&layout);
+                    GpuShaderStageFlags::Fragment as u8 | GpuShaderStageFlags::Compute as u8
+                }
+                ShaderUniformType::StorageTexture(storage_texture, access, format) => {
+                    // Core WebGpu only allows read write access for r32float.
+                    if access == ShaderStorageTextureAccess::ReadWrite
+                        && format != ShaderStorageTextureFormat::R32SFloat
+                    {
+                        Err(gpu_api_err!(
+                            "webgpu read write storage textures must use ShaderStorageTextureFormat::R32SFloat, got {:?}",
+                            format
+                        ))?;
+                    }
+                    let mut layout = GpuStorageTextureBindingLayout::new(
+                        storage_texture_format_into_webgpu(format),
+                    );
+                    layout.access(match access {
+                        ShaderStorageTextureAccess::ReadOnly => GpuStorageTextureAccess::ReadOnly,
+                        ShaderStorageTextureAccess::WriteOnly => GpuStorageTextureAccess::WriteOnly,
+                        ShaderStorageTextureAccess::ReadWrite => GpuStorageTextureAccess::ReadWrite,
+                    });
+                    let view_dimension = match storage_texture {
+                        GenericTextureId::Texture(texture_id) => context
+                            .textures
+                            .get(texture_id.id())
+                            .map(|texture| texture.storage_view_dimension),
+                        GenericTextureId::AttachmentImage(attachment_image_id) => context
+                            .attachment_images
+                            .get(attachment_image_id.id())
+                            .map(|attachment_image| attachment_image.get_storage_view_dimension()),
+                    };
+                    if let Some(view_dimension) = view_dimension {
+                        layout.view_dimension(view_dimension);
+                    }
+                    entry.storage_texture(&layout);
+                    GpuShaderStageFlags::Fragment as u8 | GpuShaderStageFlags::Compute as u8
+                }
                 ShaderUniformType::InputAttachment(_) => {
                     let mut layout = GpuTextureBindingLayout::new();
                     layout.sample_type(GpuTextureSampleType::UnfilterableFloat);
@@ -94,7 +210,9 @@ impl WebGpuBindGroups {
 
             bind_group_layouts[uniform.set].push(entry);
             bind_groups_dynamic_indices[uniform.set].append(&mut dynamic_indices);
-        });
+
+            Ok(())
+        })?;
 
         let bind_group_layouts = bind_group_layouts
             .into_iter()
@@ -143,6 +261,17 @@ impl WebGpuBindGroups {
                     let buffer = GpuBufferBinding::new(&ssbo.buffer);
                     entry.resource(&buffer);
                 }
+                ShaderUniformType::GenericUniformBuffer(buffer_id) => {
+                    let buffer = context.get_buffer_with_usage(buffer_id, BufferUsage::UNIFORM)?;
+                    let buffer = GpuBufferBinding::new(&buffer.buffer);
+                    entry.resource(&buffer);
+                }
+                ShaderUniformType::GenericShaderStorageBuffer(buffer_id)
+                | ShaderUniformType::GenericShaderStorageBufferReadOnly(buffer_id) => {
+                    let buffer = context.get_buffer_with_usage(buffer_id, BufferUsage::STORAGE)?;
+                    let buffer = GpuBufferBinding::new(&buffer.buffer);
+                    entry.resource(&buffer);
+                }
                 ShaderUniformType::Texture(texture_id) => {
                     let texture = context.textures.get(texture_id.id()).ok_or(gpu_api_err!(
                         "program uniform texture id {:?} does not exist",
@@ -157,13 +286,137 @@ impl WebGpuBindGroups {
                     ))?;
                     entry.resource(&texture.texture_view);
                 }
-                ShaderUniformType::Sampler(sampler_id) => {
+                ShaderUniformType::Texture3D(texture_id) => {
+                    let texture = context.textures.get(texture_id.id()).ok_or(gpu_api_err!(
+                        "program uniform 3d texture id {:?} does not exist",
+                        texture_id
+                    ))?;
+                    if texture.view_dimension() != TextureViewDimension::D3 {
+                        Err(gpu_api_err!(
+                            "program uniform 3d texture id {:?} is not a 3d texture",
+                            texture_id
+                        ))?;
+                    }
+                    entry.resource(&texture.texture_view);
+                }
+                ShaderUniformType::TextureArray(texture_id) => {
+                    let texture = context.textures.get(texture_id.id()).ok_or(gpu_api_err!(
+                        "program uniform texture array id {:?} does not exist",
+                        texture_id
+                    ))?;
+                    if texture.view_dimension() != TextureViewDimension::D2Array {
+                        Err(gpu_api_err!(
+                            "program uniform texture array id {:?} is not a texture array",
+                            texture_id
+                        ))?;
+                    }
+                    entry.resource(&texture.texture_view);
+                }
+                ShaderUniformType::TextureView(texture_view_id) => {
+                    let texture_view = context
+                        .texture_views
+                        .get(texture_view_id.id())
+                        .ok_or(gpu_api_err!(
+                            "program uniform texture view id {:?} does not exist",
+                            texture_view_id
+                        ))?;
+                    entry.resource(&texture_view.texture_view);
+                }
+                ShaderUniformType::Sampler(sampler_id)
+                | ShaderUniformType::ComparisonSampler(sampler_id) => {
                     let sampler = context.sampler_cache.get(sampler_id).ok_or(gpu_api_err!(
                         "program uniform sampler id {:?} does not exist",
                         sampler_id
                     ))?;
+                    let is_comparison = matches!(uniform.ty, ShaderUniformType::ComparisonSampler(_));
+                    if context.sampler_cache.is_comparison(sampler_id) != Some(is_comparison) {
+                        Err(gpu_api_err!(
+                            "program uniform sampler id {:?} must {}be a comparison sampler",
+                            sampler_id,
+                            if is_comparison { "" } else { "not " }
+                        ))?;
+                    }
                     entry.resource(&sampler);
                 }
+                ShaderUniformType::AttachmentTexture(attachment_image_id) => {
+                    let attachment_image = context
+                        .attachment_images
+                        .get(attachment_image_id.id())
+                        .ok_or(gpu_api_err!(
+                            "program uniform attachment texture id {:?} does not exist",
+                            attachment_image_id
+                        ))?;
+                    if attachment_image.is_multisampled() {
+                        Err(gpu_api_err!(
+                            "program uniform attachment texture id {:?} cannot be multisampled",
+                            attachment_image_id
+                        ))?;
+                    }
+                    entry.resource(&attachment_image.sampled_texture_view);
+                }
+                ShaderUniformType::MultisampledAttachmentTexture(attachment_image_id) => {
+                    let attachment_image = context
+                        .attachment_images
+                        .get(attachment_image_id.id())
+                        .ok_or(gpu_api_err!(
+                            "program uniform multisampled attachment texture id {:?} does not exist",
+                            attachment_image_id
+                        ))?;
+                    if !attachment_image.is_multisampled() {
+                        Err(gpu_api_err!(
+                            "program uniform multisampled attachment texture id {:?} is not multisampled",
+                            attachment_image_id
+                        ))?;
+                    }
+                    entry.resource(&attachment_image.sampled_texture_view);
+                }
+                ShaderUniformType::DepthAttachmentTexture(attachment_image_id) => {
+                    let attachment_image = context
+                        .attachment_images
+                        .get(attachment_image_id.id())
+                        .ok_or(gpu_api_err!(
+                            "program uniform depth attachment texture id {:?} does not exist",
+                            attachment_image_id
+                        ))?;
+                    if !attachment_image
+                        .get_depth_format()
+                        .is_some_and(|depth_format| depth_format.has_depth())
+                    {
+                        Err(gpu_api_err!(
+                            "program uniform depth attachment texture id {:?} has no depth",
+                            attachment_image_id
+                        ))?;
+                    }
+                    if attachment_image.is_multisampled() {
+                        Err(gpu_api_err!(
+                            "program uniform depth attachment texture id {:?} cannot be multisampled",
+                            attachment_image_id
+                        ))?;
+                    }
+                    entry.resource(&attachment_image.sampled_texture_view);
+                }
+                ShaderUniformType::StorageTexture(storage_texture, _, format) => {
+                    let format = storage_texture_format_into_webgpu(format);
+                    let storage_texture_view = match storage_texture {
+                        GenericTextureId::Texture(texture_id) => context
+                            .textures
+                            .get(texture_id.id())
+                            .ok_or(gpu_api_err!(
+                                "program uniform storage texture id {:?} does not exist",
+                                texture_id
+                            ))?
+                            .get_storage_texture_view(format)?,
+                        GenericTextureId::AttachmentImage(attachment_image_id) => context
+                            .attachment_images
+                            .get(attachment_image_id.id())
+                            .ok_or(gpu_api_err!(
+                                "program uniform storage texture attachment image id {:?} does not exist",
+                                attachment_image_id
+                            ))?
+                            .get_storage_texture_view(format)?,
+                    };
+                    entry.resource(storage_texture_view);
+                }
                 ShaderUniformType::InputAttachment(attachment_image_id) => {
                     let attachment_image = context
                         .attachment_images
@@ -271,3 +524,12 @@ impl WebGpuBindGroups {
         Ok(offsets)
     }
 }
+
+fn storage_texture_format_into_webgpu(format: ShaderStorageTextureFormat) -> GpuTextureFormat {
+    match format {
+        ShaderStorageTextureFormat::Rgba8UNorm => GpuTextureFormat::Rgba8unorm,
+        ShaderStorageTextureFormat::Rgba16SFloat => GpuTextureFormat::Rgba16float,
+        ShaderStorageTextureFormat::R32SFloat => GpuTextureFormat::R32float,
+        ShaderStorageTextureFormat::Rgba32SFloat => GpuTextureFormat::Rgba32float,
+    }
+}
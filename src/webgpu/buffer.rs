@@ -1,6 +1,186 @@
 use super::*;
 use crate::alignment::pad_raw_slice;
 
+impl WebGpuContext {
+    pub fn new_buffer<T: Copy>(
+        &mut self,
+        data: &[T],
+        usage: BufferUsage,
+        _storage_type: BufferStorageType,
+        _ext: Option<NewBufferExt>,
+    ) -> GResult<BufferId> {
+        //  Async uploads can target any buffer, regardless of the storage type.
+        let mut gpu_usage = GpuBufferUsageFlags::CopyDst as u32;
+        [
+            (BufferUsage::VERTEX, GpuBufferUsageFlags::Vertex),
+            (BufferUsage::INDEX, GpuBufferUsageFlags::Index),
+            (BufferUsage::UNIFORM, GpuBufferUsageFlags::Uniform),
+            (BufferUsage::STORAGE, GpuBufferUsageFlags::Storage),
+            (BufferUsage::COPY_SRC, GpuBufferUsageFlags::CopySrc),
+            (BufferUsage::COPY_DST, GpuBufferUsageFlags::CopyDst),
+        ]
+        .into_iter()
+        .for_each(|(usage_bit, gpu_usage_bit)| {
+            if usage.contains(usage_bit) {
+                gpu_usage |= gpu_usage_bit as u32;
+            }
+        });
+
+        let size = std::mem::size_of_val(data);
+        let buffer = WebGpuBuffer::new(
+            &self.device,
+            size as u32,
+            gpu_usage,
+            unsafe { std::slice::from_raw_parts(data.as_ptr() as *const u8, size) },
+            usage.contains(BufferUsage::COPY_SRC),
+        );
+        self.buffers.push(WebGpuGenericBuffer { buffer, usage });
+        Ok(BufferId::from_id(self.buffers.len() - 1))
+    }
+
+    pub fn read_synced_buffer(
+        &self,
+        _buffer: BufferId,
+        _ext: Option<ReadSyncedBufferExt>,
+    ) -> GResult<Vec<u8>> {
+        Err(gpu_api_err!(
+            "webgpu does not support this operation, please use async_read_synced_buffer instead"
+        ))
+    }
+
+    pub async fn async_read_synced_buffer(
+        &self,
+        buffer_id: BufferId,
+        _ext: Option<ReadSyncedBufferExt>,
+    ) -> GResult<Vec<u8>> {
+        let buffer = &self
+            .buffers
+            .get(buffer_id.id())
+            .ok_or(gpu_api_err!(
+                "webgpu read synced buffer id {:?} does not exist",
+                buffer_id
+            ))?
+            .buffer;
+
+        let readable_buffer = buffer.readable_buffer.as_ref().ok_or(gpu_api_err!(
+            "webgpu read synced buffer id {:?} was not created with BufferUsage::COPY_SRC",
+            buffer_id
+        ))?;
+
+        let command_encoder = self.device.create_command_encoder();
+        command_encoder.copy_buffer_to_buffer_with_u32_and_u32_and_u32(
+            &buffer.buffer,
+            0,
+            readable_buffer,
+            0,
+            buffer.size,
+        );
+        let command_buffer = command_encoder.finish();
+        let commands = Array::new();
+        commands.push(&command_buffer);
+        self.device.queue().submit(&commands);
+
+        JsFuture::from(readable_buffer.map_async(GpuMapModeFlags::Read as u32))
+            .await
+            .map_err(|e| {
+                gpu_api_err!(
+                    "webgpu failed to map buffer in async_read_synced_buffer: {:?}",
+                    e
+                )
+            })?;
+
+        let mapped_buf = readable_buffer.get_mapped_range();
+        let u8_js_buf = Uint8Array::new(&mapped_buf);
+        let u8_rs_buf = u8_js_buf.to_vec();
+
+        readable_buffer.unmap();
+
+        Ok(u8_rs_buf)
+    }
+
+    pub fn async_upload_buffer<T: Copy>(
+        &mut self,
+        buffer_id: BufferId,
+        data: &[T],
+        _ext: Option<AsyncUploadBufferExt>,
+    ) -> GResult<UploadId> {
+        let size = std::mem::size_of_val(data);
+        let buffer = &self
+            .buffers
+            .get(buffer_id.id())
+            .ok_or(gpu_api_err!(
+                "webgpu async upload buffer id {:?} does not exist",
+                buffer_id
+            ))?
+            .buffer;
+        if size > buffer.size as usize {
+            Err(gpu_api_err!(
+                "webgpu async upload buffer size ({}) > buffer size ({})",
+                size,
+                buffer.size
+            ))?
+        }
+        self.device
+            .queue()
+            .write_buffer_with_u32_and_u8_array(&buffer.buffer, 0, unsafe {
+                std::slice::from_raw_parts(data.as_ptr() as *const u8, size)
+            });
+        Ok(self.track_upload())
+    }
+
+    pub fn get_vertex_buffer(&self, vbo: GenericVertexBufferId) -> GResult<&GpuBuffer> {
+        match vbo {
+            GenericVertexBufferId::Vertex(vbo) => self
+                .vbos
+                .get(vbo.id())
+                .map(|vbo| &vbo.buffer)
+                .ok_or(gpu_api_err!(
+                    "webgpu vertex buffer id {:?} does not exist",
+                    vbo
+                )),
+            GenericVertexBufferId::Buffer(buffer) => self
+                .get_buffer_with_usage(buffer, BufferUsage::VERTEX)
+                .map(|buffer| &buffer.buffer),
+        }
+    }
+
+    pub fn get_index_buffer(&self, ibo: GenericIndexBufferId) -> GResult<&GpuBuffer> {
+        match ibo {
+            GenericIndexBufferId::Index(ibo) => self
+                .ibos
+                .get(ibo.id())
+                .map(|ibo| &ibo.buffer)
+                .ok_or(gpu_api_err!(
+                    "webgpu index buffer id {:?} does not exist",
+                    ibo
+                )),
+            GenericIndexBufferId::Buffer(buffer) => self
+                .get_buffer_with_usage(buffer, BufferUsage::INDEX)
+                .map(|buffer| &buffer.buffer),
+        }
+    }
+
+    pub fn get_buffer_with_usage(
+        &self,
+        buffer: BufferId,
+        usage: BufferUsage,
+    ) -> GResult<&WebGpuBuffer> {
+        let generic = self
+            .buffers
+            .get(buffer.id())
+            .ok_or(gpu_api_err!("webgpu buffer id {:?} does not exist", buffer))?;
+        generic
+            .usage
+            .contains(usage)
+            .then_some(&generic.buffer)
+            .ok_or(gpu_api_err!(
+                "webgpu buffer id {:?} was not created with usage {:?}",
+                buffer,
+                usage
+            ))
+    }
+}
+
 impl WebGpuContext {
     pub fn new_vertex_buffer(
         &mut self,
@@ -122,6 +302,11 @@ impl WebGpuBuffer {
     }
 }
 
+pub struct WebGpuGenericBuffer {
+    pub buffer: WebGpuBuffer,
+    pub usage: BufferUsage,
+}
+
 pub struct WebGpuDynamicBuffer {
     pub buffer: WebGpuBuffer,
     pub per_index_offset: usize,
@@ -16,6 +16,9 @@ pub fn check_extensions(extensions: &Extensions, is_async: bool) -> GResult<()>
             Extension::NativeDebug(_) => Ok(()),
             Extension::MemoryFlush => Ok(()),
             Extension::NagaTranslation => Ok(()),
+            Extension::TextureContainer => Ok(()),
+            Extension::ImageLoading => Ok(()),
+            Extension::Ibl => Ok(()),
             Extension::WebGpuInitFromWindow(_) => Ok(()),
             Extension::WebGpuInit(_) =>
                 if is_async {
@@ -23,8 +23,13 @@ impl WebGpuContext {
         surface.canvas.set_height(height as u32);
 
         //  Resize Attachment Images.
-        for attachment_image in self.attachment_images.iter_mut() {
+        let mut resized_images = vec![];
+        for (idx, attachment_image) in self.attachment_images.iter_mut().enumerate() {
+            if !attachment_image.is_surface_relative() {
+                continue;
+            }
             attachment_image.recreate_with_new_size(&self.device, width, height);
+            resized_images.push(AttachmentImageId::from_id(idx));
         }
 
         //  Recreate Programs.
@@ -35,10 +40,18 @@ impl WebGpuContext {
         }
 
         //  Resize Dependent Passes.
+        //  Passes that render into resized attachment images also need new attachment views.
         for pass_idx in 0..self.compiled_passes.len() {
             let pass = &mut self.compiled_passes[pass_idx];
-            pass.original_pass.render_width = width;
-            pass.original_pass.render_height = height;
+            if pass.original_pass.depends_on_surface_size {
+                pass.original_pass.render_width = width;
+                pass.original_pass.render_height = height;
+            } else if !pass
+                .original_pass
+                .uses_any_attachment_image(&resized_images)
+            {
+                continue;
+            }
             let pass = &self.compiled_passes[pass_idx];
             let new_pass =
                 WebGpuCompiledPass::new(self, &pass.original_pass, Some(pass.ext.clone()))?;
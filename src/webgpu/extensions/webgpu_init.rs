@@ -13,15 +13,31 @@ impl WebGpuContext {
                 )
             })?
             .into();
-        let device: GpuDevice = JsFuture::from(adapter.request_device())
-            .await
-            .map_err(|e| {
-                gpu_api_err!(
-                    "webgpu cannot request device (webgpu may not be supported):{:?}",
-                    e
-                )
-            })?
-            .into();
+
+        //  Request whichever texture compression and depth format features are available.
+        let adapter_features = adapter.features();
+        let required_features = Array::new();
+        WEBGPU_TEXTURE_COMPRESSION_FEATURES
+            .into_iter()
+            .map(|(feature, _)| feature)
+            .chain([WEBGPU_DEPTH32FLOAT_STENCIL8_FEATURE])
+            .filter(|feature| adapter_features.has(feature))
+            .for_each(|feature| {
+                required_features.push(&JsValue::from_str(feature));
+            });
+        let mut device_info = GpuDeviceDescriptor::new();
+        device_info.required_features(&required_features);
+
+        let device: GpuDevice =
+            JsFuture::from(adapter.request_device_with_descriptor(&device_info))
+                .await
+                .map_err(|e| {
+                    gpu_api_err!(
+                        "webgpu cannot request device (webgpu may not be supported):{:?}",
+                        e
+                    )
+                })?
+                .into();
         Ok((adapter, device, init.canvas_id))
     }
 }
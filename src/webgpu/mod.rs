@@ -8,20 +8,30 @@ use wasm_bindgen::prelude::*;
 use wasm_bindgen_futures::JsFuture;
 use web_sys::*;
 
-use attachment_image::WebGpuAttachmentImage;
+use attachment_image::{submit_attachment_image_copy_backs, WebGpuAttachmentImage};
 use bind_groups::WebGpuBindGroups;
-use buffer::{WebGpuBuffer, WebGpuDynamicBuffer};
+use buffer::{WebGpuBuffer, WebGpuDynamicBuffer, WebGpuGenericBuffer};
 use flags::{GpuBufferUsageFlags, GpuMapModeFlags, GpuShaderStageFlags, GpuTextureUsageFlags};
-use pass::WebGpuCompiledPass;
+use pass::{compare_op_into_webgpu, WebGpuCompiledPass};
 use program::WebGpuProgram;
 use sampler::WebGpuSamplerCache;
 use surface::WebGpuSurface;
-use texture::{WebGpuMipmapStateCache, WebGpuTexture};
+use texture::{
+    is_storage_format, submit_buffer_texture_copy, submit_generate_mipmaps, submit_texture_copy,
+    WebGpuBlitStateCache, WebGpuMipmapStateCache, WebGpuTexture, WebGpuTextureView,
+    WebGpuTransferTexture,
+};
 
 use extensions::compute::{WebGpuCompiledComputePass, WebGpuComputeProgram};
 
 pub const WEBGPU_COLOR_ATTACHMENT_FORMAT: GpuTextureFormat = GpuTextureFormat::Rgba8unorm;
 pub const WEBGPU_DEPTH_ATTACHMENT_FORMAT: GpuTextureFormat = GpuTextureFormat::Depth24plusStencil8;
+pub const WEBGPU_DEPTH32FLOAT_STENCIL8_FEATURE: &str = "depth32float-stencil8";
+pub const WEBGPU_TEXTURE_COMPRESSION_FEATURES: [(&str, TextureCompression); 3] = [
+    ("texture-compression-bc", TextureCompression::Bc),
+    ("texture-compression-etc2", TextureCompression::Etc2),
+    ("texture-compression-astc", TextureCompression::Astc),
+];
 
 mod attachment_image;
 mod bind_groups;
@@ -34,12 +44,14 @@ mod sampler;
 mod submit;
 mod surface;
 mod texture;
+mod upload;
 
 pub struct WebGpuContext {
     adapter: GpuAdapter,
     device: GpuDevice,
     surface: Option<WebGpuSurface>,
 
+    buffers: Vec<WebGpuGenericBuffer>,
     vbos: Vec<WebGpuBuffer>,
     ibos: Vec<WebGpuBuffer>,
     ubos: Vec<WebGpuBuffer>,
@@ -50,9 +62,14 @@ pub struct WebGpuContext {
     compiled_passes: Vec<WebGpuCompiledPass>,
     compiled_compute_passes: Vec<WebGpuCompiledComputePass>,
     textures: Vec<WebGpuTexture>,
+    texture_views: Vec<WebGpuTextureView>,
     attachment_images: Vec<WebGpuAttachmentImage>,
     sampler_cache: WebGpuSamplerCache,
     mipmap_state_cache: WebGpuMipmapStateCache,
+    blit_state_cache: WebGpuBlitStateCache,
+    //  Only pending uploads, ids are never reused so a finished upload keeps polling `true`.
+    uploads: std::collections::HashMap<usize, std::rc::Rc<std::cell::Cell<bool>>>,
+    next_upload_id: usize,
 }
 
 impl WebGpuContext {
@@ -150,12 +167,14 @@ impl WebGpuContext {
         };
 
         let mipmap_state_cache = WebGpuMipmapStateCache::new(&device);
+        let blit_state_cache = WebGpuBlitStateCache::new(&device);
 
         Ok(WebGpuContext {
             adapter,
             device,
             surface,
 
+            buffers: vec![],
             vbos: vec![],
             ibos: vec![],
             ubos: vec![],
@@ -166,9 +185,13 @@ impl WebGpuContext {
             compiled_passes: vec![],
             compiled_compute_passes: vec![],
             textures: vec![],
+            texture_views: vec![],
             attachment_images: vec![],
             sampler_cache: WebGpuSamplerCache::new(),
             mipmap_state_cache,
+            blit_state_cache,
+            uploads: std::collections::HashMap::new(),
+            next_upload_id: 0,
         })
     }
 }
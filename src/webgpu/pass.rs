@@ -26,6 +26,42 @@ impl WebGpuCompiledPass {
     pub fn new(context: &WebGpuContext, pass: &Pass, ext: Option<CompilePassExt>) -> GResult<Self> {
         let ext = ext.unwrap_or_default();
 
+        if ext.skip_msaa_resolve.is_some() && pass.surface_attachment {
+            Err(gpu_api_err!(
+                "webgpu compile pass cannot skip the msaa resolve of the surface attachment"
+            ))?;
+        }
+        if ext.skip_msaa_resolve.is_some() {
+            if ext.enable_msaa.is_none() {
+                Err(gpu_api_err!(
+                    "webgpu compile pass cannot skip the msaa resolve without enable_msaa"
+                ))?;
+            }
+            //  Render directly into the multisampled attachment images.
+            let msaa_samples = ext.msaa_samples.unwrap_or_default();
+            pass.attachments
+                .iter()
+                .filter_map(|attachment| attachment.output_image)
+                .try_for_each(|output_image| {
+                    let attachment_image =
+                        context
+                            .attachment_images
+                            .get(output_image.id())
+                            .ok_or(gpu_api_err!(
+                                "webgpu compile pass attachment image id {:?} does not exist",
+                                output_image
+                            ))?;
+                    if attachment_image.msaa_samples() != msaa_samples {
+                        Err(gpu_api_err!(
+                            "webgpu compile pass attachment image id {:?} does not have {:?}",
+                            output_image,
+                            msaa_samples
+                        ))?;
+                    }
+                    Ok(())
+                })?;
+        }
+
         let mut resolve_attachment_views = vec![];
 
         let pipelines = pass
@@ -96,22 +132,22 @@ impl WebGpuCompiledPass {
                         .primitive(&primitive);
 
                     if program.ext.enable_depth_write.is_some() || program.ext.enable_stencil_test.is_some() {
-                        let mut depth_stencil = GpuDepthStencilState::new(WEBGPU_DEPTH_ATTACHMENT_FORMAT);
+                        let depth_attachment_image = step
+                            .write_depth
+                            .and_then(|write_depth| pass.attachments.get(write_depth.id()))
+                            .and_then(|attachment| attachment.output_image)
+                            .and_then(|output_image| context.attachment_images.get(output_image.id()));
+                        let depth_format = depth_attachment_image
+                            .map(|attachment_image| attachment_image.format)
+                            .unwrap_or(WEBGPU_DEPTH_ATTACHMENT_FORMAT);
+                        let has_depth = depth_attachment_image
+                            .and_then(|attachment_image| attachment_image.get_depth_format())
+                            .is_none_or(|attachment_depth_format| attachment_depth_format.has_depth());
+
+                        let mut depth_stencil = GpuDepthStencilState::new(depth_format);
                         depth_stencil.depth_compare(GpuCompareFunction::Less);
                         depth_stencil.depth_write_enabled(true);
 
-                        fn compare_op_into_webgpu(compare_op: ShaderCompareOp) -> GpuCompareFunction {
-                            match compare_op {
-                                ShaderCompareOp::Never => GpuCompareFunction::Never,
-                                ShaderCompareOp::Less => GpuCompareFunction::Less,
-                                ShaderCompareOp::Equal => GpuCompareFunction::Equal,
-                                ShaderCompareOp::LessOrEqual => GpuCompareFunction::LessEqual,
-                                ShaderCompareOp::Greater => GpuCompareFunction::Greater,
-                                ShaderCompareOp::NotEqual => GpuCompareFunction::NotEqual,
-                                ShaderCompareOp::GreaterOrEqual => GpuCompareFunction::GreaterEqual,
-                                ShaderCompareOp::Always => GpuCompareFunction::Always,
-                            }
-                        }
                         fn stencil_op_into_webgpu(stencil_op: ShaderStencilOp) -> GpuStencilOperation {
                             match stencil_op {
                                 ShaderStencilOp::Keep => GpuStencilOperation::Keep,
@@ -130,9 +166,14 @@ impl WebGpuCompiledPass {
                             .depth_fail_op(stencil_op_into_webgpu(program.ext.stencil_depth_fail.unwrap_or_default()))
                             .fail_op(stencil_op_into_webgpu(program.ext.stencil_fail.unwrap_or_default()))
                             .pass_op(stencil_op_into_webgpu(program.ext.stencil_pass.unwrap_or_default()));
+                        //  Stencil only formats require depth writes to be disabled.
                         depth_stencil
-                            .depth_write_enabled(program.ext.enable_depth_write.is_some())
-                            .depth_compare(compare_op_into_webgpu(program.ext.depth_compare_op.unwrap_or_default()))
+                            .depth_write_enabled(has_depth && program.ext.enable_depth_write.is_some())
+                            .depth_compare(if has_depth {
+                                compare_op_into_webgpu(program.ext.depth_compare_op.unwrap_or_default())
+                            } else {
+                                GpuCompareFunction::Always
+                            })
                             .stencil_read_mask(program.ext.stencil_compare_mask.unwrap_or_default())
                             .stencil_write_mask(program.ext.stencil_write_mask.unwrap_or_default());
 
@@ -227,6 +268,11 @@ impl WebGpuCompiledPass {
                                     multisample.count(sample_count);
                                     pipeline_info.multisample(&multisample);
 
+                                    //  Render directly into the multisampled attachment images instead.
+                                    if ext.skip_msaa_resolve.is_some() {
+                                        return Ok(());
+                                    }
+
                                     let format = if pass.surface_attachment {
                                         context
                                             .surface
@@ -293,7 +339,7 @@ impl WebGpuCompiledPass {
                             "webgpu compile pass attachment image id {:?} does not exist",
                             attachment_image
                         ))?;
-                    attachment_image.texture_view.clone()
+                    attachment_image.get_render_view(attachment.output_subresource)?
                 } else {
                     JsValue::null().into()
                 })
@@ -309,3 +355,16 @@ impl WebGpuCompiledPass {
         })
     }
 }
+
+pub fn compare_op_into_webgpu(compare_op: ShaderCompareOp) -> GpuCompareFunction {
+    match compare_op {
+        ShaderCompareOp::Never => GpuCompareFunction::Never,
+        ShaderCompareOp::Less => GpuCompareFunction::Less,
+        ShaderCompareOp::Equal => GpuCompareFunction::Equal,
+        ShaderCompareOp::LessOrEqual => GpuCompareFunction::LessEqual,
+        ShaderCompareOp::Greater => GpuCompareFunction::Greater,
+        ShaderCompareOp::NotEqual => GpuCompareFunction::NotEqual,
+        ShaderCompareOp::GreaterOrEqual => GpuCompareFunction::GreaterEqual,
+        ShaderCompareOp::Always => GpuCompareFunction::Always,
+    }
+}
@@ -9,8 +9,15 @@ impl WebGpuContext {
             mip_filter,
             u_mode,
             v_mode,
+            w_mode,
+            //  There are no border colors in WebGPU.
+            border_color: _,
             min_lod,
             max_lod,
+            //  There is no lod bias in WebGPU.
+            lod_bias: _,
+            max_anisotropy,
+            compare_op,
         } = ext.unwrap_or_default();
         let data = SamplerData {
             min_filter,
@@ -18,8 +25,18 @@ impl WebGpuContext {
             mip_filter,
             u_mode,
             v_mode,
+            w_mode,
             min_lod: min_lod.map(HashableF32::from_val),
             max_lod: max_lod.map(HashableF32::from_val),
+            //  WebGPU requires all filters to be linear when using anisotropy.
+            max_anisotropy: max_anisotropy
+                .filter(|_| {
+                    min_filter == SamplerFilter::Linear
+                        && mag_filter == SamplerFilter::Linear
+                        && mip_filter == MipSamplerFilter::Linear
+                })
+                .map(|anisotropy| anisotropy.clamp(1, 16)),
+            compare_op,
         };
         self.sampler_cache
             .get_or_insert(&self.device, data)
@@ -47,9 +64,12 @@ struct SamplerData {
     mag_filter: SamplerFilter,
     u_mode: SamplerMode,
     v_mode: SamplerMode,
+    w_mode: SamplerMode,
     min_lod: Option<HashableF32>,
     max_lod: Option<HashableF32>,
+    max_anisotropy: Option<u16>,
     mip_filter: MipSamplerFilter,
+    compare_op: Option<ShaderCompareOp>,
 }
 
 pub struct WebGpuSamplerCache {
@@ -87,6 +107,22 @@ impl WebGpuSamplerCache {
         self.samplers.get(data).cloned()
     }
 
+    //  Samplers without any linear filters can be bound as non-filtering,
+    //  which is required for `UnfilterableFloat` textures.
+    pub fn is_filtering(&self, sampler_id: SamplerId) -> Option<bool> {
+        let data = self.sampler_datas.get(sampler_id.id())?;
+        Some(
+            data.min_filter == SamplerFilter::Linear
+                || data.mag_filter == SamplerFilter::Linear
+                || data.mip_filter == MipSamplerFilter::Linear,
+        )
+    }
+
+    pub fn is_comparison(&self, sampler_id: SamplerId) -> Option<bool> {
+        let data = self.sampler_datas.get(sampler_id.id())?;
+        Some(data.compare_op.is_some())
+    }
+
     fn get_or_insert(&mut self, device: &GpuDevice, data: SamplerData) -> GResult<usize> {
         if let Some(id) = self
             .sampler_datas
@@ -105,7 +141,8 @@ impl WebGpuSamplerCache {
                 .min_filter(filter_into_webgpu(data.min_filter))
                 .mag_filter(filter_into_webgpu(data.mag_filter))
                 .address_mode_u(mode_into_webgpu(data.u_mode))
-                .address_mode_v(mode_into_webgpu(data.v_mode));
+                .address_mode_v(mode_into_webgpu(data.v_mode))
+                .address_mode_w(mode_into_webgpu(data.w_mode));
 
             if let Some(lod) = data.min_lod {
                 sampler_info.lod_min_clamp(lod.get_val());
@@ -113,6 +150,12 @@ impl WebGpuSamplerCache {
             if let Some(lod) = data.max_lod {
                 sampler_info.lod_max_clamp(lod.get_val());
             }
+            if let Some(anisotropy) = data.max_anisotropy {
+                sampler_info.max_anisotropy(anisotropy);
+            }
+            if let Some(compare_op) = data.compare_op {
+                sampler_info.compare(compare_op_into_webgpu(compare_op));
+            }
 
             let sampler = device.create_sampler_with_descriptor(&sampler_info);
 
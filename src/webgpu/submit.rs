@@ -12,13 +12,26 @@ impl WebGpuContext {
                 SubmitPassType::Compute(pass) => {
                     extensions::compute::submit_compute_pass(self, pass, &command_encoder)
                 }
+                SubmitPassType::TextureCopy(copy) => {
+                    submit_texture_copy(self, copy, &command_encoder)
+                }
+                SubmitPassType::BufferTextureCopy(copy) => {
+                    submit_buffer_texture_copy(self, copy, &command_encoder)
+                }
+                SubmitPassType::GenerateMipmaps(texture) => {
+                    submit_generate_mipmaps(self, *texture, &command_encoder)
+                }
             }?;
             Ok(())
         })?;
 
+        submit_attachment_image_copy_backs(self, &submit, &command_encoder)?;
+
         let submissions = Array::new();
         submissions.push(&command_encoder.finish());
         self.device.queue().submit(&submissions);
+        self.blit_state_cache.destroy_temporaries();
+        self.mipmap_state_cache.destroy_temporaries();
 
         Ok(())
     }
@@ -75,6 +88,38 @@ fn submit_transfers(context: &WebGpuContext, submit: &Submit) -> GResult<()> {
             Ok(())
         })?;
 
+    submit
+        .buffer_transfers
+        .iter()
+        .try_for_each(|(buffer_id, data)| {
+            let buffer = context.buffers.get(buffer_id.id()).ok_or(gpu_api_err!(
+                "webgpu submit transfers buffer id {:?} does not exist",
+                buffer_id
+            ))?;
+            if data.len() > buffer.buffer.size as usize {
+                Err(gpu_api_err!(
+                    "webgpu buffer transfer size ({}) > buffer size ({})",
+                    data.len(),
+                    buffer.buffer.size
+                ))?
+            }
+            queue.write_buffer_with_u32_and_u8_array(&buffer.buffer.buffer, 0, data);
+            Ok(())
+        })?;
+
+    submit
+        .texture_transfers
+        .iter()
+        .try_for_each(|(texture_id, region, data, ext)| {
+            let texture = context.textures.get(texture_id.id()).ok_or(gpu_api_err!(
+                "webgpu submit transfers texture id {:?} does not exist",
+                texture_id
+            ))?;
+            let row_pitch = texture.check_region(region, data.len(), ext.row_pitch)?;
+            texture.write_region(&queue, region, data, row_pitch);
+            Ok(())
+        })?;
+
     Ok(())
 }
 
@@ -117,8 +162,12 @@ fn submit_pass(
 
                 let local_attachment_idx = write_color.id();
 
-                let attachment_view = if local_attachment_idx == 0 {
-                    surface_view.as_ref().unwrap()
+                let attachment_view = if pass.original_pass.surface_attachment
+                    && local_attachment_idx == 0
+                {
+                    surface_view.as_ref().ok_or(gpu_api_err!(
+                        "webgpu submit pass with a surface attachment requires a surface"
+                    ))?
                 } else {
                     &pass.attachment_views[local_attachment_idx]
                 };
@@ -130,21 +179,23 @@ fn submit_pass(
                             _ => GpuLoadOp::Load,
                         };
 
+                        let resolve_msaa = pass.ext.enable_msaa.is_some()
+                            && pass.ext.skip_msaa_resolve.is_none();
                         let mut color_attachment = GpuRenderPassColorAttachment::new(
                             op,
-                            if pass.ext.enable_msaa.is_some() {
+                            if resolve_msaa {
                                 GpuStoreOp::Discard
                             } else {
                                 GpuStoreOp::Store
                             },
-                            if pass.ext.enable_msaa.is_some() {
+                            if resolve_msaa {
                                 &pass.resolve_attachment_views[step_idx]
                             } else {
                                 attachment_view
                             },
                         );
 
-                        if pass.ext.enable_msaa.is_some() {
+                        if resolve_msaa {
                             color_attachment.resolve_target(attachment_view);
                         }
 
@@ -197,21 +248,30 @@ fn submit_pass(
                             "webpgpu clear depth stencil for attachment index {:?} not set",
                             local_attachment_id
                         ))?;
+                //  Load and store ops may only be set for aspects that the format has.
+                let depth_format = attachment
+                    .output_image
+                    .and_then(|output_image| context.attachment_images.get(output_image.id()))
+                    .and_then(|attachment_image| attachment_image.get_depth_format())
+                    .unwrap_or(AttachmentImageDepthFormat::D24UNormS8UInt);
                 match &attachment.ty {
                     PassInputType::Depth(load_op) => {
-                        depth_stencil_attachment
-                            .depth_store_op(GpuStoreOp::Store)
-                            .depth_load_op(match load_op {
-                                PassInputLoadOpDepthStencilType::Clear => GpuLoadOp::Clear,
-                                _ => GpuLoadOp::Load,
-                            })
-                            .depth_clear_value(depth_clear_val.depth)
-                            .stencil_load_op(match load_op {
-                                PassInputLoadOpDepthStencilType::Clear => GpuLoadOp::Clear,
-                                _ => GpuLoadOp::Load,
-                            })
-                            .stencil_store_op(GpuStoreOp::Store)
-                            .stencil_clear_value(depth_clear_val.stencil);
+                        let load_op = match load_op {
+                            PassInputLoadOpDepthStencilType::Clear => GpuLoadOp::Clear,
+                            _ => GpuLoadOp::Load,
+                        };
+                        if depth_format.has_depth() {
+                            depth_stencil_attachment
+                                .depth_store_op(GpuStoreOp::Store)
+                                .depth_load_op(load_op)
+                                .depth_clear_value(depth_clear_val.depth);
+                        }
+                        if depth_format.has_stencil() {
+                            depth_stencil_attachment
+                                .stencil_load_op(load_op)
+                                .stencil_store_op(GpuStoreOp::Store)
+                                .stencil_clear_value(depth_clear_val.stencil);
+                        }
                     }
                     _ => unreachable!(),
                 }
@@ -230,22 +290,16 @@ fn submit_pass(
             step.vertex_buffers
                 .iter()
                 .enumerate()
-                .try_for_each(|(slot_idx, vbo)| {
-                    let vbo = context.vbos.get(vbo.id()).ok_or(gpu_api_err!(
-                        "webgpu submit vertex buffer id {:?} does not exist",
-                        vbo
-                    ))?;
-                    pass_encoder.set_vertex_buffer(slot_idx as u32, Some(&vbo.buffer));
+                .try_for_each(|(slot_idx, &vbo)| {
+                    let vbo = context.get_vertex_buffer(vbo)?;
+                    pass_encoder.set_vertex_buffer(slot_idx as u32, Some(vbo));
                     Ok(())
                 })?;
 
             if let Some(ibo) = step.index_buffer {
-                let ibo = context.ibos.get(ibo.id()).ok_or(gpu_api_err!(
-                    "webgpu submit index buffer id {:?} does not exist",
-                    ibo
-                ))?;
+                let ibo = context.get_index_buffer(ibo)?;
                 assert_eq!(std::mem::size_of::<IndexBufferElement>(), 4);
-                pass_encoder.set_index_buffer(&ibo.buffer, GpuIndexFormat::Uint32);
+                pass_encoder.set_index_buffer(ibo, GpuIndexFormat::Uint32);
             }
 
             step_data.draws.iter().try_for_each(|draw| {
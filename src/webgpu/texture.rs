@@ -1,5 +1,4 @@
 use super::*;
-use std::collections::HashMap;
 
 impl WebGpuContext {
     pub fn new_texture(
@@ -9,6 +8,46 @@ impl WebGpuContext {
         format: TextureFormat,
         ext: Option<NewTextureExt>,
     ) -> GResult<TextureId> {
+        if let Some(compression) = format.compression() {
+            if !self
+                .get_supported_texture_compressions()?
+                .contains(&compression)
+            {
+                Err(gpu_api_err!(
+                    "webgpu texture format {:?} requires unsupported compression {:?}",
+                    format,
+                    compression
+                ))?;
+            }
+        }
+        if let Some(ext) = &ext {
+            if ext.depth.is_some()
+                && (ext.enable_cubemap.is_some()
+                    || ext.enable_mipmaps.is_some()
+                    || ext.array_layers.is_some())
+            {
+                Err(gpu_api_err!(
+                    "webgpu 3d textures cannot be cubemaps, arrays, or have mipmaps"
+                ))?;
+            }
+            if ext.array_layers.is_some() && ext.enable_cubemap.is_some() {
+                Err(gpu_api_err!("webgpu texture arrays cannot be cubemaps"))?;
+            }
+            if ext.array_layers == Some(0) {
+                Err(gpu_api_err!(
+                    "webgpu texture arrays need at least one layer"
+                ))?;
+            }
+            if ext.enable_storage.is_some()
+                && !is_storage_format(texture_format_into_webgpu(format))
+            {
+                Err(gpu_api_err!(
+                    "webgpu storage texture format {:?} is not a storage texture format",
+                    format
+                ))?;
+            }
+        }
+
         let texture =
             WebGpuTexture::new(&self.device, width, height, format, ext.unwrap_or_default());
         self.textures.push(texture);
@@ -28,25 +67,19 @@ impl WebGpuContext {
             "webgpu upload texture id {:?} does not exist",
             texture
         ))?;
+        if texture.array_layers.is_some() {
+            Err(gpu_api_err!(
+                "webgpu called upload_texture on a texture array, use upload_texture_layer instead"
+            ))?;
+        }
+        texture.check_upload(&ext)?;
 
         let queue = self.device.queue();
-        let size = Array::new();
-        size.push(&JsValue::from(texture.width));
-        size.push(&JsValue::from(texture.height));
-
-        let mut layout = GpuImageDataLayout::new();
-        layout.offset(0.0).bytes_per_row(texture.width as u32 * 4);
-
-        queue.write_texture_with_u8_array_and_u32_sequence(
-            &GpuImageCopyTexture::new(&texture.texture),
-            data,
-            &layout,
-            &size,
-        );
+        texture.write_layer(&queue, data, 0, &ext)?;
 
         if ext.generate_mipmaps.is_some() {
             self.mipmap_state_cache
-                .generate_mipmap(&self.device, texture);
+                .generate_mipmap(&self.device, texture, 0)?;
         }
 
         Ok(())
@@ -64,15 +97,9 @@ impl WebGpuContext {
             "webgpu upload cubemap texture id {:?} does not exist",
             texture
         ))?;
+        texture.check_upload(&ext)?;
 
         let queue = self.device.queue();
-        let size = Array::new();
-        size.push(&JsValue::from(texture.width));
-        size.push(&JsValue::from(texture.height));
-
-        let mut layout = GpuImageDataLayout::new();
-        layout.offset(0.0).bytes_per_row(texture.width as u32 * 4);
-
         let datas = [
             upload.posx,
             upload.negx,
@@ -82,25 +109,244 @@ impl WebGpuContext {
             upload.negz,
         ];
         for (idx, data) in datas.iter().enumerate() {
-            let origin = Array::new();
-            origin.push(&JsValue::from(0));
-            origin.push(&JsValue::from(0));
-            origin.push(&JsValue::from(idx));
+            texture.write_layer(&queue, data, idx, &ext)?;
+        }
+
+        if ext.generate_mipmaps.is_some() {
+            for layer in 0..datas.len() as u32 {
+                self.mipmap_state_cache
+                    .generate_mipmap(&self.device, texture, layer)?;
+            }
+        }
 
-            let mut copy = GpuImageCopyTexture::new(&texture.texture);
-            copy.origin(&origin);
+        Ok(())
+    }
 
-            queue.write_texture_with_u8_array_and_u32_sequence(&copy, data, &layout, &size);
+    pub fn upload_texture_layer(
+        &mut self,
+        texture: TextureId,
+        layer: u32,
+        data: &[u8],
+        ext: Option<UploadTextureExt>,
+    ) -> GResult<()> {
+        let ext = ext.unwrap_or_default();
+
+        let texture = self.textures.get(texture.id()).ok_or(gpu_api_err!(
+            "webgpu upload texture layer id {:?} does not exist",
+            texture
+        ))?;
+        let Some(array_layers) = texture.array_layers else {
+            Err(gpu_api_err!("webgpu called upload_texture_layer on a non-array texture, use upload_texture instead"))?
+        };
+        if layer >= array_layers {
+            Err(gpu_api_err!(
+                "webgpu upload texture layer {} >= array layers {}",
+                layer,
+                array_layers
+            ))?;
         }
+        texture.check_upload(&ext)?;
+
+        let queue = self.device.queue();
+        texture.write_layer(&queue, data, layer as usize, &ext)?;
 
         if ext.generate_mipmaps.is_some() {
             self.mipmap_state_cache
-                .generate_mipmap(&self.device, texture);
+                .generate_mipmap(&self.device, texture, layer)?;
         }
 
         Ok(())
     }
 
+    pub fn upload_texture_region(
+        &mut self,
+        texture: TextureId,
+        region: TextureRegion,
+        data: &[u8],
+        ext: Option<UploadTextureRegionExt>,
+    ) -> GResult<()> {
+        let ext = ext.unwrap_or_default();
+
+        let texture = self.textures.get(texture.id()).ok_or(gpu_api_err!(
+            "webgpu upload texture region id {:?} does not exist",
+            texture
+        ))?;
+        let row_pitch = texture.check_region(&region, data.len(), ext.row_pitch)?;
+        texture.write_region(&self.device.queue(), &region, data, row_pitch);
+
+        Ok(())
+    }
+
+    pub fn read_texture(
+        &mut self,
+        _texture: TextureId,
+        _mip_level: u32,
+        _layer: u32,
+        _ext: Option<ReadTextureExt>,
+    ) -> GResult<Vec<u8>> {
+        Err(gpu_api_err!(
+            "webgpu does not support this operation, please use async_read_texture instead"
+        ))
+    }
+
+    pub async fn async_read_texture(
+        &mut self,
+        texture_id: TextureId,
+        mip_level: u32,
+        layer: u32,
+        _ext: Option<ReadTextureExt>,
+    ) -> GResult<Vec<u8>> {
+        let texture = self.textures.get(texture_id.id()).ok_or(gpu_api_err!(
+            "webgpu read texture id {:?} does not exist",
+            texture_id
+        ))?;
+        if mip_level >= texture.mip_levels {
+            Err(gpu_api_err!(
+                "webgpu read texture mip level {} >= mip levels {}",
+                mip_level,
+                texture.mip_levels
+            ))?;
+        }
+        if layer >= texture.layer_count() {
+            Err(gpu_api_err!(
+                "webgpu read texture layer {} >= layers {}",
+                layer,
+                texture.layer_count()
+            ))?;
+        }
+
+        let format = texture.original_format;
+        let (block_width, block_height) = format.block_dimensions();
+        let (mip_width, mip_height) = mip_level_extent(texture.width, texture.height, mip_level);
+        let depth = texture.depth.unwrap_or(1);
+        let rows = mip_height.div_ceil(block_height);
+        let row_size = format.row_byte_size(mip_width);
+
+        //  Copies into buffers require rows to be aligned to 256 bytes.
+        let padded_row_size = row_size.next_multiple_of(256);
+        let readable_buffer = self.device.create_buffer(&GpuBufferDescriptor::new(
+            (padded_row_size * rows * depth) as f64,
+            GpuBufferUsageFlags::CopyDst as u32 | GpuBufferUsageFlags::MapRead as u32,
+        ));
+
+        let origin = Array::new();
+        origin.push(&JsValue::from(0));
+        origin.push(&JsValue::from(0));
+        origin.push(&JsValue::from(layer));
+
+        let mut source = GpuImageCopyTexture::new(&texture.texture);
+        source.origin(&origin).mip_level(mip_level);
+
+        let mut destination = GpuImageCopyBuffer::new(&readable_buffer);
+        destination
+            .bytes_per_row(padded_row_size as u32)
+            .rows_per_image(rows as u32);
+
+        let size = Array::new();
+        size.push(&JsValue::from(mip_width.next_multiple_of(block_width)));
+        size.push(&JsValue::from(mip_height.next_multiple_of(block_height)));
+        size.push(&JsValue::from(depth));
+
+        let command_encoder = self.device.create_command_encoder();
+        command_encoder.copy_texture_to_buffer_with_u32_sequence(&source, &destination, &size);
+        let command_buffer = command_encoder.finish();
+        let commands = Array::new();
+        commands.push(&command_buffer);
+        self.device.queue().submit(&commands);
+
+        JsFuture::from(readable_buffer.map_async(GpuMapModeFlags::Read as u32))
+            .await
+            .map_err(|e| {
+                gpu_api_err!("webgpu failed to map buffer in async_read_texture: {:?}", e)
+            })?;
+
+        let mapped_buf = readable_buffer.get_mapped_range();
+        let padded_data = Uint8Array::new(&mapped_buf).to_vec();
+        readable_buffer.unmap();
+        readable_buffer.destroy();
+
+        Ok(padded_data
+            .chunks(padded_row_size)
+            .flat_map(|row| &row[..row_size])
+            .copied()
+            .collect())
+    }
+
+    pub fn async_upload_texture(
+        &mut self,
+        texture: TextureId,
+        data: &[u8],
+        _ext: Option<AsyncUploadTextureExt>,
+    ) -> GResult<UploadId> {
+        let texture_ref = self.textures.get(texture.id()).ok_or(gpu_api_err!(
+            "webgpu async upload texture id {:?} does not exist",
+            texture
+        ))?;
+        let expected_size = texture_ref
+            .original_format
+            .image_byte_size(texture_ref.width, texture_ref.height)
+            * texture_ref.depth.unwrap_or(1);
+        if data.len() != expected_size {
+            Err(gpu_api_err!(
+                "webgpu async upload texture size ({}) != mip level 0 size ({})",
+                data.len(),
+                expected_size
+            ))?;
+        }
+        self.upload_texture(texture, data, None)?;
+        Ok(self.track_upload())
+    }
+
+    pub fn new_texture_view(
+        &mut self,
+        texture_id: TextureId,
+        ext: Option<NewTextureViewExt>,
+    ) -> GResult<TextureViewId> {
+        let ext = ext.unwrap_or_default();
+        if !ext.swizzle.unwrap_or_default().is_identity() {
+            Err(gpu_api_err!("webgpu texture views cannot be swizzled"))?;
+        }
+        let texture = self.textures.get(texture_id.id()).ok_or(gpu_api_err!(
+            "webgpu new texture view texture id {:?} does not exist",
+            texture_id
+        ))?;
+        let range = ext.resolve_range(
+            texture.view_dimension(),
+            texture.mip_levels,
+            texture.layer_count(),
+        )?;
+
+        let view_dimension = match range.dimension {
+            TextureViewDimension::D2 => GpuTextureViewDimension::N2d,
+            TextureViewDimension::D2Array => GpuTextureViewDimension::N2dArray,
+            TextureViewDimension::Cube => GpuTextureViewDimension::Cube,
+            TextureViewDimension::D3 => GpuTextureViewDimension::N3d,
+        };
+        let texture_view = texture.texture.create_view_with_descriptor(
+            GpuTextureViewDescriptor::new()
+                .dimension(view_dimension)
+                .base_mip_level(range.base_mip_level)
+                .mip_level_count(range.mip_level_count)
+                .base_array_layer(range.base_layer)
+                .array_layer_count(range.layer_count),
+        );
+
+        self.texture_views.push(WebGpuTextureView {
+            texture_view,
+            view_dimension,
+            sample_type: texture.get_sample_type(),
+        });
+        Ok(TextureViewId::from_id(self.texture_views.len() - 1))
+    }
+
+    pub fn get_supported_texture_compressions(&self) -> GResult<Vec<TextureCompression>> {
+        let features = self.device.features();
+        Ok(WEBGPU_TEXTURE_COMPRESSION_FEATURES
+            .into_iter()
+            .filter_map(|(feature, compression)| features.has(feature).then_some(compression))
+            .collect())
+    }
+
     pub fn get_texture_max_lod(&self, texture: TextureId) -> GResult<f32> {
         let texture = self.textures.get(texture.id()).ok_or(gpu_api_err!(
             "webgpu get_texture_max_lod texture {:?} does not exist",
@@ -113,9 +359,15 @@ impl WebGpuContext {
 pub struct WebGpuTexture {
     texture: GpuTexture,
     pub texture_view: GpuTextureView,
+    //  Only for textures created with `NewTextureExt::enable_storage`.
+    storage_texture_view: Option<GpuTextureView>,
+    pub storage_view_dimension: GpuTextureViewDimension,
     usage: u32,
     width: usize,
     height: usize,
+    depth: Option<usize>,
+    array_layers: Option<u32>,
+    is_cubemap: bool,
     mip_levels: u32,
     format: GpuTextureFormat,
     original_format: TextureFormat,
@@ -131,14 +383,12 @@ impl WebGpuTexture {
     ) -> Self {
         let mip_levels = if ext.enable_mipmaps.is_some() {
             ext.mip_levels
-                .unwrap_or(std::cmp::max(width, height).ilog2())
+                .unwrap_or(std::cmp::max(width, height).ilog2() + 1)
         } else {
             1
         };
 
-        let texture_format = match format {
-            TextureFormat::Rgba => GpuTextureFormat::Rgba8unorm,
-        };
+        let texture_format = texture_format_into_webgpu(format);
 
         let size = Array::new();
         size.push(&JsValue::from(width));
@@ -147,12 +397,25 @@ impl WebGpuTexture {
         if ext.enable_cubemap.is_some() {
             size.push(&JsValue::from(6));
         }
+        if let Some(depth) = ext.depth {
+            size.push(&JsValue::from(depth));
+        }
+        if let Some(array_layers) = ext.array_layers {
+            size.push(&JsValue::from(array_layers));
+        }
 
-        let usage =
-            GpuTextureUsageFlags::CopyDst as u32 | GpuTextureUsageFlags::TextureBinding as u32;
+        let mut usage = GpuTextureUsageFlags::CopySrc as u32
+            | GpuTextureUsageFlags::CopyDst as u32
+            | GpuTextureUsageFlags::TextureBinding as u32;
+        if ext.enable_storage.is_some() {
+            usage |= GpuTextureUsageFlags::StorageBinding as u32;
+        }
 
         let mut texture_info = GpuTextureDescriptor::new(texture_format, &size, usage);
         texture_info.mip_level_count(mip_levels);
+        if ext.depth.is_some() {
+            texture_info.dimension(GpuTextureDimension::N3d);
+        }
         let texture = device.create_texture(&texture_info);
 
         let mut texture_view_desc = GpuTextureViewDescriptor::new();
@@ -160,19 +423,351 @@ impl WebGpuTexture {
         if ext.enable_cubemap.is_some() {
             texture_view_desc.dimension(GpuTextureViewDimension::Cube);
         }
+        if ext.depth.is_some() {
+            texture_view_desc.dimension(GpuTextureViewDimension::N3d);
+        }
+        if ext.array_layers.is_some() {
+            texture_view_desc.dimension(GpuTextureViewDimension::N2dArray);
+        }
         let texture_view = texture.create_view_with_descriptor(&texture_view_desc);
 
+        //  Storage textures can only be bound with a single mip level, and never as cubemaps.
+        let storage_view_dimension = if ext.depth.is_some() {
+            GpuTextureViewDimension::N3d
+        } else if ext.array_layers.is_some() || ext.enable_cubemap.is_some() {
+            GpuTextureViewDimension::N2dArray
+        } else {
+            GpuTextureViewDimension::N2d
+        };
+        let storage_texture_view = ext.enable_storage.map(|_| {
+            let mut storage_view_desc = GpuTextureViewDescriptor::new();
+            storage_view_desc
+                .dimension(storage_view_dimension)
+                .mip_level_count(1);
+            texture.create_view_with_descriptor(&storage_view_desc)
+        });
+
         WebGpuTexture {
             texture,
             texture_view,
+            storage_texture_view,
+            storage_view_dimension,
             usage,
             width,
             height,
+            depth: ext.depth,
+            array_layers: ext.array_layers,
+            is_cubemap: ext.enable_cubemap.is_some(),
             mip_levels,
             format: texture_format,
             original_format: format,
         }
     }
+
+    pub fn get_storage_texture_view(&self, format: GpuTextureFormat) -> GResult<&GpuTextureView> {
+        if self.format != format {
+            Err(gpu_api_err!(
+                "webgpu storage texture has format {:?}, not {:?}",
+                self.format,
+                format
+            ))?;
+        }
+        self.storage_texture_view.as_ref().ok_or(gpu_api_err!(
+            "webgpu storage texture was not created with enable_storage"
+        ))
+    }
+
+    fn check_upload(&self, ext: &UploadTextureExt) -> GResult<()> {
+        if ext.generate_mipmaps.is_some() && self.original_format.compression().is_some() {
+            Err(gpu_api_err!(
+                "webgpu cannot generate mipmaps for compressed texture format {:?}",
+                self.original_format
+            ))?;
+        }
+        if ext.generate_mipmaps.is_some() && ext.includes_mip_chain.is_some() {
+            Err(gpu_api_err!(
+                "webgpu cannot generate mipmaps for data that already includes the mip chain"
+            ))?;
+        }
+        if ext.generate_mipmaps.is_some() && self.depth.is_some() {
+            Err(gpu_api_err!(
+                "webgpu cannot generate mipmaps for 3d textures"
+            ))?;
+        }
+        Ok(())
+    }
+
+    pub fn write_region(
+        &self,
+        queue: &GpuQueue,
+        region: &TextureRegion,
+        data: &[u8],
+        row_pitch: usize,
+    ) {
+        let format = self.original_format;
+        let (block_width, block_height) = format.block_dimensions();
+
+        //  Compressed copies must cover whole blocks.
+        let size = Array::new();
+        size.push(&JsValue::from(region.width.next_multiple_of(block_width)));
+        size.push(&JsValue::from(region.height.next_multiple_of(block_height)));
+        size.push(&JsValue::from(region.depth));
+
+        let mut layout = GpuImageDataLayout::new();
+        layout
+            .offset(0.0)
+            .bytes_per_row(row_pitch as u32)
+            .rows_per_image(region.height.div_ceil(block_height) as u32);
+
+        //  3D textures have no layers, array textures and cubemaps have no depth.
+        let origin = Array::new();
+        origin.push(&JsValue::from(region.x));
+        origin.push(&JsValue::from(region.y));
+        origin.push(&JsValue::from(if self.depth.is_some() {
+            region.z
+        } else {
+            region.layer as usize
+        }));
+
+        let mut copy = GpuImageCopyTexture::new(&self.texture);
+        copy.origin(&origin).mip_level(region.mip_level);
+
+        queue.write_texture_with_u8_array_and_u32_sequence(&copy, data, &layout, &size);
+    }
+
+    pub fn transfer_texture(&self) -> WebGpuTransferTexture<'_> {
+        WebGpuTransferTexture {
+            texture: &self.texture,
+            format: self.format,
+            width: self.width,
+            height: self.height,
+            depth: self.depth,
+            mip_levels: self.mip_levels,
+            array_layers: self.layer_count(),
+            is_multisampled: false,
+            buffer_aspect: GpuTextureAspect::All,
+            block_dimensions: self.original_format.block_dimensions(),
+            block_byte_size: self.original_format.block_byte_size(),
+        }
+    }
+
+    pub fn view_dimension(&self) -> TextureViewDimension {
+        if self.depth.is_some() {
+            TextureViewDimension::D3
+        } else if self.is_cubemap {
+            TextureViewDimension::Cube
+        } else if self.array_layers.is_some() {
+            TextureViewDimension::D2Array
+        } else {
+            TextureViewDimension::D2
+        }
+    }
+
+    fn layer_count(&self) -> u32 {
+        if self.is_cubemap {
+            6
+        } else {
+            self.array_layers.unwrap_or(1)
+        }
+    }
+
+    //  Returns the row pitch of the data.
+    pub fn check_region(
+        &self,
+        region: &TextureRegion,
+        data_size: usize,
+        row_pitch: Option<usize>,
+    ) -> GResult<usize> {
+        let layers = self.layer_count();
+        let depth = self.depth.unwrap_or(1);
+
+        if region.mip_level >= self.mip_levels {
+            Err(gpu_api_err!(
+                "webgpu upload texture region mip level {} >= mip levels {}",
+                region.mip_level,
+                self.mip_levels
+            ))?;
+        }
+        if region.layer >= layers {
+            Err(gpu_api_err!(
+                "webgpu upload texture region layer {} >= layers {}",
+                region.layer,
+                layers
+            ))?;
+        }
+
+        let (mip_width, mip_height) = mip_level_extent(self.width, self.height, region.mip_level);
+        if region.width == 0
+            || region.height == 0
+            || region.depth == 0
+            || region.x + region.width > mip_width
+            || region.y + region.height > mip_height
+            || region.z + region.depth > depth
+        {
+            Err(gpu_api_err!(
+                "webgpu upload texture region {:?} is outside of mip level extent ({}, {}, {})",
+                region,
+                mip_width,
+                mip_height,
+                depth
+            ))?;
+        }
+
+        let format = self.original_format;
+        let (block_width, block_height) = format.block_dimensions();
+        if !region.x.is_multiple_of(block_width)
+            || !region.y.is_multiple_of(block_height)
+            || (!region.width.is_multiple_of(block_width) && region.x + region.width != mip_width)
+            || (!region.height.is_multiple_of(block_height)
+                && region.y + region.height != mip_height)
+        {
+            Err(gpu_api_err!(
+                "webgpu upload texture region {:?} is not aligned to {:?} blocks",
+                region,
+                format
+            ))?;
+        }
+
+        let row_size = format.row_byte_size(region.width);
+        let row_pitch = row_pitch.unwrap_or(row_size);
+        if row_pitch < row_size {
+            Err(gpu_api_err!(
+                "webgpu upload texture region row pitch {} is invalid for rows of {} bytes",
+                row_pitch,
+                row_size
+            ))?;
+        }
+
+        let rows = region.height.div_ceil(block_height) * region.depth;
+        let expected_size = row_pitch * (rows - 1) + row_size;
+        if data_size < expected_size {
+            Err(gpu_api_err!(
+                "webgpu upload texture region data size ({}) < expected size ({})",
+                data_size,
+                expected_size
+            ))?;
+        }
+
+        Ok(row_pitch)
+    }
+
+    //  Writes one layer, and optionally its mip chain.
+    fn write_layer(
+        &self,
+        queue: &GpuQueue,
+        data: &[u8],
+        layer: usize,
+        ext: &UploadTextureExt,
+    ) -> GResult<()> {
+        let format = self.original_format;
+        let mip_levels = if ext.includes_mip_chain.is_some() {
+            self.mip_levels
+        } else {
+            1
+        };
+
+        let slices = self.depth.unwrap_or(1);
+        let expected_size =
+            format.mip_chain_byte_size(self.width, self.height, mip_levels) * slices;
+        if data.len() < expected_size {
+            Err(gpu_api_err!(
+                "webgpu upload texture data size ({}) < expected size ({})",
+                data.len(),
+                expected_size
+            ))?;
+        }
+
+        let (block_width, block_height) = format.block_dimensions();
+        let mut offset = 0;
+        for mip_level in 0..mip_levels {
+            let (mip_width, mip_height) = mip_level_extent(self.width, self.height, mip_level);
+
+            //  Compressed copies must cover whole blocks.
+            let size = Array::new();
+            size.push(&JsValue::from(mip_width.next_multiple_of(block_width)));
+            size.push(&JsValue::from(mip_height.next_multiple_of(block_height)));
+            size.push(&JsValue::from(slices));
+
+            let mut layout = GpuImageDataLayout::new();
+            layout
+                .offset(offset as f64)
+                .bytes_per_row(format.row_byte_size(mip_width) as u32)
+                .rows_per_image(mip_height.div_ceil(block_height) as u32);
+
+            let origin = Array::new();
+            origin.push(&JsValue::from(0));
+            origin.push(&JsValue::from(0));
+            origin.push(&JsValue::from(layer));
+
+            let mut copy = GpuImageCopyTexture::new(&self.texture);
+            copy.origin(&origin).mip_level(mip_level);
+
+            queue.write_texture_with_u8_array_and_u32_sequence(&copy, data, &layout, &size);
+
+            offset += format.image_byte_size(mip_width, mip_height) * slices;
+        }
+
+        Ok(())
+    }
+
+    pub fn get_sample_type(&self) -> GpuTextureSampleType {
+        match self.original_format {
+            TextureFormat::R32F | TextureFormat::Rgba32F => GpuTextureSampleType::UnfilterableFloat,
+            _ => GpuTextureSampleType::Float,
+        }
+    }
+}
+
+//  32 bit float formats are sampled as `UnfilterableFloat`.
+pub fn is_filterable_format(format: GpuTextureFormat) -> bool {
+    !matches!(
+        format,
+        GpuTextureFormat::R32float | GpuTextureFormat::Rg32float | GpuTextureFormat::Rgba32float
+    )
+}
+
+fn texture_format_into_webgpu(format: TextureFormat) -> GpuTextureFormat {
+    match format {
+        TextureFormat::Rgba => GpuTextureFormat::Rgba8unorm,
+        TextureFormat::Rgba8Srgb => GpuTextureFormat::Rgba8unormSrgb,
+        TextureFormat::Bgra8 => GpuTextureFormat::Bgra8unorm,
+        TextureFormat::R8 => GpuTextureFormat::R8unorm,
+        TextureFormat::Rg8 => GpuTextureFormat::Rg8unorm,
+        TextureFormat::R16F => GpuTextureFormat::R16float,
+        TextureFormat::Rgba16F => GpuTextureFormat::Rgba16float,
+        TextureFormat::R32F => GpuTextureFormat::R32float,
+        TextureFormat::Rgba32F => GpuTextureFormat::Rgba32float,
+        TextureFormat::Bc1Rgba => GpuTextureFormat::Bc1RgbaUnorm,
+        TextureFormat::Bc1RgbaSrgb => GpuTextureFormat::Bc1RgbaUnormSrgb,
+        TextureFormat::Bc3Rgba => GpuTextureFormat::Bc3RgbaUnorm,
+        TextureFormat::Bc3RgbaSrgb => GpuTextureFormat::Bc3RgbaUnormSrgb,
+        TextureFormat::Bc5Rg => GpuTextureFormat::Bc5RgUnorm,
+        TextureFormat::Bc7Rgba => GpuTextureFormat::Bc7RgbaUnorm,
+        TextureFormat::Bc7RgbaSrgb => GpuTextureFormat::Bc7RgbaUnormSrgb,
+        TextureFormat::Etc2Rgb8 => GpuTextureFormat::Etc2Rgb8unorm,
+        TextureFormat::Etc2Rgb8Srgb => GpuTextureFormat::Etc2Rgb8unormSrgb,
+        TextureFormat::Etc2Rgba8 => GpuTextureFormat::Etc2Rgba8unorm,
+        TextureFormat::Etc2Rgba8Srgb => GpuTextureFormat::Etc2Rgba8unormSrgb,
+        TextureFormat::Astc4x4 => GpuTextureFormat::Astc4x4Unorm,
+        TextureFormat::Astc4x4Srgb => GpuTextureFormat::Astc4x4UnormSrgb,
+    }
+}
+
+//  The formats of `ShaderStorageTextureFormat`.
+pub fn is_storage_format(format: GpuTextureFormat) -> bool {
+    matches!(
+        format,
+        GpuTextureFormat::Rgba8unorm
+            | GpuTextureFormat::Rgba16float
+            | GpuTextureFormat::R32float
+            | GpuTextureFormat::Rgba32float
+    )
+}
+
+pub struct WebGpuTextureView {
+    pub texture_view: GpuTextureView,
+    pub view_dimension: GpuTextureViewDimension,
+    pub sample_type: GpuTextureSampleType,
 }
 
 //  Derived from https://github.com/toji/web-texture-tool/blob/main/src/webgpu-mipmap-generator.js
@@ -181,7 +776,10 @@ pub struct WebGpuMipmapStateCache {
     sampler: GpuSampler,
     module: GpuShaderModule,
     pipeline_layout: GpuPipelineLayout,
-    pipelines: HashMap<TextureFormat, GpuRenderPipeline>,
+    //  `GpuTextureFormat` cannot be hashed.
+    pipelines: Vec<(GpuTextureFormat, GpuRenderPipeline)>,
+    //  Destroyed once the submit that uses them has been queued.
+    temporary_textures: Vec<GpuTexture>,
 }
 
 impl WebGpuMipmapStateCache {
@@ -239,61 +837,87 @@ impl WebGpuMipmapStateCache {
             module,
             sampler,
             pipeline_layout,
-            pipelines: HashMap::new(),
+            pipelines: vec![],
+            temporary_textures: vec![],
         }
     }
 
-    pub fn generate_mipmap(&mut self, device: &GpuDevice, texture: &WebGpuTexture) {
+    pub fn destroy_temporaries(&mut self) {
+        self.temporary_textures
+            .drain(..)
+            .for_each(|texture| texture.destroy());
+    }
+
+    pub fn generate_mipmap(
+        &mut self,
+        device: &GpuDevice,
+        texture: &WebGpuTexture,
+        layer: u32,
+    ) -> GResult<()> {
+        let command_encoder = device.create_command_encoder();
+        self.record_mipmaps(device, &command_encoder, &texture.transfer_texture(), layer)?;
+
+        let submits = Array::new();
+        submits.push(&command_encoder.finish());
+        device.queue().submit(&submits);
+        self.destroy_temporaries();
+        Ok(())
+    }
+
+    //  Each mip level is rendered from the one before it into a temporary texture,
+    //  then every mip level is copied back into `texture`.
+    pub fn record_mipmaps(
+        &mut self,
+        device: &GpuDevice,
+        command_encoder: &GpuCommandEncoder,
+        texture: &WebGpuTransferTexture,
+        layer: u32,
+    ) -> GResult<()> {
+        //  Mip levels are rendered with a filtering sampler.
+        if !is_filterable_format(texture.format) {
+            Err(gpu_api_err!(
+                "webgpu generate mipmaps format {:?} cannot be filtered",
+                texture.format
+            ))?;
+        }
+
+        //  Non-power-of-two sizes round down, but never below one pixel.
+        let (mip_width, mip_height) = mip_level_extent(texture.width, texture.height, 1);
         let size = Array::new();
-        size.push(&JsValue::from(texture.width / 2));
-        size.push(&JsValue::from(texture.height / 2));
+        size.push(&JsValue::from(mip_width as u32));
+        size.push(&JsValue::from(mip_height as u32));
 
         let mip_texture = device.create_texture(
             GpuTextureDescriptor::new(
                 texture.format,
                 &size,
-                texture.usage
+                GpuTextureUsageFlags::TextureBinding as u32
                     | GpuTextureUsageFlags::RenderAttachment as u32
                     | GpuTextureUsageFlags::CopySrc as u32,
             )
             .mip_level_count(texture.mip_levels - 1),
         );
 
-        let pipeline = if let Some(pipeline) = self.pipelines.get(&texture.original_format) {
-            pipeline.clone()
-        } else {
-            let fragment_targets = Array::new();
-            fragment_targets.push(&GpuColorTargetState::new(texture.format));
-
-            let mut fragment_state = GpuFragmentState::new(&self.module, &fragment_targets);
-            fragment_state.entry_point("fragmentMain");
+        let pipeline = self.get_pipeline(device, texture.format);
 
-            let pipeline = device.create_render_pipeline(
-                GpuRenderPipelineDescriptor::new(
-                    &self.pipeline_layout,
-                    &GpuVertexState::new(&self.module),
+        for i in 1..texture.mip_levels {
+            //  Only the first mip level lives in `texture`, the rest are in the temporary texture.
+            let src_view = if i == 1 {
+                texture.texture.create_view_with_descriptor(
+                    GpuTextureViewDescriptor::new()
+                        .dimension(GpuTextureViewDimension::N2d)
+                        .base_mip_level(0)
+                        .mip_level_count(1)
+                        .base_array_layer(layer)
+                        .array_layer_count(1),
                 )
-                .primitive(
-                    GpuPrimitiveState::new()
-                        .topology(GpuPrimitiveTopology::TriangleStrip)
-                        .strip_index_format(GpuIndexFormat::Uint32),
+            } else {
+                mip_texture.create_view_with_descriptor(
+                    GpuTextureViewDescriptor::new()
+                        .base_mip_level(i - 2)
+                        .mip_level_count(1),
                 )
-                .fragment(&fragment_state),
-            );
-            self.pipelines
-                .insert(texture.original_format, pipeline.clone());
-            pipeline
-        };
-
-        let src_view = texture.texture.create_view_with_descriptor(
-            GpuTextureViewDescriptor::new()
-                .base_mip_level(0)
-                .mip_level_count(1),
-        );
-
-        let command_encoder = device.create_command_encoder();
-
-        for i in 1..texture.mip_levels {
+            };
             let dst_view = mip_texture.create_view_with_descriptor(
                 GpuTextureViewDescriptor::new()
                     .base_mip_level(i - 1)
@@ -319,25 +943,707 @@ impl WebGpuMipmapStateCache {
 
             pass_encoder.set_pipeline(&pipeline);
             pass_encoder.set_bind_group(0, Some(&bind_group));
-            pass_encoder.draw(4);
+            pass_encoder.draw(3);
             pass_encoder.end();
         }
 
-        let mut mip_level_width = texture.width / 2;
-        let mut mip_level_height = texture.height / 2;
+        let layer_origin = Array::new();
+        layer_origin.push(&JsValue::from(0));
+        layer_origin.push(&JsValue::from(0));
+        layer_origin.push(&JsValue::from(layer));
+
         for i in 1..texture.mip_levels {
+            let (mip_level_width, mip_level_height) =
+                mip_level_extent(texture.width, texture.height, i);
             command_encoder.copy_texture_to_texture_with_gpu_extent_3d_dict(
                 GpuImageCopyTexture::new(&mip_texture).mip_level(i - 1),
-                GpuImageCopyTexture::new(&texture.texture).mip_level(i),
+                GpuImageCopyTexture::new(texture.texture)
+                    .mip_level(i)
+                    .origin(&layer_origin),
                 GpuExtent3dDict::new(mip_level_width as u32).height(mip_level_height as u32),
             );
+        }
+        self.temporary_textures.push(mip_texture);
+        Ok(())
+    }
 
-            mip_level_width /= 2;
-            mip_level_height /= 2;
+    fn get_pipeline(&mut self, device: &GpuDevice, format: GpuTextureFormat) -> GpuRenderPipeline {
+        if let Some((_, pipeline)) = self
+            .pipelines
+            .iter()
+            .find(|(pipeline_format, _)| *pipeline_format == format)
+        {
+            return pipeline.clone();
         }
 
-        let submits = Array::new();
-        submits.push(&command_encoder.finish());
-        device.queue().submit(&submits);
+        let fragment_targets = Array::new();
+        fragment_targets.push(&GpuColorTargetState::new(format));
+
+        let mut fragment_state = GpuFragmentState::new(&self.module, &fragment_targets);
+        fragment_state.entry_point("fragmentMain");
+
+        let pipeline = device.create_render_pipeline(
+            GpuRenderPipelineDescriptor::new(
+                &self.pipeline_layout,
+                &GpuVertexState::new(&self.module),
+            )
+            .primitive(GpuPrimitiveState::new().topology(GpuPrimitiveTopology::TriangleList))
+            .fragment(&fragment_state),
+        );
+        self.pipelines.push((format, pipeline.clone()));
+        pipeline
+    }
+}
+
+//  A texture or attachment image as seen by `Submit::copy_texture` and `Submit::blit_texture`.
+pub struct WebGpuTransferTexture<'a> {
+    pub texture: &'a GpuTexture,
+    pub format: GpuTextureFormat,
+    pub width: usize,
+    pub height: usize,
+    //  Only for 3D textures.
+    pub depth: Option<usize>,
+    pub mip_levels: u32,
+    pub array_layers: u32,
+    pub is_multisampled: bool,
+    //  The single aspect copied to and from buffers.
+    pub buffer_aspect: GpuTextureAspect,
+    pub block_dimensions: (usize, usize),
+    pub block_byte_size: usize,
+}
+
+impl WebGpuTransferTexture<'_> {
+    fn check_region(&self, region: &TextureRegion) -> GResult<()> {
+        if region.mip_level >= self.mip_levels || region.layer >= self.array_layers {
+            Err(gpu_api_err!(
+                "webgpu texture copy region {:?} is out of range for {} mip levels and {} layers",
+                region,
+                self.mip_levels,
+                self.array_layers
+            ))?;
+        }
+        let (mip_width, mip_height) = mip_level_extent(self.width, self.height, region.mip_level);
+        if region.width == 0
+            || region.height == 0
+            || region.depth == 0
+            || region.x + region.width > mip_width
+            || region.y + region.height > mip_height
+            || region.z + region.depth > self.depth.unwrap_or(1)
+        {
+            Err(gpu_api_err!(
+                "webgpu texture copy region {:?} is outside of mip level extent ({}, {}, {})",
+                region,
+                mip_width,
+                mip_height,
+                self.depth.unwrap_or(1)
+            ))?;
+        }
+        Ok(())
+    }
+
+    //  Returns the row pitch of the buffer data.
+    fn check_buffer_region(
+        &self,
+        region: &TextureRegion,
+        buffer_size: usize,
+        ext: &CopyBufferTextureExt,
+    ) -> GResult<usize> {
+        self.check_region(region)?;
+
+        let (mip_width, mip_height) = mip_level_extent(self.width, self.height, region.mip_level);
+        let (block_width, block_height) = self.block_dimensions;
+        if !region.x.is_multiple_of(block_width)
+            || !region.y.is_multiple_of(block_height)
+            || (!region.width.is_multiple_of(block_width) && region.x + region.width != mip_width)
+            || (!region.height.is_multiple_of(block_height)
+                && region.y + region.height != mip_height)
+        {
+            Err(gpu_api_err!(
+                "webgpu buffer texture copy region {:?} is not aligned to {:?} blocks",
+                region,
+                self.block_dimensions
+            ))?;
+        }
+
+        let buffer_offset = ext.buffer_offset.unwrap_or(0);
+        //  Depth and stencil copies additionally require 4 byte aligned offsets.
+        if !buffer_offset.is_multiple_of(self.block_byte_size)
+            || (self.buffer_aspect != GpuTextureAspect::All && !buffer_offset.is_multiple_of(4))
+        {
+            Err(gpu_api_err!(
+                "webgpu buffer texture copy offset {} is not aligned to {} bytes",
+                buffer_offset,
+                self.block_byte_size
+            ))?;
+        }
+
+        let row_size = region.width.div_ceil(block_width) * self.block_byte_size;
+        let rows = region.height.div_ceil(block_height) * region.depth;
+        let row_pitch = ext.row_pitch.unwrap_or(row_size);
+        if row_pitch < row_size || (rows > 1 && !row_pitch.is_multiple_of(256)) {
+            Err(gpu_api_err!(
+                "webgpu buffer texture copy row pitch {} is invalid for rows of {} bytes, multiple rows require a multiple of 256",
+                row_pitch,
+                row_size
+            ))?;
+        }
+
+        let expected_size = buffer_offset + row_pitch * (rows - 1) + row_size;
+        if buffer_size < expected_size {
+            Err(gpu_api_err!(
+                "webgpu buffer texture copy buffer size ({}) < expected size ({})",
+                buffer_size,
+                expected_size
+            ))?;
+        }
+
+        Ok(row_pitch)
+    }
+
+    fn image_copy_texture(&self, region: &TextureRegion) -> GpuImageCopyTexture {
+        //  3D textures have no layers, array textures and cubemaps have no depth.
+        let origin = Array::new();
+        origin.push(&JsValue::from(region.x));
+        origin.push(&JsValue::from(region.y));
+        origin.push(&JsValue::from(if self.depth.is_some() {
+            region.z
+        } else {
+            region.layer as usize
+        }));
+
+        let mut copy = GpuImageCopyTexture::new(self.texture);
+        copy.origin(&origin).mip_level(region.mip_level);
+        copy
+    }
+}
+
+pub fn submit_texture_copy(
+    context: &mut WebGpuContext,
+    copy: &TextureCopy,
+    command_encoder: &GpuCommandEncoder,
+) -> GResult<()> {
+    fn get_transfer_texture(
+        context: &WebGpuContext,
+        texture: GenericTextureId,
+    ) -> GResult<WebGpuTransferTexture<'_>> {
+        Ok(match texture {
+            GenericTextureId::Texture(texture_id) => context
+                .textures
+                .get(texture_id.id())
+                .ok_or(gpu_api_err!(
+                    "webgpu texture copy texture id {:?} does not exist",
+                    texture_id
+                ))?
+                .transfer_texture(),
+            GenericTextureId::AttachmentImage(attachment_image_id) => context
+                .attachment_images
+                .get(attachment_image_id.id())
+                .ok_or(gpu_api_err!(
+                    "webgpu texture copy attachment image id {:?} does not exist",
+                    attachment_image_id
+                ))?
+                .transfer_texture(),
+        })
+    }
+
+    //  Blit pipelines are created before borrowing the textures.
+    let blit_pipeline = if let Some(filter) = copy.blit_filter {
+        let dst = get_transfer_texture(context, copy.dst)?;
+        //  Blits render into a temporary texture of the destination format.
+        if dst.buffer_aspect != GpuTextureAspect::All || dst.block_dimensions != (1, 1) {
+            Err(gpu_api_err!(
+                "webgpu texture blit to {:?} cannot render to {:?}",
+                copy.dst,
+                dst.format
+            ))?;
+        }
+        let dst_format = dst.format;
+        Some(
+            context
+                .blit_state_cache
+                .get_pipeline(&context.device, dst_format, filter),
+        )
+    } else {
+        None
+    };
+
+    let src = get_transfer_texture(context, copy.src)?;
+    let dst = get_transfer_texture(context, copy.dst)?;
+    src.check_region(&copy.src_region)?;
+    dst.check_region(&copy.dst_region)?;
+
+    if src.is_multisampled || dst.is_multisampled {
+        Err(gpu_api_err!(
+            "webgpu texture copy from {:?} to {:?} cannot be multisampled",
+            copy.src,
+            copy.dst
+        ))?;
+    }
+    if copy.src == copy.dst
+        && copy.src_region.mip_level == copy.dst_region.mip_level
+        && copy.src_region.layer == copy.dst_region.layer
+    {
+        Err(gpu_api_err!(
+            "webgpu texture copy from {:?} cannot use the same mip level and layer as its destination",
+            copy.src
+        ))?;
+    }
+
+    if copy.blit_filter.is_some() && src.buffer_aspect != GpuTextureAspect::All {
+        Err(gpu_api_err!(
+            "webgpu texture blit from {:?} cannot sample {:?}",
+            copy.src,
+            src.format
+        ))?;
+    }
+    if copy.blit_filter == Some(SamplerFilter::Linear) && !is_filterable_format(src.format) {
+        Err(gpu_api_err!(
+            "webgpu texture blit from {:?} cannot linearly filter {:?}",
+            copy.src,
+            src.format
+        ))?;
+    }
+
+    match (copy.blit_filter, blit_pipeline) {
+        (Some(filter), Some(blit_pipeline)) => {
+            let (blit_texture, region_buffer) = context.blit_state_cache.blit(
+                &context.device,
+                command_encoder,
+                &blit_pipeline,
+                (&src, &copy.src_region),
+                (&dst, &copy.dst_region),
+                filter,
+            )?;
+            let blit_state_cache = &mut context.blit_state_cache;
+            blit_state_cache.temporary_textures.push(blit_texture);
+            blit_state_cache.temporary_buffers.push(region_buffer);
+            Ok(())
+        }
+        _ => {
+            if src.format != dst.format {
+                Err(gpu_api_err!(
+                    "webgpu texture copy from {:?} to {:?} requires the same format, got {:?} and {:?}",
+                    copy.src,
+                    copy.dst,
+                    src.format,
+                    dst.format
+                ))?;
+            }
+            if (
+                copy.src_region.width,
+                copy.src_region.height,
+                copy.src_region.depth,
+            ) != (
+                copy.dst_region.width,
+                copy.dst_region.height,
+                copy.dst_region.depth,
+            ) {
+                Err(gpu_api_err!(
+                    "webgpu texture copy regions {:?} and {:?} are not the same size",
+                    copy.src_region,
+                    copy.dst_region
+                ))?;
+            }
+
+            let size = Array::new();
+            size.push(&JsValue::from(copy.src_region.width));
+            size.push(&JsValue::from(copy.src_region.height));
+            size.push(&JsValue::from(copy.src_region.depth));
+
+            command_encoder.copy_texture_to_texture_with_u32_sequence(
+                &src.image_copy_texture(&copy.src_region),
+                &dst.image_copy_texture(&copy.dst_region),
+                &size,
+            );
+            Ok(())
+        }
+    }
+}
+
+pub fn submit_buffer_texture_copy(
+    context: &WebGpuContext,
+    copy: &BufferTextureCopy,
+    command_encoder: &GpuCommandEncoder,
+) -> GResult<()> {
+    let buffer = context.get_buffer_with_usage(
+        copy.buffer,
+        if copy.to_texture {
+            BufferUsage::COPY_SRC
+        } else {
+            BufferUsage::COPY_DST
+        },
+    )?;
+    let texture = match copy.texture {
+        GenericTextureId::Texture(texture_id) => context
+            .textures
+            .get(texture_id.id())
+            .ok_or(gpu_api_err!(
+                "webgpu buffer texture copy texture id {:?} does not exist",
+                texture_id
+            ))?
+            .transfer_texture(),
+        GenericTextureId::AttachmentImage(attachment_image_id) => context
+            .attachment_images
+            .get(attachment_image_id.id())
+            .ok_or(gpu_api_err!(
+                "webgpu buffer texture copy attachment image id {:?} does not exist",
+                attachment_image_id
+            ))?
+            .transfer_texture(),
+    };
+    let row_pitch = texture.check_buffer_region(&copy.region, buffer.size as usize, &copy.ext)?;
+
+    if texture.is_multisampled {
+        Err(gpu_api_err!(
+            "webgpu buffer texture copy {:?} cannot be multisampled",
+            copy.texture
+        ))?;
+    }
+    //  WebGpu cannot copy packed depth at all, and cannot copy float depth from buffers.
+    if texture.format == GpuTextureFormat::Depth24plusStencil8
+        || (copy.to_texture
+            && matches!(
+                texture.format,
+                GpuTextureFormat::Depth32float | GpuTextureFormat::Depth32floatStencil8
+            ))
+    {
+        Err(gpu_api_err!(
+            "webgpu buffer texture copy {:?} uses {:?} which cannot be copied {}",
+            copy.texture,
+            texture.format,
+            if copy.to_texture { "into" } else { "from" }
+        ))?;
+    }
+
+    let (block_width, block_height) = texture.block_dimensions;
+    let mut image_copy_buffer = GpuImageCopyBuffer::new(&buffer.buffer);
+    image_copy_buffer
+        .offset(copy.ext.buffer_offset.unwrap_or(0) as f64)
+        .bytes_per_row(row_pitch as u32)
+        .rows_per_image(copy.region.height.div_ceil(block_height) as u32);
+
+    let mut image_copy_texture = texture.image_copy_texture(&copy.region);
+    image_copy_texture.aspect(texture.buffer_aspect);
+
+    //  Compressed copies must cover whole blocks.
+    let size = Array::new();
+    size.push(&JsValue::from(
+        copy.region.width.next_multiple_of(block_width),
+    ));
+    size.push(&JsValue::from(
+        copy.region.height.next_multiple_of(block_height),
+    ));
+    size.push(&JsValue::from(copy.region.depth));
+
+    if copy.to_texture {
+        command_encoder.copy_buffer_to_texture_with_u32_sequence(
+            &image_copy_buffer,
+            &image_copy_texture,
+            &size,
+        );
+    } else {
+        command_encoder.copy_texture_to_buffer_with_u32_sequence(
+            &image_copy_texture,
+            &image_copy_buffer,
+            &size,
+        );
+    }
+    Ok(())
+}
+
+pub fn submit_generate_mipmaps(
+    context: &mut WebGpuContext,
+    texture: GenericTextureId,
+    command_encoder: &GpuCommandEncoder,
+) -> GResult<()> {
+    let transfer_texture = match texture {
+        GenericTextureId::Texture(texture_id) => context
+            .textures
+            .get(texture_id.id())
+            .ok_or(gpu_api_err!(
+                "webgpu generate mipmaps texture id {:?} does not exist",
+                texture_id
+            ))?
+            .transfer_texture(),
+        GenericTextureId::AttachmentImage(attachment_image_id) => context
+            .attachment_images
+            .get(attachment_image_id.id())
+            .ok_or(gpu_api_err!(
+                "webgpu generate mipmaps attachment image id {:?} does not exist",
+                attachment_image_id
+            ))?
+            .transfer_texture(),
+    };
+    if transfer_texture.mip_levels < 2 {
+        Err(gpu_api_err!(
+            "webgpu generate mipmaps {:?} has no mip levels to generate",
+            texture
+        ))?;
+    }
+    if transfer_texture.buffer_aspect != GpuTextureAspect::All
+        || transfer_texture.is_multisampled
+        || transfer_texture.block_dimensions != (1, 1)
+        || transfer_texture.depth.is_some()
+    {
+        Err(gpu_api_err!(
+            "webgpu generate mipmaps {:?} requires an uncompressed 2d color image without msaa",
+            texture
+        ))?;
+    }
+
+    for layer in 0..transfer_texture.array_layers {
+        context.mipmap_state_cache.record_mipmaps(
+            &context.device,
+            command_encoder,
+            &transfer_texture,
+            layer,
+        )?;
+    }
+    Ok(())
+}
+
+//  Blits are rendered into a temporary texture which is then copied into the destination region.
+//  This way, the destination does not need to be renderable.
+pub struct WebGpuBlitStateCache {
+    nearest_sampler: GpuSampler,
+    linear_sampler: GpuSampler,
+    module: GpuShaderModule,
+    nearest_pipeline_layout: GpuPipelineLayout,
+    linear_pipeline_layout: GpuPipelineLayout,
+    //  `GpuTextureFormat` cannot be hashed.
+    pipelines: Vec<(GpuTextureFormat, SamplerFilter, GpuRenderPipeline)>,
+    //  Destroyed once the submit that uses them has been queued.
+    temporary_textures: Vec<GpuTexture>,
+    temporary_buffers: Vec<GpuBuffer>,
+}
+
+impl WebGpuBlitStateCache {
+    pub fn new(device: &GpuDevice) -> Self {
+        let code = r#"
+            var<private> pos : array<vec2<f32>, 3> = array<vec2<f32>, 3>(
+              vec2<f32>(-1.0, -1.0), vec2<f32>(-1.0, 3.0), vec2<f32>(3.0, -1.0));
+
+            struct BlitRegion {
+              offset : vec2<f32>,
+              scale : vec2<f32>,
+            };
+
+            struct VertexOutput {
+              @builtin(position) position : vec4<f32>,
+              @location(0) texCoord : vec2<f32>,
+            };
+
+            @group(0) @binding(2) var<uniform> region : BlitRegion;
+
+            @vertex
+            fn vertexMain(@builtin(vertex_index) vertexIndex : u32) -> VertexOutput {
+              var output : VertexOutput;
+              let uv = pos[vertexIndex] * vec2<f32>(0.5, -0.5) + vec2<f32>(0.5);
+              output.texCoord = region.offset + uv * region.scale;
+              output.position = vec4<f32>(pos[vertexIndex], 0.0, 1.0);
+              return output;
+            }
+
+            @group(0) @binding(0) var imgSampler : sampler;
+            @group(0) @binding(1) var img : texture_2d<f32>;
+
+            @fragment
+            fn fragmentMain(@location(0) texCoord : vec2<f32>) -> @location(0) vec4<f32> {
+              return textureSample(img, imgSampler, texCoord);
+            }"#;
+
+        let module = device.create_shader_module(&GpuShaderModuleDescriptor::new(code));
+
+        //  Nearest blits sample the source as unfilterable so that formats like r32float work.
+        let create_pipeline_layout = |filter: SamplerFilter| {
+            let mut sampler_layout = GpuSamplerBindingLayout::new();
+            let mut texture_layout = GpuTextureBindingLayout::new();
+            if filter == SamplerFilter::Nearest {
+                sampler_layout.type_(GpuSamplerBindingType::NonFiltering);
+                texture_layout.sample_type(GpuTextureSampleType::UnfilterableFloat);
+            }
+
+            let pipeline_layout_entries = Array::new();
+            pipeline_layout_entries.push(
+                GpuBindGroupLayoutEntry::new(0, GpuShaderStageFlags::Fragment as u32)
+                    .sampler(&sampler_layout),
+            );
+            pipeline_layout_entries.push(
+                GpuBindGroupLayoutEntry::new(1, GpuShaderStageFlags::Fragment as u32)
+                    .texture(&texture_layout),
+            );
+            pipeline_layout_entries.push(
+                GpuBindGroupLayoutEntry::new(2, GpuShaderStageFlags::Vertex as u32)
+                    .buffer(&GpuBufferBindingLayout::new()),
+            );
+
+            let bind_group_layouts = Array::new();
+            bind_group_layouts.push(&device.create_bind_group_layout(
+                &GpuBindGroupLayoutDescriptor::new(&pipeline_layout_entries),
+            ));
+
+            device.create_pipeline_layout(&GpuPipelineLayoutDescriptor::new(&bind_group_layouts))
+        };
+        let nearest_pipeline_layout = create_pipeline_layout(SamplerFilter::Nearest);
+        let linear_pipeline_layout = create_pipeline_layout(SamplerFilter::Linear);
+
+        let nearest_sampler = device.create_sampler_with_descriptor(
+            GpuSamplerDescriptor::new()
+                .min_filter(GpuFilterMode::Nearest)
+                .mag_filter(GpuFilterMode::Nearest),
+        );
+        let linear_sampler = device.create_sampler_with_descriptor(
+            GpuSamplerDescriptor::new()
+                .min_filter(GpuFilterMode::Linear)
+                .mag_filter(GpuFilterMode::Linear),
+        );
+
+        WebGpuBlitStateCache {
+            nearest_sampler,
+            linear_sampler,
+            module,
+            nearest_pipeline_layout,
+            linear_pipeline_layout,
+            pipelines: vec![],
+            temporary_textures: vec![],
+            temporary_buffers: vec![],
+        }
+    }
+
+    pub fn destroy_temporaries(&mut self) {
+        self.temporary_textures
+            .drain(..)
+            .for_each(|texture| texture.destroy());
+        self.temporary_buffers
+            .drain(..)
+            .for_each(|buffer| buffer.destroy());
+    }
+
+    fn get_pipeline(
+        &mut self,
+        device: &GpuDevice,
+        format: GpuTextureFormat,
+        filter: SamplerFilter,
+    ) -> GpuRenderPipeline {
+        if let Some((_, _, pipeline)) =
+            self.pipelines
+                .iter()
+                .find(|(pipeline_format, pipeline_filter, _)| {
+                    *pipeline_format == format && *pipeline_filter == filter
+                })
+        {
+            return pipeline.clone();
+        }
+
+        let fragment_targets = Array::new();
+        fragment_targets.push(&GpuColorTargetState::new(format));
+
+        let mut fragment_state = GpuFragmentState::new(&self.module, &fragment_targets);
+        fragment_state.entry_point("fragmentMain");
+
+        let mut vertex_state = GpuVertexState::new(&self.module);
+        vertex_state.entry_point("vertexMain");
+
+        let pipeline_layout = match filter {
+            SamplerFilter::Nearest => &self.nearest_pipeline_layout,
+            SamplerFilter::Linear => &self.linear_pipeline_layout,
+        };
+        let pipeline = device.create_render_pipeline(
+            GpuRenderPipelineDescriptor::new(pipeline_layout, &vertex_state)
+                .primitive(GpuPrimitiveState::new().topology(GpuPrimitiveTopology::TriangleList))
+                .fragment(&fragment_state),
+        );
+        self.pipelines.push((format, filter, pipeline.clone()));
+        pipeline
+    }
+
+    //  Returns the temporary texture and region buffer to destroy after submitting.
+    fn blit(
+        &self,
+        device: &GpuDevice,
+        command_encoder: &GpuCommandEncoder,
+        pipeline: &GpuRenderPipeline,
+        (src, src_region): (&WebGpuTransferTexture, &TextureRegion),
+        (dst, dst_region): (&WebGpuTransferTexture, &TextureRegion),
+        filter: SamplerFilter,
+    ) -> GResult<(GpuTexture, GpuBuffer)> {
+        if src.depth.is_some() || dst.depth.is_some() {
+            Err(gpu_api_err!(
+                "webgpu texture blit does not support 3d textures"
+            ))?;
+        }
+
+        let src_view = src.texture.create_view_with_descriptor(
+            GpuTextureViewDescriptor::new()
+                .dimension(GpuTextureViewDimension::N2d)
+                .base_mip_level(src_region.mip_level)
+                .mip_level_count(1)
+                .base_array_layer(src_region.layer)
+                .array_layer_count(1),
+        );
+
+        let (mip_width, mip_height) = mip_level_extent(src.width, src.height, src_region.mip_level);
+        let blit_region = [
+            src_region.x as f32 / mip_width as f32,
+            src_region.y as f32 / mip_height as f32,
+            src_region.width as f32 / mip_width as f32,
+            src_region.height as f32 / mip_height as f32,
+        ];
+        let mut region_buffer_info = GpuBufferDescriptor::new(
+            std::mem::size_of_val(&blit_region) as f64,
+            GpuBufferUsageFlags::Uniform as u32,
+        );
+        region_buffer_info.mapped_at_creation(true);
+        let region_buffer = device.create_buffer(&region_buffer_info);
+        Float32Array::new(&region_buffer.get_mapped_range().into()).copy_from(&blit_region);
+        region_buffer.unmap();
+
+        let size = Array::new();
+        size.push(&JsValue::from(dst_region.width));
+        size.push(&JsValue::from(dst_region.height));
+        let blit_texture = device.create_texture(&GpuTextureDescriptor::new(
+            dst.format,
+            &size,
+            GpuTextureUsageFlags::RenderAttachment as u32 | GpuTextureUsageFlags::CopySrc as u32,
+        ));
+
+        let color_attachments = Array::new();
+        color_attachments.push(&GpuRenderPassColorAttachment::new(
+            GpuLoadOp::Clear,
+            GpuStoreOp::Store,
+            &blit_texture.create_view(),
+        ));
+        let pass_encoder =
+            command_encoder.begin_render_pass(&GpuRenderPassDescriptor::new(&color_attachments));
+
+        let bind_group_entries = Array::new();
+        bind_group_entries.push(&GpuBindGroupEntry::new(
+            0,
+            match filter {
+                SamplerFilter::Nearest => &self.nearest_sampler,
+                SamplerFilter::Linear => &self.linear_sampler,
+            },
+        ));
+        bind_group_entries.push(&GpuBindGroupEntry::new(1, &src_view));
+        bind_group_entries.push(&GpuBindGroupEntry::new(
+            2,
+            &GpuBufferBinding::new(&region_buffer),
+        ));
+        let bind_group = device.create_bind_group(&GpuBindGroupDescriptor::new(
+            &bind_group_entries,
+            &pipeline.get_bind_group_layout(0),
+        ));
+
+        pass_encoder.set_pipeline(pipeline);
+        pass_encoder.set_bind_group(0, Some(&bind_group));
+        pass_encoder.draw(3);
+        pass_encoder.end();
+
+        command_encoder.copy_texture_to_texture_with_gpu_extent_3d_dict(
+            &GpuImageCopyTexture::new(&blit_texture),
+            &dst.image_copy_texture(dst_region),
+            GpuExtent3dDict::new(dst_region.width as u32).height(dst_region.height as u32),
+        );
+        Ok((blit_texture, region_buffer))
     }
 }
@@ -0,0 +1,37 @@
+use super::*;
+use std::{cell::Cell, rc::Rc};
+
+impl WebGpuContext {
+    pub fn poll_upload(&mut self, upload: UploadId) -> GResult<bool> {
+        if upload.id() >= self.next_upload_id {
+            Err(gpu_api_err!(
+                "webgpu poll upload id {:?} does not exist",
+                upload
+            ))?;
+        }
+        let Some(pending) = self.uploads.get(&upload.id()) else {
+            return Ok(true);
+        };
+        let done = pending.get();
+        if done {
+            self.uploads.remove(&upload.id());
+        }
+        Ok(done)
+    }
+
+    //  `writeBuffer` and `writeTexture` never block, we only need to know when the queue
+    //  has caught up.
+    pub(super) fn track_upload(&mut self) -> UploadId {
+        let done = Rc::new(Cell::new(false));
+        let done_ref = Rc::clone(&done);
+        let work_done = JsFuture::from(self.device.queue().on_submitted_work_done());
+        wasm_bindgen_futures::spawn_local(async move {
+            let _ = work_done.await;
+            done_ref.set(true);
+        });
+        let id = self.next_upload_id;
+        self.next_upload_id += 1;
+        self.uploads.insert(id, done);
+        UploadId::from_id(id)
+    }
+}